@@ -0,0 +1,51 @@
+// Rough timings of the B-tree against the red-black tree, for a few fanouts. Run with:
+//  cargo run --release --example b_tree_benchmark
+
+#[path = "../tests/common/mod.rs"]
+mod common;
+
+use common::scrambled_values;
+use trees::b_tree::BTree;
+use trees::red_black_tree::RedBlackTree;
+use trees::search_tree::SearchTree;
+
+use std::time::{Duration, Instant};
+
+const COUNT: u64 = 200_000;
+
+fn time<R>(run: impl FnOnce() -> R) -> (Duration, R) {
+    let start = Instant::now();
+    let result = run();
+    (start.elapsed(), result)
+}
+
+fn bench<S: SearchTree<u64> + Default>(name: &str) {
+    let values: Vec<u64> = scrambled_values(COUNT as i32)
+        .into_iter()
+        .map(|val| val as u64)
+        .collect();
+    let mut tree = S::default();
+
+    let (insert_time, _) = time(|| values.iter().for_each(|val| tree.insert(*val)));
+    let (lookup_time, found) = time(|| values.iter().filter(|val| tree.contains(val)).count());
+    let (scan_time, sum) = time(|| (0..10).map(|_| tree.iter().sum::<u64>()).sum::<u64>());
+    let (remove_time, _) = time(|| values.iter().for_each(|val| tree.remove(val).unwrap()));
+
+    assert_eq!(found as u64, COUNT);
+    assert_eq!(sum, 10 * COUNT * (COUNT - 1) / 2);
+
+    println!(
+        "{:<18} insert {:>8.2?}  lookup {:>8.2?}  10 scans {:>8.2?}  remove {:>8.2?}",
+        name, insert_time, lookup_time, scan_time, remove_time
+    );
+}
+
+fn main() {
+    println!("{} scrambled values\n", COUNT);
+
+    bench::<RedBlackTree<u64>>("RedBlackTree");
+    bench::<BTree<u64, 4>>("BTree<_, 4>");
+    bench::<BTree<u64, 16>>("BTree<_, 16>");
+    bench::<BTree<u64, 64>>("BTree<_, 64>");
+    bench::<BTree<u64, 256>>("BTree<_, 256>");
+}
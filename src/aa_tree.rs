@@ -0,0 +1,245 @@
+use generational_arena::Index;
+use std::collections::HashMap;
+
+use super::base_tree::{BfsIter, DfsIter, InorderIter, InternalBinarySearchTree, Node};
+use super::search_tree::SearchTree;
+use super::tree_errs::NodeNotFoundErr;
+
+use std::cmp::PartialOrd;
+use std::fmt::Display;
+
+// Andersson (AA) trees are red-black trees where only right children may be red. Rather
+//  than colors, every node stores a level: leaves sit at level one, left children are
+//  exactly one level below their parent, and right children may share their parent's
+//  level but never twice in a row. Two operations keep that shape, `skew` and `split`,
+//  which makes for far fewer cases than in a red-black tree, deletion included.
+//  See: https://user.it.uu.se/~arnea/ps/simp.pdf
+#[derive(Default)]
+pub struct AaTree<T: PartialOrd + Display + Default> {
+    bst: InternalBinarySearchTree<T>,
+    levels: HashMap<Index, usize>,
+}
+
+impl<T: PartialOrd + Display + Default> AaTree<T> {
+    pub fn new() -> Self {
+        AaTree {
+            bst: InternalBinarySearchTree::new(),
+            levels: HashMap::new(),
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.bst.nodes.len()
+    }
+
+    pub fn insert(&mut self, item: T) {
+        let leaf = Node {
+            data: item,
+            left: None,
+            right: None,
+            parent: None,
+        };
+
+        let new_leaf_idx = self.bst.insert_node(leaf);
+        self.levels.insert(new_leaf_idx, 1);
+
+        let mut cur_node_opt = self.bst.nodes[new_leaf_idx].parent;
+        while let Some(cur_node_idx) = cur_node_opt {
+            let subtree_root_idx = self.skew(cur_node_idx);
+            let subtree_root_idx = self.split(subtree_root_idx);
+            cur_node_opt = self.bst.nodes[subtree_root_idx].parent;
+        }
+    }
+
+    pub fn contains(&self, item: &T) -> bool {
+        self.bst.find_node_index(item).is_some()
+    }
+
+    // Removal always ends up unlinking a level one node. On the way back up, each node's
+    //  level is lowered to one above its lowest child, then up to three skews and two
+    //  splits put the horizontal links back in order.
+    pub fn remove(&mut self, item: &T) -> Result<(), NodeNotFoundErr> {
+        let node_idx_to_remove = self.bst.find_node_index(item).ok_or(NodeNotFoundErr)?;
+        let (unlinked_idx, parent_idx_opt, _) = self.bst.unlink_node(node_idx_to_remove);
+
+        self.levels.remove(&unlinked_idx);
+        let _ =
+            self.bst.nodes.remove(unlinked_idx).expect(
+                "Exclusive access during mutation ensures that a node exists for every index",
+            );
+
+        let mut cur_node_opt = parent_idx_opt;
+        while let Some(cur_node_idx) = cur_node_opt {
+            self.decrease_level(cur_node_idx);
+
+            let subtree_root_idx = self.skew(cur_node_idx);
+            if let Some(right_idx) = self.bst.nodes[subtree_root_idx].right {
+                let right_idx = self.skew(right_idx);
+                if let Some(right_right_idx) = self.bst.nodes[right_idx].right {
+                    self.skew(right_right_idx);
+                }
+            }
+
+            let subtree_root_idx = self.split(subtree_root_idx);
+            if let Some(right_idx) = self.bst.nodes[subtree_root_idx].right {
+                self.split(right_idx);
+            }
+
+            cur_node_opt = self.bst.nodes[subtree_root_idx].parent;
+        }
+
+        Ok(())
+    }
+
+    // Lowers a node, and a right child on the same level, once a child one level down is gone
+    fn decrease_level(&mut self, node_idx: Index) {
+        let node = &self.bst.nodes[node_idx];
+        let right_idx_opt = node.right;
+        let expected_level = 1 + self
+            .get_node_level(node.left)
+            .min(self.get_node_level(node.right));
+
+        if expected_level < self.levels[&node_idx] {
+            self.levels.insert(node_idx, expected_level);
+
+            if let Some(right_idx) = right_idx_opt {
+                if expected_level < self.levels[&right_idx] {
+                    self.levels.insert(right_idx, expected_level);
+                }
+            }
+        }
+    }
+
+    // Removes a horizontal left link by rotating the left child up, which turns it into a
+    //  horizontal right link. Returns the root of the subtree.
+    fn skew(&mut self, node_idx: Index) -> Index {
+        match self.bst.nodes[node_idx].left {
+            Some(left_idx) if self.levels[&left_idx] == self.levels[&node_idx] => {
+                self.bst.rotate_node_right(left_idx);
+                left_idx
+            }
+            _ => node_idx,
+        }
+    }
+
+    // Removes two horizontal right links in a row by rotating the middle node up a level.
+    //  Returns the root of the subtree.
+    fn split(&mut self, node_idx: Index) -> Index {
+        let right_idx = match self.bst.nodes[node_idx].right {
+            Some(right_idx) => right_idx,
+            None => return node_idx,
+        };
+
+        match self.bst.nodes[right_idx].right {
+            Some(right_right_idx) if self.levels[&right_right_idx] == self.levels[&node_idx] => {
+                self.bst.rotate_node_left(right_idx);
+                self.levels.insert(right_idx, self.levels[&right_idx] + 1);
+                right_idx
+            }
+            _ => node_idx,
+        }
+    }
+
+    fn get_node_level(&self, node_idx_opt: Option<Index>) -> usize {
+        // Terminating nodes sit at level zero
+        node_idx_opt.map_or(0, |node_idx| self.levels[&node_idx])
+    }
+
+    // Checks that values are in order, that every child links back to its parent and that
+    //  levels follow the AA rules: a left child sits one level below its parent, a right
+    //  child at most one level below, and no right grandchild at its grandparent's level.
+    pub fn is_valid(&self) -> bool {
+        if self.levels.len() != self.bst.nodes.len() {
+            return false;
+        }
+
+        if let Some(root_idx) = self.bst.root {
+            if self.bst.nodes[root_idx].parent.is_some() {
+                return false;
+            }
+        }
+
+        let values: Vec<&T> = self.bst.iter().collect();
+        let levels_valid = self.bst.nodes.iter().all(|(node_idx, node)| {
+            let level = self.levels[&node_idx];
+            let left_level = self.get_node_level(node.left);
+            let right_level = self.get_node_level(node.right);
+            let right_right_level = node.right.map_or(0, |right_idx| {
+                self.get_node_level(self.bst.nodes[right_idx].right)
+            });
+
+            let links_valid = node
+                .left
+                .iter()
+                .chain(node.right.iter())
+                .all(|child_idx| self.bst.nodes[*child_idx].parent == Some(node_idx));
+
+            links_valid
+                && left_level + 1 == level
+                && (right_level == level || right_level + 1 == level)
+                && right_right_level < level
+        });
+
+        levels_valid
+            && values.len() == self.bst.nodes.len()
+            && values.windows(2).all(|pair| pair[0] <= pair[1])
+    }
+
+    // Create a new iterator w/ a stack for DFS taversal
+    pub fn dfs_iter(&self) -> DfsIter<'_, T> {
+        self.bst.dfs_iter()
+    }
+
+    // Create a new iterator w/ a queue for BFS traversal
+    pub fn bfs_iter(&self) -> BfsIter<'_, T> {
+        self.bst.bfs_iter()
+    }
+
+    // Create a new iterator that visits values in sorted order
+    pub fn iter(&self) -> InorderIter<'_, T> {
+        self.bst.iter()
+    }
+}
+
+impl<T: PartialOrd + Display + Default> SearchTree<T> for AaTree<T> {
+    type Iter<'a>
+        = InorderIter<'a, T>
+    where
+        T: 'a;
+
+    fn insert(&mut self, item: T) {
+        AaTree::insert(self, item)
+    }
+
+    fn remove(&mut self, item: &T) -> Result<(), NodeNotFoundErr> {
+        AaTree::remove(self, item)
+    }
+
+    fn contains(&self, item: &T) -> bool {
+        AaTree::contains(self, item)
+    }
+
+    fn len(&self) -> usize {
+        self.get_size()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.bst.iter()
+    }
+
+    fn min(&self) -> Option<&T> {
+        self.bst.get_min()
+    }
+
+    fn max(&self) -> Option<&T> {
+        self.bst.get_max()
+    }
+
+    fn successor(&self, item: &T) -> Option<&T> {
+        self.bst.get_successor(item)
+    }
+
+    fn predecessor(&self, item: &T) -> Option<&T> {
+        self.bst.get_predecessor(item)
+    }
+}
@@ -0,0 +1,257 @@
+use generational_arena::Index;
+use std::collections::HashMap;
+
+use super::base_tree::{BfsIter, DfsIter, InorderIter, InternalBinarySearchTree, Node};
+use super::search_tree::SearchTree;
+use super::tree_errs::NodeNotFoundErr;
+
+use std::cmp::PartialOrd;
+use std::fmt::Display;
+
+// AVL trees keep the heights of every node's two subtrees within one of each other,
+//  which bounds the tree's height to ~1.44 log(n), a little tighter than red-black trees.
+#[derive(Default)]
+pub struct AvlTree<T: PartialOrd + Display + Default> {
+    bst: InternalBinarySearchTree<T>,
+    heights: HashMap<Index, usize>,
+}
+
+impl<T: PartialOrd + Display + Default> AvlTree<T> {
+    pub fn new() -> Self {
+        AvlTree {
+            bst: InternalBinarySearchTree::new(),
+            heights: HashMap::new(),
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.bst.nodes.len()
+    }
+
+    pub fn insert(&mut self, item: T) {
+        let leaf = Node {
+            data: item,
+            left: None,
+            right: None,
+            parent: None,
+        };
+
+        let new_leaf_idx = self.bst.insert_node(leaf);
+        self.heights.insert(new_leaf_idx, 1);
+        self.rebalance_from(self.bst.nodes[new_leaf_idx].parent);
+    }
+
+    pub fn contains(&self, item: &T) -> bool {
+        self.bst.find_node_index(item).is_some()
+    }
+
+    pub fn remove(&mut self, item: &T) -> Result<(), NodeNotFoundErr> {
+        let node_idx_to_remove = self.bst.find_node_index(item).ok_or(NodeNotFoundErr)?;
+        let (unlinked_idx, parent_idx_opt, _) = self.bst.unlink_node(node_idx_to_remove);
+
+        self.heights.remove(&unlinked_idx);
+        let _ =
+            self.bst.nodes.remove(unlinked_idx).expect(
+                "Exclusive access during mutation ensures that a node exists for every index",
+            );
+
+        self.rebalance_from(parent_idx_opt);
+        Ok(())
+    }
+
+    // Walks from a node up to the root, refreshing heights and rotating any node whose
+    //  subtrees' heights have drifted more than one apart. Insertions only ever need a
+    //  single (double) rotation, but removals can require one at every level.
+    fn rebalance_from(&mut self, node_idx_opt: Option<Index>) {
+        let mut cur_node_opt = node_idx_opt;
+
+        while let Some(cur_node_idx) = cur_node_opt {
+            self.update_height(cur_node_idx);
+            let subtree_root_idx = self.rebalance_node(cur_node_idx);
+            cur_node_opt = self.bst.nodes[subtree_root_idx].parent;
+        }
+    }
+
+    // Rotates a single unbalanced node back into shape, returning the root of its subtree
+    fn rebalance_node(&mut self, node_idx: Index) -> Index {
+        let node = &self.bst.nodes[node_idx];
+        let (left_idx_opt, right_idx_opt) = (node.left, node.right);
+        let balance = self.get_balance(node_idx);
+
+        if balance > 1 {
+            let left_idx = left_idx_opt.expect("A left-heavy node has a left child");
+
+            // Left-right case: first rotate the inner grandchild above the left child
+            if self.get_balance(left_idx) < 0 {
+                let inner_idx = self.bst.nodes[left_idx]
+                    .right
+                    .expect("A right-heavy node has a right child");
+                self.bst.rotate_node_left(inner_idx);
+                self.update_height(left_idx);
+                self.update_height(inner_idx);
+            }
+
+            let new_root_idx = self.bst.nodes[node_idx]
+                .left
+                .expect("A left-heavy node has a left child");
+            self.bst.rotate_node_right(new_root_idx);
+            self.update_height(node_idx);
+            self.update_height(new_root_idx);
+
+            return new_root_idx;
+        }
+
+        if balance < -1 {
+            let right_idx = right_idx_opt.expect("A right-heavy node has a right child");
+
+            // Right-left case: mirror of the left-right case
+            if self.get_balance(right_idx) > 0 {
+                let inner_idx = self.bst.nodes[right_idx]
+                    .left
+                    .expect("A left-heavy node has a left child");
+                self.bst.rotate_node_right(inner_idx);
+                self.update_height(right_idx);
+                self.update_height(inner_idx);
+            }
+
+            let new_root_idx = self.bst.nodes[node_idx]
+                .right
+                .expect("A right-heavy node has a right child");
+            self.bst.rotate_node_left(new_root_idx);
+            self.update_height(node_idx);
+            self.update_height(new_root_idx);
+
+            return new_root_idx;
+        }
+
+        node_idx
+    }
+
+    fn get_node_height(&self, node_idx_opt: Option<Index>) -> usize {
+        // Terminating nodes have a height of zero
+        node_idx_opt.map_or(0, |node_idx| self.heights[&node_idx])
+    }
+
+    fn update_height(&mut self, node_idx: Index) {
+        let node = &self.bst.nodes[node_idx];
+        let height = 1 + self
+            .get_node_height(node.left)
+            .max(self.get_node_height(node.right));
+        self.heights.insert(node_idx, height);
+    }
+
+    // Positive when the left subtree is taller, negative when the right one is
+    fn get_balance(&self, node_idx: Index) -> isize {
+        let node = &self.bst.nodes[node_idx];
+        self.get_node_height(node.left) as isize - self.get_node_height(node.right) as isize
+    }
+
+    // Height of the whole tree, counting the root as one
+    pub fn get_height(&self) -> usize {
+        self.get_node_height(self.bst.root)
+    }
+
+    // Checks that values are in order, that every child links back to its parent, that the
+    //  cached height of each node matches its subtrees and that no two sibling subtrees
+    //  differ in height by more than one. The height map has to hold one entry per node.
+    pub fn is_valid(&self) -> bool {
+        if self.heights.len() != self.bst.nodes.len() {
+            return false;
+        }
+
+        if let Some(root_idx) = self.bst.root {
+            if self.bst.nodes[root_idx].parent.is_some() {
+                return false;
+            }
+        }
+
+        let values: Vec<&T> = self.bst.iter().collect();
+        values.len() == self.bst.nodes.len()
+            && values.windows(2).all(|pair| pair[0] <= pair[1])
+            && self.get_valid_height(self.bst.root).is_some()
+    }
+
+    // Recursive function that returns a subtree's height, or None if anything under it
+    //  is out of balance or out of date
+    fn get_valid_height(&self, node_idx_opt: Option<Index>) -> Option<usize> {
+        let node_idx = match node_idx_opt {
+            Some(node_idx) => node_idx,
+            None => return Some(0),
+        };
+
+        let node = &self.bst.nodes[node_idx];
+        for child_idx in node.left.iter().chain(node.right.iter()) {
+            if self.bst.nodes[*child_idx].parent != node_idx_opt {
+                return None;
+            }
+        }
+
+        let left_height = self.get_valid_height(node.left)?;
+        let right_height = self.get_valid_height(node.right)?;
+        let height = 1 + left_height.max(right_height);
+
+        if left_height.abs_diff(right_height) > 1 || self.heights[&node_idx] != height {
+            return None;
+        }
+
+        Some(height)
+    }
+
+    // Create a new iterator w/ a stack for DFS taversal
+    pub fn dfs_iter(&self) -> DfsIter<'_, T> {
+        self.bst.dfs_iter()
+    }
+
+    // Create a new iterator w/ a queue for BFS traversal
+    pub fn bfs_iter(&self) -> BfsIter<'_, T> {
+        self.bst.bfs_iter()
+    }
+
+    // Create a new iterator that visits values in sorted order
+    pub fn iter(&self) -> InorderIter<'_, T> {
+        self.bst.iter()
+    }
+}
+
+impl<T: PartialOrd + Display + Default> SearchTree<T> for AvlTree<T> {
+    type Iter<'a>
+        = InorderIter<'a, T>
+    where
+        T: 'a;
+
+    fn insert(&mut self, item: T) {
+        AvlTree::insert(self, item)
+    }
+
+    fn remove(&mut self, item: &T) -> Result<(), NodeNotFoundErr> {
+        AvlTree::remove(self, item)
+    }
+
+    fn contains(&self, item: &T) -> bool {
+        AvlTree::contains(self, item)
+    }
+
+    fn len(&self) -> usize {
+        self.get_size()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.bst.iter()
+    }
+
+    fn min(&self) -> Option<&T> {
+        self.bst.get_min()
+    }
+
+    fn max(&self) -> Option<&T> {
+        self.bst.get_max()
+    }
+
+    fn successor(&self, item: &T) -> Option<&T> {
+        self.bst.get_successor(item)
+    }
+
+    fn predecessor(&self, item: &T) -> Option<&T> {
+        self.bst.get_predecessor(item)
+    }
+}
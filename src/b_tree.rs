@@ -0,0 +1,439 @@
+use super::search_tree::SearchTree;
+use super::tree_errs::NodeNotFoundErr;
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::PartialOrd;
+use core::fmt::Display;
+use core::mem::replace;
+use core::ops::{Bound, RangeBounds};
+
+// B-trees store a sorted run of keys in every node, so each node has up to `B` children
+//  instead of two. With a large enough fanout, the keys touched by a search or a scan sit
+//  next to each other in memory, and the tree is only log_B(n) levels deep.
+//
+// Nodes own their children directly instead of going through the arena the other trees
+//  share, since the whole point is to avoid looking up every node by `Index`. Splits and
+//  merges happen on the way back up from the leaves, which works for any fanout of three
+//  or more. Like the binary trees, duplicate values are allowed.
+pub struct BTree<T: PartialOrd + Display + Default, const B: usize> {
+    root: BTreeNode<T>,
+    len: usize,
+}
+
+struct BTreeNode<T> {
+    keys: Vec<T>,
+    // Empty for leaves, otherwise one more child than there are keys
+    children: Vec<BTreeNode<T>>,
+}
+
+impl<T> BTreeNode<T> {
+    fn new() -> Self {
+        BTreeNode {
+            keys: Vec::new(),
+            children: Vec::new(),
+        }
+    }
+
+    fn is_leaf(&self) -> bool {
+        self.children.is_empty()
+    }
+}
+
+impl<T: PartialOrd + Display + Default, const B: usize> Default for BTree<T, B> {
+    fn default() -> Self {
+        BTree::new()
+    }
+}
+
+impl<T: PartialOrd + Display + Default, const B: usize> BTree<T, B> {
+    // Fails to compile for fanouts below three, which can't be split into two valid nodes
+    const VALID_FANOUT: () = assert!(B >= 3, "B-trees need a fanout of at least three");
+
+    // Every node but the root holds between MIN_KEYS and MAX_KEYS keys
+    const MAX_KEYS: usize = B - 1;
+    const MIN_KEYS: usize = B.div_ceil(2) - 1;
+
+    pub fn new() -> Self {
+        let () = Self::VALID_FANOUT;
+
+        BTree {
+            root: BTreeNode::new(),
+            len: 0,
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.len
+    }
+
+    pub fn insert(&mut self, item: T) {
+        if let Some((median, right_node)) = Self::insert_into(&mut self.root, item) {
+            // The root itself overflowed, so the tree grows a level
+            let left_node = replace(&mut self.root, BTreeNode::new());
+            self.root.keys.push(median);
+            self.root.children = vec![left_node, right_node];
+        }
+
+        self.len += 1;
+    }
+
+    // Recursive function that inserts a value after any equal values in the subtree. When
+    //  the node overflows, it's split in two and the median key and new right node are
+    //  handed back for the parent to link in.
+    fn insert_into(node: &mut BTreeNode<T>, item: T) -> Option<(T, BTreeNode<T>)> {
+        let pos = node.keys.partition_point(|key| *key <= item);
+
+        if node.is_leaf() {
+            node.keys.insert(pos, item);
+        } else if let Some((median, right_node)) = Self::insert_into(&mut node.children[pos], item)
+        {
+            node.keys.insert(pos, median);
+            node.children.insert(pos + 1, right_node);
+        }
+
+        if node.keys.len() <= Self::MAX_KEYS {
+            return None;
+        }
+
+        let mid = node.keys.len() / 2;
+        let right_keys = node.keys.split_off(mid + 1);
+        let median = node
+            .keys
+            .pop()
+            .expect("An overflowing node holds a median key");
+        let right_children = if node.is_leaf() {
+            Vec::new()
+        } else {
+            node.children.split_off(mid + 1)
+        };
+
+        Some((
+            median,
+            BTreeNode {
+                keys: right_keys,
+                children: right_children,
+            },
+        ))
+    }
+
+    pub fn contains(&self, item: &T) -> bool {
+        let mut cur_node = &self.root;
+
+        loop {
+            let pos = cur_node.keys.partition_point(|key| key < item);
+            if pos < cur_node.keys.len() && cur_node.keys[pos] == *item {
+                return true;
+            }
+
+            if cur_node.is_leaf() {
+                return false;
+            }
+            cur_node = &cur_node.children[pos];
+        }
+    }
+
+    pub fn remove(&mut self, item: &T) -> Result<(), NodeNotFoundErr> {
+        if !Self::remove_from(&mut self.root, item) {
+            return Err(NodeNotFoundErr);
+        }
+
+        // The root's last key was merged into its only child, so the tree shrinks a level
+        if self.root.keys.is_empty() {
+            if let Some(child) = self.root.children.pop() {
+                self.root = child;
+            }
+        }
+
+        self.len -= 1;
+        Ok(())
+    }
+
+    // Recursive function that removes one key equal to `item` from the subtree, returning
+    //  whether one was found. Keys in internal nodes are replaced by their predecessor,
+    //  so keys are only ever taken out of leaves.
+    fn remove_from(node: &mut BTreeNode<T>, item: &T) -> bool {
+        let pos = node.keys.partition_point(|key| key < item);
+        let found = pos < node.keys.len() && node.keys[pos] == *item;
+
+        if node.is_leaf() {
+            if found {
+                node.keys.remove(pos);
+            }
+            return found;
+        }
+
+        let removed = if found {
+            node.keys[pos] = Self::remove_max(&mut node.children[pos]);
+            true
+        } else {
+            Self::remove_from(&mut node.children[pos], item)
+        };
+
+        if removed {
+            Self::fix_underflow(node, pos);
+        }
+        removed
+    }
+
+    // Recursive function that removes and returns the largest key of the subtree
+    fn remove_max(node: &mut BTreeNode<T>) -> T {
+        if node.is_leaf() {
+            return node.keys.pop().expect("Non-root nodes are never empty");
+        }
+
+        let last_idx = node.children.len() - 1;
+        let max = Self::remove_max(&mut node.children[last_idx]);
+        Self::fix_underflow(node, last_idx);
+        max
+    }
+
+    // Tops a child back up to MIN_KEYS keys after a removal, by borrowing a key through
+    //  the parent from a sibling that can spare one, or otherwise by merging the child
+    //  with a sibling and the key separating them.
+    fn fix_underflow(node: &mut BTreeNode<T>, child_idx: usize) {
+        if node.children[child_idx].keys.len() >= Self::MIN_KEYS {
+            return;
+        }
+
+        if child_idx > 0 && node.children[child_idx - 1].keys.len() > Self::MIN_KEYS {
+            let (left_children, right_children) = node.children.split_at_mut(child_idx);
+            let left_node = &mut left_children[child_idx - 1];
+            let child = &mut right_children[0];
+
+            let borrowed_key = left_node.keys.pop().expect("The sibling can spare a key");
+            let separator = replace(&mut node.keys[child_idx - 1], borrowed_key);
+            child.keys.insert(0, separator);
+            if let Some(grandchild) = left_node.children.pop() {
+                child.children.insert(0, grandchild);
+            }
+        } else if child_idx + 1 < node.children.len()
+            && node.children[child_idx + 1].keys.len() > Self::MIN_KEYS
+        {
+            let (left_children, right_children) = node.children.split_at_mut(child_idx + 1);
+            let child = &mut left_children[child_idx];
+            let right_node = &mut right_children[0];
+
+            let borrowed_key = right_node.keys.remove(0);
+            let separator = replace(&mut node.keys[child_idx], borrowed_key);
+            child.keys.push(separator);
+            if !right_node.is_leaf() {
+                child.children.push(right_node.children.remove(0));
+            }
+        } else {
+            // Neither sibling can spare a key, so together they fit in a single node
+            let left_idx = child_idx.saturating_sub(1);
+            let right_node = node.children.remove(left_idx + 1);
+            let separator = node.keys.remove(left_idx);
+
+            let left_node = &mut node.children[left_idx];
+            left_node.keys.push(separator);
+            left_node.keys.extend(right_node.keys);
+            left_node.children.extend(right_node.children);
+        }
+    }
+
+    // Visits, in order, the values that fall within `range`
+    pub fn range(&self, range: impl RangeBounds<T>) -> BTreeIter<'_, T> {
+        let last = self.get_last_within(range.end_bound());
+        let mut iter = BTreeIter {
+            node_stack: Vec::new(),
+            last,
+        };
+
+        // Walk down to the first value within the start bound, leaving every node on
+        //  the way positioned at the next key it should yield
+        let mut cur_node_opt = Some(&self.root);
+        while let Some(cur_node) = cur_node_opt {
+            let pos = cur_node
+                .keys
+                .partition_point(|key| match range.start_bound() {
+                    Bound::Included(start) => key < start,
+                    Bound::Excluded(start) => key <= start,
+                    Bound::Unbounded => false,
+                });
+
+            iter.node_stack.push((cur_node, pos));
+            cur_node_opt = cur_node.children.get(pos);
+        }
+
+        // The first value in range coming after the last one means the range is empty
+        let first = self.get_first_within(range.start_bound());
+        if first.is_none() || last.is_none() || first > last {
+            iter.node_stack.clear();
+        }
+
+        iter
+    }
+
+    // Finds the first value that falls within a start bound
+    fn get_first_within(&self, start: Bound<&T>) -> Option<&T> {
+        let mut first = None;
+        let mut cur_node_opt = Some(&self.root);
+
+        while let Some(cur_node) = cur_node_opt {
+            let pos = cur_node.keys.partition_point(|key| match start {
+                Bound::Included(start) => key < start,
+                Bound::Excluded(start) => key <= start,
+                Bound::Unbounded => false,
+            });
+
+            // Anything further down in the previous child comes before this key
+            if pos < cur_node.keys.len() {
+                first = Some(&cur_node.keys[pos]);
+            }
+            cur_node_opt = cur_node.children.get(pos);
+        }
+
+        first
+    }
+
+    // Finds the last value that falls within an end bound
+    fn get_last_within(&self, end: Bound<&T>) -> Option<&T> {
+        let mut last = None;
+        let mut cur_node_opt = Some(&self.root);
+
+        while let Some(cur_node) = cur_node_opt {
+            let pos = cur_node.keys.partition_point(|key| match end {
+                Bound::Included(end) => key <= end,
+                Bound::Excluded(end) => key < end,
+                Bound::Unbounded => true,
+            });
+
+            // Anything further down in the next child comes after this key
+            if pos > 0 {
+                last = Some(&cur_node.keys[pos - 1]);
+            }
+            cur_node_opt = cur_node.children.get(pos);
+        }
+
+        last
+    }
+
+    // Create a new iterator that visits values in sorted order
+    pub fn iter(&self) -> BTreeIter<'_, T> {
+        self.range(..)
+    }
+
+    // Checks every B-tree invariant: sorted keys, key counts within bounds, one more
+    //  child than keys in internal nodes and every leaf at the same depth.
+    pub fn is_valid(&self) -> bool {
+        let values: Vec<&T> = self.iter().collect();
+
+        values.len() == self.len
+            && values.windows(2).all(|pair| pair[0] <= pair[1])
+            && self.root.keys.len() <= Self::MAX_KEYS
+            && (self.root.is_leaf() || !self.root.keys.is_empty())
+            && Self::get_valid_depth(&self.root).is_some()
+    }
+
+    // Recursive function that returns the depth of the subtree's leaves, or None if they
+    //  aren't all at the same depth or any node below the root holds the wrong number of keys
+    fn get_valid_depth(node: &BTreeNode<T>) -> Option<usize> {
+        if node.is_leaf() {
+            return Some(1);
+        }
+
+        if node.children.len() != node.keys.len() + 1 {
+            return None;
+        }
+
+        let mut depth = None;
+        for child in &node.children {
+            if child.keys.len() < Self::MIN_KEYS || child.keys.len() > Self::MAX_KEYS {
+                return None;
+            }
+
+            let child_depth = Self::get_valid_depth(child)?;
+            if depth.is_some_and(|depth| depth != child_depth) {
+                return None;
+            }
+            depth = Some(child_depth);
+        }
+
+        depth.map(|depth| depth + 1)
+    }
+}
+
+// Walks the tree in order with a stack holding each node on the current path, along with
+//  the index of the next key to yield from it. Stops after yielding `last`, which is
+//  compared by address so that runs of equal values are cut off at the right spot.
+pub struct BTreeIter<'a, T> {
+    node_stack: Vec<(&'a BTreeNode<T>, usize)>,
+    last: Option<&'a T>,
+}
+
+impl<'a, T> Iterator for BTreeIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (node, key_idx) = self.node_stack.last_mut()?;
+            let node: &'a BTreeNode<T> = node;
+
+            if *key_idx >= node.keys.len() {
+                self.node_stack.pop();
+                continue;
+            }
+
+            let key = &node.keys[*key_idx];
+            *key_idx += 1;
+
+            // Everything in the child after this key comes next, starting from its leftmost leaf
+            let mut child_opt = node.children.get(*key_idx);
+            while let Some(child) = child_opt {
+                self.node_stack.push((child, 0));
+                child_opt = child.children.first();
+            }
+
+            if self.last.is_some_and(|last| core::ptr::eq(last, key)) {
+                self.node_stack.clear();
+            }
+
+            return Some(key);
+        }
+    }
+}
+
+impl<T: PartialOrd + Display + Default, const B: usize> SearchTree<T> for BTree<T, B> {
+    type Iter<'a>
+        = BTreeIter<'a, T>
+    where
+        T: 'a;
+
+    fn insert(&mut self, item: T) {
+        BTree::insert(self, item)
+    }
+
+    fn remove(&mut self, item: &T) -> Result<(), NodeNotFoundErr> {
+        BTree::remove(self, item)
+    }
+
+    fn contains(&self, item: &T) -> bool {
+        BTree::contains(self, item)
+    }
+
+    fn len(&self) -> usize {
+        self.get_size()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        BTree::iter(self)
+    }
+
+    fn min(&self) -> Option<&T> {
+        self.get_first_within(Bound::Unbounded)
+    }
+
+    fn max(&self) -> Option<&T> {
+        self.get_last_within(Bound::Unbounded)
+    }
+
+    fn successor(&self, item: &T) -> Option<&T> {
+        self.range((Bound::Excluded(item), Bound::Unbounded)).next()
+    }
+
+    fn predecessor(&self, item: &T) -> Option<&T> {
+        self.get_last_within(Bound::Excluded(item))
+    }
+}
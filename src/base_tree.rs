@@ -1,9 +1,15 @@
-use generational_arena::{Arena, Index};
-use std::collections::VecDeque;
-use std::fmt::Display;
-use std::mem::take;
-
-#[derive(Default, Debug)]
+use super::node_store::{ArenaStore, NodeStore};
+
+use alloc::collections::VecDeque;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::fmt::Display;
+use core::marker::PhantomData;
+use core::mem::{swap, take};
+use core::ops::{Bound, RangeBounds};
+use generational_arena::Index;
+
+#[derive(Default, Debug, Clone)]
 pub struct Node<T: Display> {
     pub data: T,
     pub left: Option<Index>,
@@ -11,12 +17,12 @@ pub struct Node<T: Display> {
     pub parent: Option<Index>, // Optional for doubly-linked trees
 }
 
-pub type BoxedNode<T> = Box<Node<T>>;
-
-#[derive(Default)]
-pub struct InternalBinarySearchTree<T: Display + PartialOrd + Default> {
+// Nodes live in a `NodeStore`, the generational arena unless another backend is picked
+#[derive(Default, Clone)]
+pub struct InternalBinarySearchTree<T: Display + PartialOrd + Default, S = ArenaStore<T>> {
     pub root: Option<Index>,
-    pub nodes: Arena<BoxedNode<T>>,
+    pub nodes: S,
+    _values: PhantomData<T>,
 }
 
 // Enum used to signal whether a node is the left or right child for
@@ -29,14 +35,50 @@ pub enum ChildSide {
 
 // The copy trait facilitates easier node removal by allowing us to
 //  copy the contents of
-impl<T: Display + PartialOrd + Default> InternalBinarySearchTree<T> {
+impl<T: Display + PartialOrd + Default, S: NodeStore<T>> InternalBinarySearchTree<T, S> {
     pub fn new() -> Self {
         InternalBinarySearchTree {
             root: None,
-            nodes: Arena::new(),
+            nodes: S::default(),
+            _values: PhantomData,
+        }
+    }
+
+    // Create a new iterator w/ a stack for DFS taversal
+    pub fn dfs_iter(&self) -> DfsIter<'_, T, S> {
+        let mut node_idx_stack = Vec::new();
+
+        if let Some(root_idx) = self.root {
+            node_idx_stack.push(root_idx);
+        }
+
+        DfsIter {
+            node_idx_stack,
+            nodes: &self.nodes,
+            _values: PhantomData,
+        }
+    }
+
+    // Create a new iterator w/ a queue for BFS traversal
+    pub fn bfs_iter(&self) -> BfsIter<'_, T, S> {
+        let mut node_idx_queue = VecDeque::new();
+
+        if let Some(root_idx) = self.root {
+            node_idx_queue.push_front(root_idx);
+        }
+
+        BfsIter {
+            node_idx_queue,
+            nodes: &self.nodes,
+            _values: PhantomData,
         }
     }
 
+    // Create a new iterator w/ a stack for in-order traversal
+    pub fn iter(&self) -> InorderIter<'_, T, S> {
+        InorderIter::new(self.root, &self.nodes)
+    }
+
     pub fn insert_node(&mut self, mut new_leaf: Node<T>) -> Index {
         let mut cur_idx_option = self.root;
         let mut child_side = ChildSide::Right;
@@ -64,7 +106,7 @@ impl<T: Display + PartialOrd + Default> InternalBinarySearchTree<T> {
         }
 
         new_leaf.parent = cur_idx_option;
-        let leaf_id = self.nodes.insert(Box::new(new_leaf));
+        let leaf_id = self.nodes.insert(new_leaf);
 
         // Parent node found, so we set it to the corresponding child node
         if let Some(parent_idx) = cur_idx_option {
@@ -120,6 +162,77 @@ impl<T: Display + PartialOrd + Default> InternalBinarySearchTree<T> {
         cur_node_opt
     }
 
+    // Walk left from the given node until we find the smallest node in its subtree
+    pub fn get_subtree_min(&self, node_idx: Index) -> Index {
+        let mut cur_node_idx = node_idx;
+
+        while let Some(left_node_idx) = self.nodes[cur_node_idx].left {
+            cur_node_idx = left_node_idx;
+        }
+
+        cur_node_idx
+    }
+
+    // Walk right from the given node until we find the largest node in its subtree
+    pub fn get_subtree_max(&self, node_idx: Index) -> Index {
+        let mut cur_node_idx = node_idx;
+
+        while let Some(right_node_idx) = self.nodes[cur_node_idx].right {
+            cur_node_idx = right_node_idx;
+        }
+
+        cur_node_idx
+    }
+
+    pub fn get_min(&self) -> Option<&T> {
+        let root_idx = self.root?;
+        Some(&self.nodes[self.get_subtree_min(root_idx)].data)
+    }
+
+    pub fn get_max(&self) -> Option<&T> {
+        let root_idx = self.root?;
+        Some(&self.nodes[self.get_subtree_max(root_idx)].data)
+    }
+
+    // Finds the smallest value strictly greater than `item`, which doesn't need to be in
+    //  the tree. Every time we step left, the node we left from is the best candidate so far.
+    pub fn get_successor(&self, item: &T) -> Option<&T> {
+        let mut successor = None;
+        let mut cur_node_opt = self.root;
+
+        while let Some(cur_node_idx) = cur_node_opt {
+            let cur_node = &self.nodes[cur_node_idx];
+
+            if cur_node.data > *item {
+                successor = Some(&cur_node.data);
+                cur_node_opt = cur_node.left;
+            } else {
+                cur_node_opt = cur_node.right;
+            }
+        }
+
+        successor
+    }
+
+    // Finds the largest value strictly less than `item`, mirroring `get_successor`
+    pub fn get_predecessor(&self, item: &T) -> Option<&T> {
+        let mut predecessor = None;
+        let mut cur_node_opt = self.root;
+
+        while let Some(cur_node_idx) = cur_node_opt {
+            let cur_node = &self.nodes[cur_node_idx];
+
+            if cur_node.data < *item {
+                predecessor = Some(&cur_node.data);
+                cur_node_opt = cur_node.right;
+            } else {
+                cur_node_opt = cur_node.left;
+            }
+        }
+
+        predecessor
+    }
+
     // Recursive function that takes a Tree, a starting "root", and a node index to remove.
     //  Starting at the root, we find the specified node and remove it from the tree.
     //  When removing a node with multiple children, things get a little more complicated.
@@ -140,7 +253,7 @@ impl<T: Display + PartialOrd + Default> InternalBinarySearchTree<T> {
             }
             // Both children exist, so we must find the inorder successor first
             (Some(_), Some(_)) => {
-                let inorder_successor = self.get_inorder_successor(&node_to_remove);
+                let inorder_successor = self.get_inorder_successor(node_to_remove);
 
                 let successor_idx = inorder_successor
                 .expect("Proper tree structure ensures that every node with children has an inorder successor");
@@ -169,8 +282,8 @@ impl<T: Display + PartialOrd + Default> InternalBinarySearchTree<T> {
                     parent_node.right = replacement_idx_opt;
                 }
             } else {
-                // It's the root node, and we nuke the world
-                self.root = None;
+                // It's the root node, so its only child (if any) takes over
+                self.root = replacement_idx_opt;
             }
 
             if let Some(replacement_idx) = replacement_idx_opt {
@@ -184,15 +297,492 @@ impl<T: Display + PartialOrd + Default> InternalBinarySearchTree<T> {
             );
         }
     }
+
+    // When rotating a node right, we set the parent's left child equal to the
+    //  target node's right child. Then we set the parent as the right child
+    //  of the target. Finally we fix all of the parent references, et voila.
+    pub fn rotate_node_right(&mut self, node_idx: Index) {
+        let node = &mut self.nodes[node_idx];
+        let parent_idx = node.parent.expect(
+            "Proper tree structure ensures that a rotation occurs only on nodes with parents",
+        );
+
+        // Make parent node a child of the rotating node
+        let right_node_idx_opt = node.right;
+        node.right = Some(parent_idx);
+
+        // Make parent node a child of the rotating node
+        let parent_node = &mut self.nodes[parent_idx];
+        let grandparent_idx_opt = parent_node.parent;
+
+        // Update parent's 'parent' and 'left' children accordingly
+        parent_node.parent = Some(node_idx);
+        parent_node.left = right_node_idx_opt;
+
+        // Update right child node with new parent idx
+        if let Some(right_node_idx) = right_node_idx_opt {
+            let right_node = &mut self.nodes[right_node_idx];
+            right_node.parent = Some(parent_idx);
+        }
+
+        // Set node's parent to the grandparent
+        self.nodes[node_idx].parent = grandparent_idx_opt;
+
+        // If grandparent exists, we replace its child idx with the parent with the target node
+        if let Some(grandparent_idx) = grandparent_idx_opt {
+            let grandparent_node = &mut self.nodes[grandparent_idx];
+            if grandparent_node.left == Some(parent_idx) {
+                grandparent_node.left = Some(node_idx);
+            } else {
+                grandparent_node.right = Some(node_idx);
+            }
+        } else {
+            self.root = Some(node_idx);
+        }
+    }
+
+    // When rotating a node left, we set the parent's right child equal to the
+    //  target node's left child. Then we set the parent as the left child
+    //  of the target. Finally we fix all of the parent references, et voila.
+    pub fn rotate_node_left(&mut self, node_idx: Index) {
+        let node = &mut self.nodes[node_idx];
+        let parent_idx = node.parent.expect(
+            "Proper tree structure ensures that a rotation occurs only on nodes with parents",
+        );
+
+        let left_node_idx_opt = node.left;
+        node.left = Some(parent_idx);
+
+        let parent_node = &mut self.nodes[parent_idx];
+        let grandparent_idx_opt = parent_node.parent;
+
+        // Update parent's 'parent' and 'left' children accordingly
+        parent_node.parent = Some(node_idx);
+        parent_node.right = left_node_idx_opt;
+
+        // If rotating node had a left child, update its parent index
+        if let Some(left_node_idx) = left_node_idx_opt {
+            let left_node = &mut self.nodes[left_node_idx];
+            left_node.parent = Some(parent_idx);
+        }
+
+        // Set node's parent to the grandparent
+        self.nodes[node_idx].parent = grandparent_idx_opt;
+
+        // If grandparent exists, we replace its child idx with the parent with the target node
+        if let Some(grandparent_idx) = grandparent_idx_opt {
+            let grandparent_node = &mut self.nodes[grandparent_idx];
+            if grandparent_node.left == Some(parent_idx) {
+                grandparent_node.left = Some(node_idx);
+            } else {
+                grandparent_node.right = Some(node_idx);
+            }
+        } else {
+            self.root = Some(node_idx);
+        }
+    }
+
+    // Rotates a node above its parent, whichever side of the parent it's on
+    pub fn rotate_node_up(&mut self, node_idx: Index) {
+        let parent_idx = self.nodes[node_idx].parent.expect(
+            "Proper tree structure ensures that a rotation occurs only on nodes with parents",
+        );
+
+        if self.nodes[parent_idx].left == Some(node_idx) {
+            self.rotate_node_right(node_idx);
+        } else {
+            self.rotate_node_left(node_idx);
+        }
+    }
+
+    // Splices a node out of the tree, but leaves it allocated in the arena so that callers
+    //  can reuse it or read its metadata first. A node with two children first swaps its data
+    //  with its inorder successor, and the successor is unlinked instead. Returns the index of
+    //  the node that was actually unlinked (which now holds the removed value), along with the
+    //  parent it was unlinked from and the child that took its place.
+    //
+    // Only the std-only trees unlink nodes this way
+    #[cfg_attr(not(feature = "std"), allow(dead_code))]
+    pub fn unlink_node(&mut self, node_idx: Index) -> (Index, Option<Index>, Option<Index>) {
+        let mut target_idx = node_idx;
+        let node = &self.nodes[node_idx];
+
+        if node.left.is_some() && node.right.is_some() {
+            let successor_idx = self.get_inorder_successor(node).expect(
+                "Proper tree structure ensures that every node with children has an inorder successor",
+            );
+
+            if let (Some(node), Some(successor)) = self.nodes.get2_mut(node_idx, successor_idx) {
+                swap(&mut node.data, &mut successor.data);
+            }
+            target_idx = successor_idx;
+        }
+
+        // The target has at most one child, which takes its place under the parent
+        let target = &self.nodes[target_idx];
+        let child_idx_opt = target.left.or(target.right);
+        let parent_idx_opt = target.parent;
+
+        if let Some(parent_idx) = parent_idx_opt {
+            let parent_node = &mut self.nodes[parent_idx];
+            if parent_node.left == Some(target_idx) {
+                parent_node.left = child_idx_opt;
+            } else {
+                parent_node.right = child_idx_opt;
+            }
+        } else {
+            self.root = child_idx_opt;
+        }
+
+        if let Some(child_idx) = child_idx_opt {
+            self.nodes[child_idx].parent = parent_idx_opt;
+        }
+
+        let target = &mut self.nodes[target_idx];
+        target.left = None;
+        target.right = None;
+        target.parent = None;
+
+        (target_idx, parent_idx_opt, child_idx_opt)
+    }
+
+    // Counts the nodes in the subtree under the given node
+    pub fn get_subtree_size(&self, node_idx_opt: Option<Index>) -> usize {
+        DfsIter {
+            node_idx_stack: node_idx_opt.into_iter().collect(),
+            nodes: &self.nodes,
+            _values: PhantomData,
+        }
+        .count()
+    }
+
+    // Whether the first subtree holds fewer nodes than the second. Both subtrees are walked
+    //  in lockstep, so this only costs as much as visiting the smaller one.
+    pub fn is_smaller_subtree(
+        &self,
+        first_idx_opt: Option<Index>,
+        second_idx_opt: Option<Index>,
+    ) -> bool {
+        let subtree_iter = |node_idx_opt: Option<Index>| DfsIter {
+            node_idx_stack: node_idx_opt.into_iter().collect(),
+            nodes: &self.nodes,
+            _values: PhantomData,
+        };
+        let mut first_iter = subtree_iter(first_idx_opt);
+        let mut second_iter = subtree_iter(second_idx_opt);
+
+        loop {
+            match (first_iter.next(), second_iter.next()) {
+                (Some(_), Some(_)) => continue,
+                (None, Some(_)) => return true,
+                _ => return false,
+            }
+        }
+    }
+
+    // Number of levels in the tree, walking it one level at a time
+    pub fn get_height(&self) -> usize {
+        let mut height = 0;
+        let mut level: Vec<Index> = self.root.into_iter().collect();
+
+        while !level.is_empty() {
+            height += 1;
+            level = level
+                .iter()
+                .flat_map(|node_idx| {
+                    let node = &self.nodes[*node_idx];
+                    node.left.into_iter().chain(node.right)
+                })
+                .collect();
+        }
+
+        height
+    }
+
+    // Relinks the existing nodes of the subtree under `root_idx` into a perfectly balanced
+    //  shape, in place of the old subtree. Returns the index of the subtree's new root.
+    pub fn rebuild_subtree(&mut self, root_idx: Index) -> Index {
+        let parent_idx_opt = self.nodes[root_idx].parent;
+        let mut node_idxs = Vec::new();
+        let mut inorder_iter = InorderIter::new(Some(root_idx), &self.nodes);
+
+        while let Some(node_idx) = inorder_iter.next_index() {
+            node_idxs.push(node_idx);
+        }
+
+        let new_root_idx = self
+            .link_balanced(&node_idxs)
+            .expect("A subtree always holds at least its own root");
+        self.nodes[new_root_idx].parent = parent_idx_opt;
+
+        match parent_idx_opt {
+            Some(parent_idx) => {
+                let parent_node = &mut self.nodes[parent_idx];
+                if parent_node.left == Some(root_idx) {
+                    parent_node.left = Some(new_root_idx);
+                } else {
+                    parent_node.right = Some(new_root_idx);
+                }
+            }
+            None => self.root = Some(new_root_idx),
+        }
+
+        new_root_idx
+    }
+
+    // Recursive function that links a sorted run of nodes into a balanced subtree, using
+    //  the middle node as the root. Returns the subtree's root, with its parent left unset.
+    fn link_balanced(&mut self, node_idxs: &[Index]) -> Option<Index> {
+        if node_idxs.is_empty() {
+            return None;
+        }
+
+        let mid = (node_idxs.len() - 1) / 2;
+        let node_idx = node_idxs[mid];
+        let left_node_opt = self.link_balanced(&node_idxs[..mid]);
+        let right_node_opt = self.link_balanced(&node_idxs[mid + 1..]);
+
+        let node = &mut self.nodes[node_idx];
+        node.left = left_node_opt;
+        node.right = right_node_opt;
+
+        for child_idx in left_node_opt.iter().chain(right_node_opt.iter()) {
+            self.nodes[*child_idx].parent = Some(node_idx);
+        }
+
+        Some(node_idx)
+    }
+
+    // Splits the subtree under `root_idx` into two detached subtrees: one holding every
+    //  value less than `key`, and one holding the rest. We walk a single path down from
+    //  the root, hooking each visited node onto the right spine of the lesser tree or the
+    //  left spine of the greater tree, so no node is ever copied or rebalanced.
+    pub fn split_subtree(
+        &mut self,
+        root_idx: Option<Index>,
+        key: &T,
+    ) -> (Option<Index>, Option<Index>) {
+        let mut lesser_root = None;
+        let mut greater_root = None;
+        let mut lesser_hook: Option<Index> = None;
+        let mut greater_hook: Option<Index> = None;
+        let mut cur_node_opt = root_idx;
+
+        while let Some(cur_node_idx) = cur_node_opt {
+            if self.nodes[cur_node_idx].data < *key {
+                // This node and its left subtree are all lesser, keep looking to the right
+                match lesser_hook {
+                    Some(hook_idx) => self.nodes[hook_idx].right = Some(cur_node_idx),
+                    None => lesser_root = Some(cur_node_idx),
+                }
+                self.nodes[cur_node_idx].parent = lesser_hook;
+                lesser_hook = Some(cur_node_idx);
+                cur_node_opt = self.nodes[cur_node_idx].right;
+            } else {
+                match greater_hook {
+                    Some(hook_idx) => self.nodes[hook_idx].left = Some(cur_node_idx),
+                    None => greater_root = Some(cur_node_idx),
+                }
+                self.nodes[cur_node_idx].parent = greater_hook;
+                greater_hook = Some(cur_node_idx);
+                cur_node_opt = self.nodes[cur_node_idx].left;
+            }
+        }
+
+        // The last hooked nodes may still point across the split, so cut them loose
+        if let Some(hook_idx) = lesser_hook {
+            self.nodes[hook_idx].right = None;
+        }
+
+        if let Some(hook_idx) = greater_hook {
+            self.nodes[hook_idx].left = None;
+        }
+
+        (lesser_root, greater_root)
+    }
+
+    // Moves every node in the subtree under `root_idx` out of this store and into `dst`,
+    //  returning the index of the subtree's new root. Nodes are moved as-is, so no values
+    //  are cloned, and `on_move` is handed each (old, new) index pair so that callers can
+    //  carry any per-node metadata along. The caller is responsible for unlinking the
+    //  subtree from its parent beforehand.
+    pub fn move_subtree(
+        &mut self,
+        root_idx: Index,
+        dst: &mut InternalBinarySearchTree<T, S>,
+        mut on_move: impl FnMut(Index, Index),
+    ) -> Index {
+        let mut new_root_idx = None;
+        let mut pending = vec![(root_idx, None, ChildSide::Left)];
+
+        while let Some((node_idx, new_parent_opt, child_side)) = pending.pop() {
+            let mut node = self.nodes.remove(node_idx).expect(
+                "Exclusive access during mutation ensures that a node exists for every index",
+            );
+
+            let left_node_opt = node.left.take();
+            let right_node_opt = node.right.take();
+            node.parent = new_parent_opt;
+
+            let new_node_idx = dst.nodes.insert(node);
+            on_move(node_idx, new_node_idx);
+
+            match new_parent_opt {
+                Some(new_parent_idx) => match child_side {
+                    ChildSide::Left => dst.nodes[new_parent_idx].left = Some(new_node_idx),
+                    ChildSide::Right => dst.nodes[new_parent_idx].right = Some(new_node_idx),
+                },
+                None => new_root_idx = Some(new_node_idx),
+            }
+
+            if let Some(left_node_idx) = left_node_opt {
+                pending.push((left_node_idx, Some(new_node_idx), ChildSide::Left));
+            }
+
+            if let Some(right_node_idx) = right_node_opt {
+                pending.push((right_node_idx, Some(new_node_idx), ChildSide::Right));
+            }
+        }
+
+        new_root_idx.expect("The subtree root is always moved first")
+    }
+
+    // Builds a perfectly balanced subtree out of the next `len` sorted items, returning its
+    //  detached root. Every new node's index and depth is handed to `on_insert`, which lets
+    //  balanced trees decide on any per-node metadata up front.
+    pub fn build_balanced(
+        &mut self,
+        items: &mut impl Iterator<Item = T>,
+        len: usize,
+        depth: usize,
+        on_insert: &mut impl FnMut(Index, usize),
+    ) -> Option<Index> {
+        if len == 0 {
+            return None;
+        }
+
+        // The left half is built first so that items are consumed in order
+        let left_len = (len - 1) / 2;
+        let left_node_opt = self.build_balanced(items, left_len, depth + 1, on_insert);
+
+        let data = items
+            .next()
+            .expect("Callers always supply at least `len` items");
+        let node_idx = self.nodes.insert(Node {
+            data,
+            left: left_node_opt,
+            right: None,
+            parent: None,
+        });
+
+        let right_node_opt = self.build_balanced(items, len - left_len - 1, depth + 1, on_insert);
+        self.nodes[node_idx].right = right_node_opt;
+
+        for child_idx in left_node_opt.iter().chain(right_node_opt.iter()) {
+            self.nodes[*child_idx].parent = Some(node_idx);
+        }
+
+        on_insert(node_idx, depth);
+        Some(node_idx)
+    }
+
+    // Collects the indices of every node whose value falls inside `range`, in sorted order
+    pub fn get_range_indices(&self, range: &impl RangeBounds<T>) -> Vec<Index> {
+        let mut node_idxs = Vec::new();
+        let mut inorder_iter =
+            InorderIter::from_start_bound(self.root, &self.nodes, range.start_bound());
+
+        while let Some(node_idx) = inorder_iter.next_index() {
+            let data = &self.nodes[node_idx].data;
+            let past_end = match range.end_bound() {
+                Bound::Included(end) => data > end,
+                Bound::Excluded(end) => data >= end,
+                Bound::Unbounded => false,
+            };
+
+            if past_end {
+                break;
+            }
+            node_idxs.push(node_idx);
+        }
+
+        node_idxs
+    }
+
+    // Collects the indices of every node whose value fails the `keep` check, in sorted order
+    pub fn get_rejected_indices(&self, mut keep: impl FnMut(&T) -> bool) -> Vec<Index> {
+        let mut node_idxs = Vec::new();
+        let mut inorder_iter = InorderIter::new(self.root, &self.nodes);
+
+        while let Some(node_idx) = inorder_iter.next_index() {
+            if !keep(&self.nodes[node_idx].data) {
+                node_idxs.push(node_idx);
+            }
+        }
+
+        node_idxs
+    }
+
+    // Empties the tree, handing back every value in sorted order
+    pub fn drain_sorted(&mut self) -> Vec<T> {
+        self.drain_sorted_with_indices()
+            .into_iter()
+            .map(|(_, data)| data)
+            .collect()
+    }
+
+    // Empties the tree, handing back every value in sorted order along with the index
+    //  of the node it was taken from
+    pub fn drain_sorted_with_indices(&mut self) -> Vec<(Index, T)> {
+        let mut node_idxs = Vec::with_capacity(self.nodes.len());
+        let mut inorder_iter = InorderIter::new(self.root, &self.nodes);
+
+        while let Some(node_idx) = inorder_iter.next_index() {
+            node_idxs.push(node_idx);
+        }
+
+        self.root = None;
+        node_idxs
+            .into_iter()
+            .map(|node_idx| {
+                let node = self
+                    .nodes
+                    .remove(node_idx)
+                    .expect("Every index in the tree belongs to the arena");
+                (node_idx, node.data)
+            })
+            .collect()
+    }
 }
 
-pub struct DfsIter<'a, T: Display> {
+// Merges two sorted runs into one, keeping values from the first run ahead of equal values
+//  from the second.
+pub fn merge_sorted<T: PartialOrd>(first: Vec<T>, second: Vec<T>) -> Vec<T> {
+    let mut merged = Vec::with_capacity(first.len() + second.len());
+    let mut first_iter = first.into_iter().peekable();
+    let mut second_iter = second.into_iter().peekable();
+
+    while let (Some(first_val), Some(second_val)) = (first_iter.peek(), second_iter.peek()) {
+        if second_val < first_val {
+            merged.extend(second_iter.next());
+        } else {
+            merged.extend(first_iter.next());
+        }
+    }
+
+    merged.extend(first_iter);
+    merged.extend(second_iter);
+    merged
+}
+
+pub struct DfsIter<'a, T: Display, S = ArenaStore<T>> {
     pub node_idx_stack: Vec<Index>,
-    pub nodes: &'a Arena<BoxedNode<T>>,
+    pub nodes: &'a S,
+    _values: PhantomData<&'a T>,
 }
 
 // Iterate through nodes using depth-first traversal
-impl<'a, T: PartialOrd + Display> Iterator for DfsIter<'a, T> {
+impl<'a, T: PartialOrd + Display, S: NodeStore<T>> Iterator for DfsIter<'a, T, S> {
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -211,13 +801,16 @@ impl<'a, T: PartialOrd + Display> Iterator for DfsIter<'a, T> {
     }
 }
 
-pub struct BfsIter<'a, T: Display> {
+pub struct BfsIter<'a, T: Display, S = ArenaStore<T>> {
     pub node_idx_queue: VecDeque<Index>,
-    pub nodes: &'a Arena<BoxedNode<T>>,
+    pub nodes: &'a S,
+    _values: PhantomData<&'a T>,
 }
 
 // Iterate through nodes using breadth-first traversal
-impl<'a, T: std::cmp::PartialOrd + std::fmt::Display> Iterator for BfsIter<'a, T> {
+impl<'a, T: core::cmp::PartialOrd + core::fmt::Display, S: NodeStore<T>> Iterator
+    for BfsIter<'a, T, S>
+{
     type Item = &'a T;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -240,3 +833,78 @@ impl<'a, T: std::cmp::PartialOrd + std::fmt::Display> Iterator for BfsIter<'a, T
         Some(&cur_node.data)
     }
 }
+
+pub struct InorderIter<'a, T: Display, S = ArenaStore<T>> {
+    pub node_idx_stack: Vec<Index>,
+    pub nodes: &'a S,
+    _values: PhantomData<&'a T>,
+}
+
+impl<'a, T: Display, S: NodeStore<T>> InorderIter<'a, T, S> {
+    pub fn new(root: Option<Index>, nodes: &'a S) -> Self {
+        let mut inorder_iter = InorderIter {
+            node_idx_stack: Vec::new(),
+            nodes,
+            _values: PhantomData,
+        };
+
+        inorder_iter.push_left_spine(root);
+        inorder_iter
+    }
+
+    // Stack up a node and all of its left descendants, so the smallest one is on top
+    fn push_left_spine(&mut self, mut node_opt: Option<Index>) {
+        while let Some(node_idx) = node_opt {
+            self.node_idx_stack.push(node_idx);
+            node_opt = self.nodes[node_idx].left;
+        }
+    }
+
+    pub fn next_index(&mut self) -> Option<Index> {
+        let node_idx = self.node_idx_stack.pop()?;
+        self.push_left_spine(self.nodes[node_idx].right);
+
+        Some(node_idx)
+    }
+}
+
+impl<'a, T: PartialOrd + Display, S: NodeStore<T>> InorderIter<'a, T, S> {
+    // Starts the iterator at the first value within `start`. Every subtree that lies
+    //  entirely before the bound is skipped on the way down, rather than visited.
+    pub fn from_start_bound(root: Option<Index>, nodes: &'a S, start: Bound<&T>) -> Self {
+        let mut inorder_iter = InorderIter {
+            node_idx_stack: Vec::new(),
+            nodes,
+            _values: PhantomData,
+        };
+        let mut cur_node_opt = root;
+
+        while let Some(cur_node_idx) = cur_node_opt {
+            let cur_node = &nodes[cur_node_idx];
+            let before_start = match start {
+                Bound::Included(start) => cur_node.data < *start,
+                Bound::Excluded(start) => cur_node.data <= *start,
+                Bound::Unbounded => false,
+            };
+
+            if before_start {
+                cur_node_opt = cur_node.right;
+            } else {
+                inorder_iter.node_idx_stack.push(cur_node_idx);
+                cur_node_opt = cur_node.left;
+            }
+        }
+
+        inorder_iter
+    }
+}
+
+// Iterate through nodes in sorted order
+impl<'a, T: Display, S: NodeStore<T>> Iterator for InorderIter<'a, T, S> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node_idx = self.next_index()?;
+        Some(&self.nodes[node_idx].data)
+    }
+}
@@ -1,24 +1,133 @@
-use super::base_tree::{BfsIter, DfsIter, InternalBinarySearchTree, Node};
-use super::tree_errs::NodeNotFoundErr;
+use generational_arena::Index;
 
-use std::cmp::PartialOrd;
-use std::collections::VecDeque;
-use std::fmt::Display;
+use super::base_tree::{
+    merge_sorted, BfsIter, DfsIter, InorderIter, InternalBinarySearchTree, Node,
+};
+use super::search_tree::SearchTree;
+use super::tree_errs::{InvalidAlphaErr, InvalidProbabilityErr, NodeNotFoundErr};
+
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::{Ordering, PartialOrd};
+use core::fmt::Display;
+use core::mem::{swap, take};
+use core::ops::RangeBounds;
 
 // Public class that wraps the internal Binary Search Tree impl without
 //   leaking any abstractions.
 #[derive(Default)]
 pub struct BinarySearchTree<T: PartialOrd + Display + Default> {
     bst: InternalBinarySearchTree<T>,
+    scapegoat: Option<Scapegoat>,
+}
+
+// Balancing state for trees built in scapegoat mode. A tree with n nodes is allowed to be
+//  log(n) / log(1 / alpha) levels deep, and every subtree's children may each hold at most
+//  alpha of its nodes. `max_size` tracks the largest size since the last full rebuild.
+//  `raw::BinarySearchTree` keeps the same state.
+#[derive(Clone, Copy)]
+pub(crate) struct Scapegoat {
+    pub(crate) alpha: f64,
+    pub(crate) max_size: usize,
+}
+
+impl Scapegoat {
+    // Works out floor(log(size) / log(1 / alpha)) by repeated multiplication, since `core`
+    //  has no logarithms
+    pub(crate) fn get_max_depth(&self, size: usize) -> usize {
+        let mut depth = 0;
+        let mut reach = 1.0 / self.alpha;
+
+        while reach <= size as f64 {
+            depth += 1;
+            reach /= self.alpha;
+        }
+
+        depth
+    }
 }
 
 impl<T: PartialOrd + Display + Default> BinarySearchTree<T> {
     pub fn new() -> Self {
         BinarySearchTree {
             bst: InternalBinarySearchTree::new(),
+            scapegoat: None,
         }
     }
 
+    // Creates a tree that keeps itself balanced scapegoat-style: whenever an insert lands
+    //  too deep, the nearest ancestor whose subtrees are too lopsided is rebuilt perfectly.
+    //  No per-node data is needed. Lower alpha values keep the tree closer to perfectly
+    //  balanced, at the cost of more frequent rebuilds.
+    pub fn with_scapegoat_alpha(alpha: f64) -> Result<Self, InvalidAlphaErr> {
+        if !(0.5..1.0).contains(&alpha) {
+            return Err(InvalidAlphaErr);
+        }
+
+        Ok(BinarySearchTree {
+            bst: InternalBinarySearchTree::new(),
+            scapegoat: Some(Scapegoat { alpha, max_size: 0 }),
+        })
+    }
+
+    // Builds the tree with the lowest expected search cost for a fixed set of values, given
+    //  how likely each one is to be looked up, using Knuth's O(n^2) dynamic programming
+    //  algorithm. Returns the tree along with its expected cost: the number of nodes visited
+    //  by a lookup, averaged over the probabilities. Probabilities don't need to add up to
+    //  one, in which case they're treated as relative weights and the cost is scaled the same way.
+    //  See: https://doi.org/10.1007/BF00264289
+    pub fn from_access_probabilities(
+        items: Vec<(T, f64)>,
+    ) -> Result<(Self, f64), InvalidProbabilityErr> {
+        let mut items = items;
+        let (roots, cost) = plan_optimal_tree(&mut items)?;
+        let len = items.len();
+
+        let mut tree = BinarySearchTree::new();
+        let node_idxs: Vec<Index> = items
+            .into_iter()
+            .map(|(data, _)| {
+                tree.bst.nodes.insert(Node {
+                    data,
+                    left: None,
+                    right: None,
+                    parent: None,
+                })
+            })
+            .collect();
+
+        tree.bst.root = tree.link_optimal(&node_idxs, &roots, 0, len);
+        Ok((tree, cost))
+    }
+
+    // Recursive function that links the nodes for the values in start..end under the root
+    //  picked for that range, returning the subtree's root
+    fn link_optimal(
+        &mut self,
+        node_idxs: &[Index],
+        roots: &[usize],
+        start: usize,
+        end: usize,
+    ) -> Option<Index> {
+        if start == end {
+            return None;
+        }
+
+        let root = roots[start * (node_idxs.len() + 1) + end];
+        let node_idx = node_idxs[root];
+        let left_node_opt = self.link_optimal(node_idxs, roots, start, root);
+        let right_node_opt = self.link_optimal(node_idxs, roots, root + 1, end);
+
+        for child_idx in left_node_opt.iter().chain(right_node_opt.iter()) {
+            self.bst.nodes[*child_idx].parent = Some(node_idx);
+        }
+
+        let node = &mut self.bst.nodes[node_idx];
+        node.left = left_node_opt;
+        node.right = right_node_opt;
+        Some(node_idx)
+    }
+
     pub fn get_size(&self) -> usize {
         self.bst.nodes.len()
     }
@@ -31,7 +140,136 @@ impl<T: PartialOrd + Display + Default> BinarySearchTree<T> {
             parent: None,
         };
 
-        self.bst.insert_node(new_leaf);
+        let new_leaf_idx = self.bst.insert_node(new_leaf);
+        self.rebuild_scapegoat(new_leaf_idx);
+    }
+
+    // Scapegoat mode only: if the new leaf is deeper than alpha allows, walk back up
+    //  towards the root, counting subtree sizes as we go, until we find an ancestor
+    //  with a child holding more than alpha of its nodes. That ancestor is the scapegoat,
+    //  and its subtree gets rebuilt perfectly balanced.
+    fn rebuild_scapegoat(&mut self, new_leaf_idx: Index) {
+        let size = self.get_size();
+        let scapegoat = match self.scapegoat.as_mut() {
+            Some(scapegoat) => scapegoat,
+            None => return,
+        };
+        scapegoat.max_size = scapegoat.max_size.max(size);
+        let (alpha, max_depth) = (scapegoat.alpha, scapegoat.get_max_depth(size));
+
+        let mut depth = 0;
+        let mut cur_node_idx = new_leaf_idx;
+        while let Some(parent_idx) = self.bst.nodes[cur_node_idx].parent {
+            depth += 1;
+            cur_node_idx = parent_idx;
+        }
+
+        if depth <= max_depth {
+            return;
+        }
+
+        let mut child_idx = new_leaf_idx;
+        let mut child_size = 1;
+
+        while let Some(parent_idx) = self.bst.nodes[child_idx].parent {
+            let parent_node = &self.bst.nodes[parent_idx];
+            let sibling_idx_opt = if parent_node.left == Some(child_idx) {
+                parent_node.right
+            } else {
+                parent_node.left
+            };
+            let parent_size = child_size + 1 + self.bst.get_subtree_size(sibling_idx_opt);
+
+            if child_size as f64 > alpha * parent_size as f64 {
+                self.bst.rebuild_subtree(parent_idx);
+                return;
+            }
+
+            child_idx = parent_idx;
+            child_size = parent_size;
+        }
+    }
+
+    // Scapegoat mode only: rebuilds the whole tree once it has shrunk below alpha of its
+    //  largest size since the last full rebuild, or, if the tree may have been deepened
+    //  by something other than an insert, once it's deeper than alpha allows.
+    fn rebuild_if_unbalanced(&mut self, may_have_deepened: bool) {
+        let size = self.get_size();
+        let scapegoat = match self.scapegoat.as_mut() {
+            Some(scapegoat) => scapegoat,
+            None => return,
+        };
+        scapegoat.max_size = scapegoat.max_size.max(size);
+
+        let too_sparse = (size as f64) < scapegoat.alpha * scapegoat.max_size as f64;
+        let too_deep =
+            may_have_deepened && self.bst.get_height() > scapegoat.get_max_depth(size) + 1;
+
+        if too_sparse || too_deep {
+            scapegoat.max_size = size;
+            if let Some(root_idx) = self.bst.root {
+                self.bst.rebuild_subtree(root_idx);
+            }
+        }
+    }
+
+    // Number of levels in the tree
+    pub fn get_height(&self) -> usize {
+        self.bst.get_height()
+    }
+
+    // Reshapes the tree to be perfectly balanced (every level full, apart from the last)
+    //  using the Day-Stout-Warren algorithm. Runs in O(n) time with O(1) extra space,
+    //  since it only rotates the existing nodes around.
+    pub fn rebalance(&mut self) {
+        let size = self.get_size();
+        self.tree_to_vine();
+
+        // Number of nodes in the largest perfect tree that fits, i.e. 2^floor(log2(n + 1)) - 1
+        let mut perfect_size = (1 << (usize::BITS - (size + 1).leading_zeros() - 1)) - 1;
+        self.compress_vine(size - perfect_size);
+
+        while perfect_size > 1 {
+            perfect_size /= 2;
+            self.compress_vine(perfect_size);
+        }
+
+        if let Some(scapegoat) = self.scapegoat.as_mut() {
+            scapegoat.max_size = size;
+        }
+    }
+
+    // Rotates every left child up until the tree is a "vine" of right children in sorted order
+    fn tree_to_vine(&mut self) {
+        let mut cur_node_opt = self.bst.root;
+
+        while let Some(cur_node_idx) = cur_node_opt {
+            match self.bst.nodes[cur_node_idx].left {
+                Some(left_idx) => {
+                    self.bst.rotate_node_right(left_idx);
+                    cur_node_opt = Some(left_idx);
+                }
+                None => cur_node_opt = self.bst.nodes[cur_node_idx].right,
+            }
+        }
+    }
+
+    // Walks down the right spine, rotating every other node left over its parent, `count` times
+    fn compress_vine(&mut self, count: usize) {
+        let mut cur_node_opt = self.bst.root;
+
+        for _ in 0..count {
+            let cur_node_idx = match cur_node_opt {
+                Some(cur_node_idx) => cur_node_idx,
+                None => return,
+            };
+
+            let right_idx = self.bst.nodes[cur_node_idx]
+                .right
+                .expect("The vine holds enough nodes for every compression");
+            self.bst.rotate_node_left(right_idx);
+            cur_node_opt = self.bst.nodes[right_idx].right;
+        }
     }
 
     pub fn contains(&self, item: &T) -> bool {
@@ -41,35 +279,253 @@ impl<T: PartialOrd + Display + Default> BinarySearchTree<T> {
     pub fn remove(&mut self, item: &T) -> Result<(), NodeNotFoundErr> {
         let node_idx_to_remove = self.bst.find_node_index(item).ok_or(NodeNotFoundErr)?;
         self.bst.remove_node(node_idx_to_remove);
+        self.rebuild_if_unbalanced(false);
 
         Ok(())
     }
 
-    // Create a new iterator w/ a stack for DFS taversal
-    pub fn dfs_iter(&mut self) -> DfsIter<T> {
-        let mut node_idx_stack = Vec::new();
+    // Removes every value that fails the `keep` check
+    pub fn retain(&mut self, keep: impl FnMut(&T) -> bool) {
+        let node_idxs = self.bst.get_rejected_indices(keep);
+        self.remove_nodes(node_idxs, drop);
+    }
+
+    // Removes every value within `range`, handing them back in sorted order
+    pub fn drain_range(&mut self, range: impl RangeBounds<T>) -> Vec<T> {
+        let node_idxs = self.bst.get_range_indices(&range);
+        let mut removed = Vec::with_capacity(node_idxs.len());
+
+        self.remove_nodes(node_idxs, |data| removed.push(data));
+        removed.reverse();
+        removed
+    }
 
-        if let Some(root_idx) = self.bst.root {
-            node_idx_stack.push(root_idx);
+    // Removes every value within `range`, returning how many were removed. The values are
+    //  dropped as they come out of the tree rather than collected first.
+    pub fn remove_range(&mut self, range: impl RangeBounds<T>) -> usize {
+        let node_idxs = self.bst.get_range_indices(&range);
+        let removed_count = node_idxs.len();
+
+        self.remove_nodes(node_idxs, drop);
+        removed_count
+    }
+
+    // Removes a sorted run of nodes, handing each removed value to `on_removed`, largest
+    //  first. Removing a node with two children deletes its inorder successor's slot
+    //  instead, and going backwards guarantees that successor was already dealt with, so
+    //  the remaining indices all stay valid.
+    fn remove_nodes(&mut self, node_idxs: Vec<Index>, mut on_removed: impl FnMut(T)) {
+        for node_idx in node_idxs.into_iter().rev() {
+            let data = take(&mut self.bst.nodes[node_idx].data);
+            self.bst.remove_node(node_idx);
+            on_removed(data);
         }
 
-        DfsIter {
-            node_idx_stack,
-            nodes: &self.bst.nodes,
+        self.rebuild_if_unbalanced(false);
+    }
+
+    // Moves every value greater than or equal to `key` into a new tree. The split walks a
+    //  single path down the tree, but every tree owns its own arena, so one of the two
+    //  halves then has to be moved into a new one. Whichever half is smaller gets moved,
+    //  which makes splitting off either end of the tree cheap.
+    pub fn split_off(&mut self, key: &T) -> Self {
+        let mut split_tree = BinarySearchTree {
+            bst: InternalBinarySearchTree::new(),
+            scapegoat: self.scapegoat,
+        };
+        let (lesser_root, greater_root) = self.bst.split_subtree(self.bst.root, key);
+        let greater_is_smaller = self.bst.is_smaller_subtree(greater_root, lesser_root);
+        let (kept_root, moved_root) = if greater_is_smaller {
+            (lesser_root, greater_root)
+        } else {
+            (greater_root, lesser_root)
+        };
+
+        self.bst.root = kept_root;
+        if let Some(moved_root_idx) = moved_root {
+            let split_root_idx =
+                self.bst
+                    .move_subtree(moved_root_idx, &mut split_tree.bst, |_, _| {});
+            split_tree.bst.root = Some(split_root_idx);
         }
+
+        // The lesser half was the one moved, so trade arenas to keep it in this tree
+        if !greater_is_smaller {
+            swap(&mut self.bst, &mut split_tree.bst);
+        }
+
+        // Splitting never makes a node deeper, but either half may now be too sparse
+        if let Some(scapegoat) = split_tree.scapegoat.as_mut() {
+            scapegoat.max_size = 0;
+        }
+        split_tree.rebuild_if_unbalanced(false);
+        self.rebuild_if_unbalanced(false);
+
+        split_tree
     }
 
-    // Create a new iterator w/ a queue for BFS traversal
-    pub fn bfs_iter(&mut self) -> BfsIter<T> {
-        let mut node_idx_queue = VecDeque::new();
+    // Moves every value out of `other` and into this tree. When the two trees don't overlap,
+    //  the smaller one is hung off the edge of the larger one as-is. Otherwise both trees
+    //  are merged in sorted order and rebuilt as a single balanced tree.
+    pub fn append(&mut self, other: &mut Self) {
+        if other.bst.root.is_none() {
+            return;
+        }
 
-        if let Some(root_idx) = self.bst.root {
-            node_idx_queue.push_front(root_idx);
+        // Always move the smaller tree's nodes across arenas
+        if self.get_size() < other.get_size() {
+            swap(&mut self.bst, &mut other.bst);
         }
 
-        BfsIter {
-            node_idx_queue,
-            nodes: &self.bst.nodes,
+        let (root_idx, other_root_idx) = match (self.bst.root, other.bst.root) {
+            (Some(root_idx), Some(other_root_idx)) => (root_idx, other_root_idx),
+            _ => return,
+        };
+
+        let min_idx = self.bst.get_subtree_min(root_idx);
+        let max_idx = self.bst.get_subtree_max(root_idx);
+        let other_min = &other.bst.nodes[other.bst.get_subtree_min(other_root_idx)].data;
+        let other_max = &other.bst.nodes[other.bst.get_subtree_max(other_root_idx)].data;
+
+        if self.bst.nodes[max_idx].data <= *other_min {
+            other.bst.root = None;
+            let moved_root_idx = other
+                .bst
+                .move_subtree(other_root_idx, &mut self.bst, |_, _| {});
+
+            self.bst.nodes[max_idx].right = Some(moved_root_idx);
+            self.bst.nodes[moved_root_idx].parent = Some(max_idx);
+        } else if *other_max < self.bst.nodes[min_idx].data {
+            other.bst.root = None;
+            let moved_root_idx = other
+                .bst
+                .move_subtree(other_root_idx, &mut self.bst, |_, _| {});
+
+            self.bst.nodes[min_idx].left = Some(moved_root_idx);
+            self.bst.nodes[moved_root_idx].parent = Some(min_idx);
+        } else {
+            let merged = merge_sorted(self.bst.drain_sorted(), other.bst.drain_sorted());
+            let merged_len = merged.len();
+
+            self.bst.root =
+                self.bst
+                    .build_balanced(&mut merged.into_iter(), merged_len, 0, &mut |_, _| {});
         }
+
+        // Hanging one tree off the other can leave it deeper than alpha allows
+        other.rebuild_if_unbalanced(false);
+        self.rebuild_if_unbalanced(true);
+    }
+
+    // Create a new iterator that visits values in sorted order
+    pub fn iter(&self) -> InorderIter<'_, T> {
+        self.bst.iter()
     }
+
+    // Create a new iterator w/ a stack for DFS taversal
+    pub fn dfs_iter(&self) -> DfsIter<'_, T> {
+        self.bst.dfs_iter()
+    }
+
+    // Create a new iterator w/ a queue for BFS traversal
+    pub fn bfs_iter(&self) -> BfsIter<'_, T> {
+        self.bst.bfs_iter()
+    }
+}
+
+impl<T: PartialOrd + Display + Default> SearchTree<T> for BinarySearchTree<T> {
+    type Iter<'a>
+        = InorderIter<'a, T>
+    where
+        T: 'a;
+
+    fn insert(&mut self, item: T) {
+        BinarySearchTree::insert(self, item)
+    }
+
+    fn remove(&mut self, item: &T) -> Result<(), NodeNotFoundErr> {
+        BinarySearchTree::remove(self, item)
+    }
+
+    fn contains(&self, item: &T) -> bool {
+        BinarySearchTree::contains(self, item)
+    }
+
+    fn len(&self) -> usize {
+        self.get_size()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.bst.iter()
+    }
+
+    fn min(&self) -> Option<&T> {
+        self.bst.get_min()
+    }
+
+    fn max(&self) -> Option<&T> {
+        self.bst.get_max()
+    }
+
+    fn successor(&self, item: &T) -> Option<&T> {
+        self.bst.get_successor(item)
+    }
+
+    fn predecessor(&self, item: &T) -> Option<&T> {
+        self.bst.get_predecessor(item)
+    }
+}
+
+// Sorts the values and works out which one roots the optimal tree for every range of
+//  them, using Knuth's O(n^2) dynamic programming algorithm. The root for the values in
+//  start..end is at `roots[start * (n + 1) + end]`. Returns that table along with the
+//  optimal tree's expected search cost.
+pub(crate) fn plan_optimal_tree<T: PartialOrd>(
+    items: &mut [(T, f64)],
+) -> Result<(Vec<usize>, f64), InvalidProbabilityErr> {
+    if items
+        .iter()
+        .any(|(_, probability)| !probability.is_finite() || *probability < 0.0)
+    {
+        return Err(InvalidProbabilityErr);
+    }
+
+    items.sort_by(|(first, _), (second, _)| first.partial_cmp(second).unwrap_or(Ordering::Equal));
+
+    // costs[i][j] and roots[i][j] cover the values in i..j, with every table flattened
+    //  into rows of n + 1 entries
+    let len = items.len();
+    let row = len + 1;
+    let mut prefix_sums = vec![0.0; row];
+    for (item_idx, (_, probability)) in items.iter().enumerate() {
+        prefix_sums[item_idx + 1] = prefix_sums[item_idx] + probability;
+    }
+
+    let mut costs = vec![0.0; row * row];
+    let mut roots = vec![0; row * row];
+    for start in 0..len {
+        costs[start * row + start + 1] = items[start].1;
+        roots[start * row + start + 1] = start;
+    }
+
+    for span in 2..=len {
+        for start in 0..=len - span {
+            let end = start + span;
+
+            // Knuth's observation: the best root never moves left as the range grows to
+            //  the right, or right as it shrinks from the left, which bounds the search
+            let mut best = (f64::INFINITY, start);
+            for root in roots[start * row + end - 1]..=roots[(start + 1) * row + end] {
+                let cost = costs[start * row + root] + costs[(root + 1) * row + end];
+                if cost < best.0 {
+                    best = (cost, root);
+                }
+            }
+
+            costs[start * row + end] = best.0 + prefix_sums[end] - prefix_sums[start];
+            roots[start * row + end] = best.1;
+        }
+    }
+
+    Ok((roots, costs[len]))
 }
@@ -0,0 +1,206 @@
+use generational_arena::Index;
+use std::collections::{HashMap, VecDeque};
+
+use super::base_tree::{BfsIter, DfsIter, InorderIter, InternalBinarySearchTree, Node};
+
+use std::cmp::PartialOrd;
+use std::fmt::Display;
+use std::ops::{Bound, RangeBounds};
+
+// A Cartesian tree is built from a sequence rather than from sorted values: it's a min-heap
+//  by value, and an in order walk gives back the original sequence. The smallest value
+//  between two positions is then the lowest common ancestor of their nodes, so range minimum
+//  queries come down to ancestor lookups, answered in O(log n) with a binary lifting table.
+//
+// Unlike the search trees, the tree is static: it can't be searched by value or changed
+//  once built.
+pub struct CartesianTree<T: PartialOrd + Display + Default> {
+    bst: InternalBinarySearchTree<T>,
+    // Arena index of the node for every position in the sequence, and the reverse lookup
+    node_idxs: Vec<Index>,
+    positions: HashMap<Index, usize>,
+    depths: Vec<usize>,
+    // ancestors[k][pos] is the position of the node 2^k levels above `pos`'s node,
+    //  or of the root when the tree isn't that deep
+    ancestors: Vec<Vec<usize>>,
+}
+
+impl<T: PartialOrd + Display + Default + Clone> CartesianTree<T> {
+    // Builds the tree in O(n) by keeping a stack of the tree's right spine. Each new value
+    //  pops every larger value off the spine, adopts the last popped one as its left child,
+    //  and hangs itself off the remaining top as its right child. Equal values stay above
+    //  later ones, so queries return the leftmost minimum.
+    pub fn from_slice(items: &[T]) -> Self {
+        let mut bst: InternalBinarySearchTree<T> = InternalBinarySearchTree::new();
+        let mut node_idxs = Vec::with_capacity(items.len());
+        let mut positions = HashMap::new();
+        let mut parents: Vec<Option<usize>> = vec![None; items.len()];
+        let mut spine: Vec<usize> = Vec::new();
+
+        for (pos, item) in items.iter().enumerate() {
+            let node_idx = bst.nodes.insert(Node {
+                data: item.clone(),
+                left: None,
+                right: None,
+                parent: None,
+            });
+            node_idxs.push(node_idx);
+            positions.insert(node_idx, pos);
+
+            let mut last_popped = None;
+            while let Some(&top_pos) = spine.last() {
+                if items[top_pos] <= *item {
+                    break;
+                }
+                last_popped = spine.pop();
+            }
+
+            if let Some(left_pos) = last_popped {
+                bst.nodes[node_idx].left = Some(node_idxs[left_pos]);
+                bst.nodes[node_idxs[left_pos]].parent = Some(node_idx);
+                parents[left_pos] = Some(pos);
+            }
+
+            if let Some(&parent_pos) = spine.last() {
+                bst.nodes[node_idxs[parent_pos]].right = Some(node_idx);
+                bst.nodes[node_idx].parent = Some(node_idxs[parent_pos]);
+                parents[pos] = Some(parent_pos);
+            }
+
+            spine.push(pos);
+        }
+
+        bst.root = spine.first().map(|root_pos| node_idxs[*root_pos]);
+
+        let mut tree = CartesianTree {
+            bst,
+            node_idxs,
+            positions,
+            depths: vec![0; items.len()],
+            ancestors: Vec::new(),
+        };
+        tree.build_ancestors(&parents);
+        tree
+    }
+}
+
+impl<T: PartialOrd + Display + Default> CartesianTree<T> {
+    // Fills in every node's depth, then the binary lifting table one power of two at a time
+    fn build_ancestors(&mut self, parents: &[Option<usize>]) {
+        let mut pending: VecDeque<Index> = self.bst.root.into_iter().collect();
+        while let Some(node_idx) = pending.pop_front() {
+            let node = &self.bst.nodes[node_idx];
+            let pos = self.positions[&node_idx];
+            self.depths[pos] = parents[pos].map_or(0, |parent_pos| self.depths[parent_pos] + 1);
+
+            pending.extend(node.left.iter().chain(node.right.iter()));
+        }
+
+        let root_pos = self
+            .bst
+            .root
+            .map_or(0, |root_idx| self.positions[&root_idx]);
+        let mut level: Vec<usize> = parents
+            .iter()
+            .map(|parent_pos| parent_pos.unwrap_or(root_pos))
+            .collect();
+
+        let max_depth = self.depths.iter().copied().max().unwrap_or(0);
+        self.ancestors.push(level.clone());
+        for _ in 1..(usize::BITS - max_depth.leading_zeros()) {
+            level = level
+                .iter()
+                .map(|ancestor_pos| level[*ancestor_pos])
+                .collect();
+            self.ancestors.push(level.clone());
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.bst.nodes.len()
+    }
+
+    // Value at a position in the original sequence
+    pub fn get(&self, pos: usize) -> Option<&T> {
+        let node_idx = self.node_idxs.get(pos)?;
+        Some(&self.bst.nodes[*node_idx].data)
+    }
+
+    // Smallest value in the whole sequence, which sits at the root
+    pub fn min(&self) -> Option<&T> {
+        let root_idx = self.bst.root?;
+        Some(&self.bst.nodes[root_idx].data)
+    }
+
+    // Position of the smallest value within a range of positions, or None if the range is
+    //  empty or runs past the end of the sequence, much like `slice::get`. Bounds that
+    //  can't be turned into an exclusive end without overflowing are past the end too.
+    pub fn range_min_position(&self, range: impl RangeBounds<usize>) -> Option<usize> {
+        let start = match range.start_bound() {
+            Bound::Included(start) => *start,
+            Bound::Excluded(start) => start.checked_add(1)?,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(end) => end.checked_add(1)?,
+            Bound::Excluded(end) => *end,
+            Bound::Unbounded => self.node_idxs.len(),
+        };
+
+        if start >= end || end > self.node_idxs.len() {
+            return None;
+        }
+
+        Some(self.get_lca(start, end - 1))
+    }
+
+    // Smallest value within a range of positions
+    pub fn range_min(&self, range: impl RangeBounds<usize>) -> Option<&T> {
+        self.get(self.range_min_position(range)?)
+    }
+
+    // Lowest common ancestor of two positions' nodes: lift the deeper node to the other
+    //  one's depth, then lift both by the largest jumps that keep them apart
+    fn get_lca(&self, first_pos: usize, second_pos: usize) -> usize {
+        let (mut deeper_pos, mut other_pos) = if self.depths[first_pos] >= self.depths[second_pos] {
+            (first_pos, second_pos)
+        } else {
+            (second_pos, first_pos)
+        };
+
+        let depth_diff = self.depths[deeper_pos] - self.depths[other_pos];
+        for (power, level) in self.ancestors.iter().enumerate() {
+            if depth_diff & (1 << power) != 0 {
+                deeper_pos = level[deeper_pos];
+            }
+        }
+
+        if deeper_pos == other_pos {
+            return deeper_pos;
+        }
+
+        for level in self.ancestors.iter().rev() {
+            if level[deeper_pos] != level[other_pos] {
+                deeper_pos = level[deeper_pos];
+                other_pos = level[other_pos];
+            }
+        }
+
+        self.ancestors[0][deeper_pos]
+    }
+
+    // Create a new iterator w/ a stack for DFS taversal
+    pub fn dfs_iter(&self) -> DfsIter<'_, T> {
+        self.bst.dfs_iter()
+    }
+
+    // Create a new iterator w/ a queue for BFS traversal
+    pub fn bfs_iter(&self) -> BfsIter<'_, T> {
+        self.bst.bfs_iter()
+    }
+
+    // Create a new iterator that visits values in their original order
+    pub fn iter(&self) -> InorderIter<'_, T> {
+        self.bst.iter()
+    }
+}
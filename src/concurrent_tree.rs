@@ -0,0 +1,189 @@
+use super::persistent::PersistentRedBlackTree;
+use super::red_black_tree::RedBlackTree;
+use super::tree_errs::NodeNotFoundErr;
+
+use std::cmp::PartialOrd;
+use std::collections::VecDeque;
+use std::fmt::Display;
+use std::ops::Bound;
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+
+// Values copied out per lock acquisition by `iter`
+const DEFAULT_CHUNK_SIZE: usize = 64;
+
+// A red-black tree that can be shared between threads. Any number of threads can look
+//  values up or iterate at once, while `insert` and `remove` wait for exclusive access.
+//
+// Long scans shouldn't hold the lock at all: `snapshot` hands out a frozen, point-in-time
+//  version of the tree that readers can walk without blocking writers, and `iter` reads
+//  the live tree a chunk at a time.
+//
+// The tree is a `PersistentRedBlackTree`, so writers never change a node in place: every
+//  write copies the O(log n) nodes on the path down to the change and swaps in the new
+//  root, sharing every other node with the versions before it. Taking a snapshot only
+//  clones the root's `Arc`, and readers can't catch a rotation halfway through. Copying
+//  those paths is also why values have to be `Clone`.
+pub struct ConcurrentRedBlackTree<T: PartialOrd + Clone> {
+    tree: RwLock<PersistentRedBlackTree<T>>,
+}
+
+impl<T: PartialOrd + Clone> Default for ConcurrentRedBlackTree<T> {
+    fn default() -> Self {
+        ConcurrentRedBlackTree::new()
+    }
+}
+
+impl<T: PartialOrd + Clone> ConcurrentRedBlackTree<T> {
+    pub fn new() -> Self {
+        ConcurrentRedBlackTree {
+            tree: RwLock::new(PersistentRedBlackTree::new()),
+        }
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, PersistentRedBlackTree<T>> {
+        self.tree
+            .read()
+            .expect("A thread panicked while holding the tree lock")
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, PersistentRedBlackTree<T>> {
+        self.tree
+            .write()
+            .expect("A thread panicked while holding the tree lock")
+    }
+
+    // Immutable version of the tree as it is right now, which only takes the lock long
+    //  enough to clone the root. Later writes to the live tree won't show up in it.
+    pub fn snapshot(&self) -> PersistentRedBlackTree<T> {
+        self.read().clone()
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.read().get_size()
+    }
+
+    pub fn contains(&self, item: &T) -> bool {
+        self.read().contains(item)
+    }
+
+    pub fn is_valid(&self) -> bool {
+        self.read().is_valid()
+    }
+
+    // Writers hold the lock while copying the path down to the change, which leaves any
+    //  snapshots taken so far untouched
+    pub fn insert(&self, item: T) {
+        let mut tree = self.write();
+        *tree = tree.insert(item);
+    }
+
+    pub fn remove(&self, item: &T) -> Result<(), NodeNotFoundErr> {
+        let mut tree = self.write();
+        *tree = tree.remove(item)?;
+        Ok(())
+    }
+
+    // Iterates over copies of the values in sorted order, taking the read lock once per
+    //  chunk of values. See `ChunkedIter` for what writes in between chunks look like.
+    pub fn iter(&self) -> ChunkedIter<'_, T> {
+        self.iter_chunked(DEFAULT_CHUNK_SIZE)
+    }
+
+    pub fn iter_chunked(&self, chunk_size: usize) -> ChunkedIter<'_, T> {
+        ChunkedIter {
+            source: self,
+            chunk: VecDeque::new(),
+            chunk_size: chunk_size.max(1),
+            last: None,
+            last_count: 0,
+            is_done: false,
+        }
+    }
+}
+
+// Converting to and from the regular tree copies every value over, in O(n log n)
+impl<T: PartialOrd + Display + Default + Clone> ConcurrentRedBlackTree<T> {
+    pub fn from_tree(tree: RedBlackTree<T>) -> Self {
+        ConcurrentRedBlackTree {
+            tree: RwLock::new(
+                tree.iter()
+                    .fold(PersistentRedBlackTree::new(), |versions, val| {
+                        versions.insert(val.clone())
+                    }),
+            ),
+        }
+    }
+
+    pub fn into_inner(self) -> RedBlackTree<T> {
+        let versions = self
+            .tree
+            .into_inner()
+            .expect("A thread panicked while holding the tree lock");
+
+        let mut tree = RedBlackTree::new();
+        for val in versions.iter() {
+            tree.insert(val.clone());
+        }
+        tree
+    }
+}
+
+// Reads the tree a chunk at a time, picking up after the last value it handed out. Values
+//  always come out in sorted order and are never repeated, but every chunk reflects the
+//  tree as it was when that chunk was read: values inserted ahead of the iterator will be
+//  seen, and values removed after their chunk was read may still be handed out.
+pub struct ChunkedIter<'a, T: PartialOrd + Clone> {
+    source: &'a ConcurrentRedBlackTree<T>,
+    chunk: VecDeque<T>,
+    chunk_size: usize,
+    // The largest value read so far, and how many copies of it were read, so that the
+    //  next chunk can skip past duplicates that were already handed out
+    last: Option<T>,
+    last_count: usize,
+    is_done: bool,
+}
+
+impl<'a, T: PartialOrd + Clone> ChunkedIter<'a, T> {
+    // Only the snapshot's root is cloned under the lock, and the chunk is then copied
+    //  out of the snapshot with the lock already released
+    fn read_chunk(&mut self) {
+        let tree = self.source.snapshot();
+        let start = self.last.clone();
+        let mut values = match &start {
+            Some(start) => tree.iter_from(Bound::Included(start)),
+            None => tree.iter(),
+        }
+        .peekable();
+
+        for _ in 0..self.last_count {
+            if values.next_if(|val| Some(*val) == start.as_ref()).is_none() {
+                break;
+            }
+        }
+
+        for val in values.take(self.chunk_size) {
+            if Some(val) == self.last.as_ref() {
+                self.last_count += 1;
+            } else {
+                self.last = Some(val.clone());
+                self.last_count = 1;
+            }
+
+            self.chunk.push_back(val.clone());
+        }
+
+        self.is_done = self.chunk.len() < self.chunk_size;
+    }
+}
+
+impl<'a, T: PartialOrd + Clone> Iterator for ChunkedIter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.chunk.is_empty() && !self.is_done {
+            self.read_chunk();
+        }
+
+        self.chunk.pop_front()
+    }
+}
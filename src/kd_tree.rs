@@ -0,0 +1,384 @@
+use alloc::collections::BinaryHeap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use generational_arena::Index;
+
+use super::base_tree::{BfsIter, DfsIter, InternalBinarySearchTree, Node};
+use super::tree_errs::InvalidPointErr;
+
+use core::cmp::Ordering;
+use core::fmt::{Display, Formatter, Result};
+
+// A point in K dimensional space
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd)]
+pub struct Point<const K: usize>(pub [f64; K]);
+
+impl<const K: usize> Point<K> {
+    pub fn distance_squared(&self, other: &Point<K>) -> f64 {
+        self.0
+            .iter()
+            .zip(other.0.iter())
+            .map(|(coord, other_coord)| (coord - other_coord) * (coord - other_coord))
+            .sum()
+    }
+
+    fn is_finite(&self) -> bool {
+        self.0.iter().all(|coord| coord.is_finite())
+    }
+}
+
+// Arrays only implement Default up to 32 elements, so the origin is spelled out by hand
+impl<const K: usize> Default for Point<K> {
+    fn default() -> Self {
+        Point([0.0; K])
+    }
+}
+
+impl<const K: usize> Display for Point<K> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let coord_strs: Vec<String> = self.0.iter().map(|coord| coord.to_string()).collect();
+        write!(f, "({})", coord_strs.join(", "))
+    }
+}
+
+// Candidate for a k-nearest search, ordered by distance so the heap keeps the farthest
+//  candidate on top, ready to be evicted by a closer one
+struct Neighbour {
+    dist_sq: f64,
+    node_idx: Index,
+}
+
+impl PartialEq for Neighbour {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Neighbour {}
+
+impl PartialOrd for Neighbour {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Neighbour {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.dist_sq.total_cmp(&other.dist_sq)
+    }
+}
+
+// A k-d tree is a binary tree over points that cycles through the axes as it goes down:
+//  nodes at depth d split their subtree on axis d % K, with smaller coordinates to the left
+//  and equal or larger ones to the right. That lets nearest neighbour and box queries skip
+//  every subtree on the far side of a split that can't hold a match.
+//
+// Points are only ever inserted, so a tree built one point at a time can end up lopsided.
+//  `from_points` builds a balanced tree from a known set of points instead.
+#[derive(Default)]
+pub struct KdTree<const K: usize> {
+    bst: InternalBinarySearchTree<Point<K>>,
+}
+
+impl<const K: usize> KdTree<K> {
+    // Fails to compile for zero dimensions, which leave nothing to split on
+    const VALID_DIMENSIONS: () = assert!(K >= 1, "k-d trees need at least one dimension");
+
+    pub fn new() -> Self {
+        let () = Self::VALID_DIMENSIONS;
+
+        KdTree {
+            bst: InternalBinarySearchTree::new(),
+        }
+    }
+
+    // Builds a balanced tree by splitting every subtree on the median of its points along
+    //  the subtree's axis, which takes O(n log n) time overall
+    pub fn from_points(mut points: Vec<Point<K>>) -> core::result::Result<Self, InvalidPointErr> {
+        if !points.iter().all(Point::is_finite) {
+            return Err(InvalidPointErr);
+        }
+
+        let mut tree = KdTree::new();
+        tree.bst.root = tree.build_subtree(&mut points, 0);
+        Ok(tree)
+    }
+
+    // Recursive function that links the points into a subtree, returning its detached root
+    fn build_subtree(&mut self, points: &mut [Point<K>], depth: usize) -> Option<Index> {
+        if points.is_empty() {
+            return None;
+        }
+
+        let axis = depth % K;
+        let median_pos = points.len() / 2;
+        points.select_nth_unstable_by(median_pos, |point, other| {
+            point.0[axis].total_cmp(&other.0[axis])
+        });
+
+        // Points sharing the median's coordinate may have landed on its left, but they
+        //  belong on its right, so the median moves in front of them
+        let median_coord = points[median_pos].0[axis];
+        let mut split_pos = 0;
+        for pos in 0..median_pos {
+            if points[pos].0[axis] < median_coord {
+                points.swap(pos, split_pos);
+                split_pos += 1;
+            }
+        }
+        points.swap(split_pos, median_pos);
+
+        let (left_points, rest) = points.split_at_mut(split_pos);
+        let (median, right_points) = rest.split_first_mut().expect("The median is in range");
+
+        let left_node_opt = self.build_subtree(left_points, depth + 1);
+        let right_node_opt = self.build_subtree(right_points, depth + 1);
+        let node_idx = self.bst.nodes.insert(Node {
+            data: *median,
+            left: left_node_opt,
+            right: right_node_opt,
+            parent: None,
+        });
+
+        for child_idx in left_node_opt.iter().chain(right_node_opt.iter()) {
+            self.bst.nodes[*child_idx].parent = Some(node_idx);
+        }
+
+        Some(node_idx)
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.bst.nodes.len()
+    }
+
+    pub fn get_height(&self) -> usize {
+        self.bst.get_height()
+    }
+
+    // Coordinates must be finite, since NaNs can't be placed on either side of a split
+    pub fn insert(&mut self, point: Point<K>) -> core::result::Result<(), InvalidPointErr> {
+        if !point.is_finite() {
+            return Err(InvalidPointErr);
+        }
+
+        let new_leaf_idx = self.bst.nodes.insert(Node {
+            data: point,
+            left: None,
+            right: None,
+            parent: None,
+        });
+
+        let mut cur_node_idx = match self.bst.root {
+            Some(root_idx) => root_idx,
+            None => {
+                self.bst.root = Some(new_leaf_idx);
+                return Ok(());
+            }
+        };
+
+        let mut depth = 0;
+        loop {
+            let cur_node = &mut self.bst.nodes[cur_node_idx];
+            let child_opt = if point.0[depth % K] < cur_node.data.0[depth % K] {
+                &mut cur_node.left
+            } else {
+                &mut cur_node.right
+            };
+
+            match *child_opt {
+                Some(child_idx) => cur_node_idx = child_idx,
+                None => {
+                    *child_opt = Some(new_leaf_idx);
+                    break;
+                }
+            }
+            depth += 1;
+        }
+
+        self.bst.nodes[new_leaf_idx].parent = Some(cur_node_idx);
+        Ok(())
+    }
+
+    pub fn contains(&self, point: &Point<K>) -> bool {
+        let mut cur_node_opt = self.bst.root;
+        let mut depth = 0;
+
+        while let Some(cur_node_idx) = cur_node_opt {
+            let cur_node = &self.bst.nodes[cur_node_idx];
+            if cur_node.data == *point {
+                return true;
+            }
+
+            cur_node_opt = if point.0[depth % K] < cur_node.data.0[depth % K] {
+                cur_node.left
+            } else {
+                cur_node.right
+            };
+            depth += 1;
+        }
+
+        false
+    }
+
+    // Closest point to the target, or None if the tree is empty
+    pub fn nearest(&self, target: &Point<K>) -> Option<&Point<K>> {
+        self.k_nearest(target, 1).pop()
+    }
+
+    // Up to `count` points closest to the target, nearest first. Points at the same
+    //  distance are returned in no particular order.
+    pub fn k_nearest(&self, target: &Point<K>, count: usize) -> Vec<&Point<K>> {
+        if count == 0 {
+            return Vec::new();
+        }
+
+        // The tree bounds the heap, however many neighbours were asked for
+        let mut candidates =
+            BinaryHeap::with_capacity(count.min(self.get_size()).saturating_add(1));
+        self.search_nearest(self.bst.root, 0, target, count, &mut candidates);
+
+        candidates
+            .into_sorted_vec()
+            .into_iter()
+            .map(|neighbour| &self.bst.nodes[neighbour.node_idx].data)
+            .collect()
+    }
+
+    // Recursive function that offers every point in the subtree that could still beat the
+    //  farthest candidate. The near side of each split is searched first so that the far
+    //  side can be skipped whenever the split itself lies farther away than that candidate.
+    fn search_nearest(
+        &self,
+        node_idx_opt: Option<Index>,
+        depth: usize,
+        target: &Point<K>,
+        count: usize,
+        candidates: &mut BinaryHeap<Neighbour>,
+    ) {
+        let node_idx = match node_idx_opt {
+            Some(node_idx) => node_idx,
+            None => return,
+        };
+        let node = &self.bst.nodes[node_idx];
+
+        candidates.push(Neighbour {
+            dist_sq: node.data.distance_squared(target),
+            node_idx,
+        });
+        if candidates.len() > count {
+            candidates.pop();
+        }
+
+        let axis = depth % K;
+        let split_dist = target.0[axis] - node.data.0[axis];
+        let (near_opt, far_opt) = if split_dist < 0.0 {
+            (node.left, node.right)
+        } else {
+            (node.right, node.left)
+        };
+
+        self.search_nearest(near_opt, depth + 1, target, count, candidates);
+
+        let farthest_dist_sq = candidates
+            .peek()
+            .map_or(f64::INFINITY, |neighbour| neighbour.dist_sq);
+        if candidates.len() < count || split_dist * split_dist < farthest_dist_sq {
+            self.search_nearest(far_opt, depth + 1, target, count, candidates);
+        }
+    }
+
+    // Every point inside the axis-aligned box spanning `min` to `max`, bounds included
+    pub fn within_box(&self, min: &Point<K>, max: &Point<K>) -> Vec<&Point<K>> {
+        let mut points = Vec::new();
+        let mut pending: Vec<(Index, usize)> = self
+            .bst
+            .root
+            .map(|root_idx| (root_idx, 0))
+            .into_iter()
+            .collect();
+
+        while let Some((node_idx, depth)) = pending.pop() {
+            let node = &self.bst.nodes[node_idx];
+            let is_inside = (0..K)
+                .all(|axis| min.0[axis] <= node.data.0[axis] && node.data.0[axis] <= max.0[axis]);
+            if is_inside {
+                points.push(&node.data);
+            }
+
+            let axis = depth % K;
+            if let Some(left_idx) = node.left {
+                if min.0[axis] < node.data.0[axis] {
+                    pending.push((left_idx, depth + 1));
+                }
+            }
+            if let Some(right_idx) = node.right {
+                if max.0[axis] >= node.data.0[axis] {
+                    pending.push((right_idx, depth + 1));
+                }
+            }
+        }
+
+        points
+    }
+
+    // Checks that every point falls inside the region carved out by the splits above it,
+    //  on the left of a split when its coordinate is smaller and the right otherwise, and
+    //  that every child links back to its parent. No balance is checked, as the tree only
+    //  balances itself when built from a batch of points.
+    pub fn is_valid(&self) -> bool {
+        if let Some(root_idx) = self.bst.root {
+            if self.bst.nodes[root_idx].parent.is_some() {
+                return false;
+            }
+        }
+
+        self.bst.get_subtree_size(self.bst.root) == self.bst.nodes.len()
+            && self.is_valid_subtree(self.bst.root, 0, [f64::NEG_INFINITY; K], [f64::INFINITY; K])
+    }
+
+    // Recursive function that checks a subtree against the region its ancestors carved out,
+    //  `lower` bounds being inclusive and `upper` bounds exclusive
+    fn is_valid_subtree(
+        &self,
+        node_idx_opt: Option<Index>,
+        depth: usize,
+        lower: [f64; K],
+        upper: [f64; K],
+    ) -> bool {
+        let node_idx = match node_idx_opt {
+            Some(node_idx) => node_idx,
+            None => return true,
+        };
+        let node = &self.bst.nodes[node_idx];
+        let coords = node.data.0;
+
+        let in_region =
+            (0..K).all(|axis| lower[axis] <= coords[axis] && coords[axis] < upper[axis]);
+        let links_valid = node
+            .left
+            .iter()
+            .chain(node.right.iter())
+            .all(|child_idx| self.bst.nodes[*child_idx].parent == Some(node_idx));
+
+        let axis = depth % K;
+        let mut left_upper = upper;
+        left_upper[axis] = coords[axis];
+        let mut right_lower = lower;
+        right_lower[axis] = coords[axis];
+
+        in_region
+            && links_valid
+            && self.is_valid_subtree(node.left, depth + 1, lower, left_upper)
+            && self.is_valid_subtree(node.right, depth + 1, right_lower, upper)
+    }
+
+    // Create a new iterator w/ a stack for DFS taversal
+    pub fn dfs_iter(&self) -> DfsIter<'_, Point<K>> {
+        self.bst.dfs_iter()
+    }
+
+    // Create a new iterator w/ a queue for BFS traversal
+    pub fn bfs_iter(&self) -> BfsIter<'_, Point<K>> {
+        self.bst.bfs_iter()
+    }
+}
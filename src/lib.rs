@@ -1,4 +1,52 @@
+// Without the `std` feature this is a `no_std` crate. The `alloc` feature brings back every
+//  tree that only needs a heap, while `static_tree` needs neither.
+#![cfg_attr(not(feature = "std"), no_std)]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "std")]
+pub mod aa_tree;
+#[cfg(feature = "std")]
+pub mod avl_tree;
+#[cfg(feature = "alloc")]
+pub mod b_tree;
+#[cfg(feature = "alloc")]
 pub(crate) mod base_tree;
+#[cfg(feature = "alloc")]
 pub mod binary_search_tree;
+#[cfg(feature = "std")]
+pub mod cartesian_tree;
+#[cfg(feature = "std")]
+pub mod concurrent_tree;
+#[cfg(feature = "alloc")]
+pub mod kd_tree;
+#[cfg(feature = "std")]
+pub mod llrb_tree;
+#[cfg(feature = "alloc")]
+pub mod node_store;
+#[cfg(feature = "std")]
+pub mod persistent;
+#[cfg(feature = "alloc")]
+pub mod raw;
+mod rb_balance;
+#[cfg(feature = "std")]
 pub mod red_black_tree;
+pub mod search_tree;
+#[cfg(feature = "std")]
+pub mod sequence_tree;
+#[cfg(feature = "alloc")]
+pub mod set_ops;
+#[cfg(feature = "std")]
+pub mod sharded_map;
+mod slots;
+#[cfg(feature = "alloc")]
+pub mod splay_tree;
+pub mod static_tree;
+#[cfg(feature = "std")]
+pub mod treap;
 pub mod tree_errs;
+#[cfg(feature = "alloc")]
+pub mod two_three_four;
+#[cfg(feature = "std")]
+pub mod weight_balanced_tree;
@@ -0,0 +1,389 @@
+use generational_arena::Index;
+use std::collections::HashMap;
+
+use super::base_tree::{BfsIter, DfsIter, InorderIter, InternalBinarySearchTree, Node};
+use super::red_black_tree::TreeColors;
+use super::search_tree::SearchTree;
+use super::tree_errs::NodeNotFoundErr;
+
+use std::cmp::PartialOrd;
+use std::fmt::Display;
+use std::mem::{replace, take};
+
+// Sedgewick's left-leaning red-black tree. On top of the usual red-black properties,
+//  red links may only lean left, which makes the tree a one-to-one encoding of a 2-3 tree.
+//  Every insert and delete case then boils down to three local fixes: rotate a right-
+//  leaning red link left, rotate two reds in a row right, and split a 4-node by flipping
+//  colors. See: https://sedgewick.io/wp-content/themes/sedgewick/papers/2008LLRB.pdf
+#[derive(Default)]
+pub struct LlrbTree<T: PartialOrd + Display + Default> {
+    bst: InternalBinarySearchTree<T>,
+    colors: HashMap<Index, TreeColors>,
+}
+
+impl<T: PartialOrd + Display + Default> LlrbTree<T> {
+    pub fn new() -> Self {
+        LlrbTree {
+            bst: InternalBinarySearchTree::new(),
+            colors: HashMap::new(),
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.bst.nodes.len()
+    }
+
+    // New leaves are linked in red, then every node on the way back up to the root
+    //  gets the same three fixes
+    pub fn insert(&mut self, item: T) {
+        let leaf = Node {
+            data: item,
+            left: None,
+            right: None,
+            parent: None,
+        };
+
+        let new_leaf_idx = self.bst.insert_node(leaf);
+        self.colors.insert(new_leaf_idx, TreeColors::Red);
+
+        let mut cur_node_opt = self.bst.nodes[new_leaf_idx].parent;
+        while let Some(cur_node_idx) = cur_node_opt {
+            let subtree_root_idx = self.fix_up(cur_node_idx);
+            cur_node_opt = self.bst.nodes[subtree_root_idx].parent;
+        }
+
+        self.set_root_black();
+    }
+
+    pub fn contains(&self, item: &T) -> bool {
+        self.bst.find_node_index(item).is_some()
+    }
+
+    // Deletes top-down, pushing a red link ahead of the search so that the node finally
+    //  removed is never a lone 2-node, then fixes the tree back up on the way out
+    pub fn remove(&mut self, item: &T) -> Result<(), NodeNotFoundErr> {
+        self.bst.find_node_index(item).ok_or(NodeNotFoundErr)?;
+        let root_idx = self.bst.root.expect("A tree holding the item has a root");
+
+        let root_node = &self.bst.nodes[root_idx];
+        if !self.is_red(root_node.left) && !self.is_red(root_node.right) {
+            self.colors.insert(root_idx, TreeColors::Red);
+        }
+
+        self.remove_from(root_idx, item);
+        self.set_root_black();
+
+        Ok(())
+    }
+
+    // Recursive function that removes one node holding `item` from the subtree under `node_idx`
+    fn remove_from(&mut self, node_idx: Index, item: &T) {
+        let mut node_idx = node_idx;
+
+        if *item < self.bst.nodes[node_idx].data {
+            if !self.is_red(self.bst.nodes[node_idx].left) && !self.is_left_left_red(node_idx) {
+                node_idx = self.move_red_left(node_idx);
+            }
+
+            let left_idx = self.bst.nodes[node_idx]
+                .left
+                .expect("A smaller item exists in the left subtree");
+            self.remove_from(left_idx, item);
+        } else {
+            if self.is_red(self.bst.nodes[node_idx].left) {
+                node_idx = self.rotate_right(node_idx);
+            }
+
+            // Without a right child, the left child can't be black, and the one above
+            //  was just rotated away, so the node is a leaf
+            if self.bst.nodes[node_idx].data == *item && self.bst.nodes[node_idx].right.is_none() {
+                self.remove_leaf(node_idx);
+                return;
+            }
+
+            let right_idx_opt = self.bst.nodes[node_idx].right;
+            if !self.is_red(right_idx_opt) && !self.is_right_left_red(node_idx) {
+                node_idx = self.move_red_right(node_idx);
+            }
+
+            let right_idx = self.bst.nodes[node_idx]
+                .right
+                .expect("An equal or greater item exists in the right subtree");
+
+            if self.bst.nodes[node_idx].data == *item {
+                // Replace the value with its successor's, then remove the successor instead
+                let successor_idx = self.bst.get_subtree_min(right_idx);
+                let successor_data = take(&mut self.bst.nodes[successor_idx].data);
+                let removed_data = replace(&mut self.bst.nodes[node_idx].data, successor_data);
+                self.bst.nodes[successor_idx].data = removed_data;
+
+                self.remove_min(right_idx);
+            } else {
+                self.remove_from(right_idx, item);
+            }
+        }
+
+        self.fix_up(node_idx);
+    }
+
+    // Recursive function that removes the smallest node of the subtree under `node_idx`
+    fn remove_min(&mut self, node_idx: Index) {
+        // Left-leaning trees have no lone right children, so the smallest node is a leaf
+        if self.bst.nodes[node_idx].left.is_none() {
+            self.remove_leaf(node_idx);
+            return;
+        }
+
+        let mut node_idx = node_idx;
+        if !self.is_red(self.bst.nodes[node_idx].left) && !self.is_left_left_red(node_idx) {
+            node_idx = self.move_red_left(node_idx);
+        }
+
+        let left_idx = self.bst.nodes[node_idx]
+            .left
+            .expect("Moving a red link left keeps the left child");
+        self.remove_min(left_idx);
+        self.fix_up(node_idx);
+    }
+
+    fn remove_leaf(&mut self, node_idx: Index) {
+        match self.bst.nodes[node_idx].parent {
+            Some(parent_idx) => {
+                let parent_node = &mut self.bst.nodes[parent_idx];
+                if parent_node.left == Some(node_idx) {
+                    parent_node.left = None;
+                } else {
+                    parent_node.right = None;
+                }
+            }
+            None => self.bst.root = None,
+        }
+
+        self.colors.remove(&node_idx);
+        let _ =
+            self.bst.nodes.remove(node_idx).expect(
+                "Exclusive access during mutation ensures that a node exists for every index",
+            );
+    }
+
+    // Restores the left-leaning invariants at a single node, returning its subtree's root
+    fn fix_up(&mut self, node_idx: Index) -> Index {
+        let mut node_idx = node_idx;
+
+        let node = &self.bst.nodes[node_idx];
+        if self.is_red(node.right) && !self.is_red(node.left) {
+            node_idx = self.rotate_left(node_idx);
+        }
+
+        if self.is_red(self.bst.nodes[node_idx].left) && self.is_left_left_red(node_idx) {
+            node_idx = self.rotate_right(node_idx);
+        }
+
+        let node = &self.bst.nodes[node_idx];
+        if self.is_red(node.left) && self.is_red(node.right) {
+            self.flip_colors(node_idx);
+        }
+
+        node_idx
+    }
+
+    // Borrows from the right sibling so that the left child, or one of its children, is red
+    fn move_red_left(&mut self, node_idx: Index) -> Index {
+        self.flip_colors(node_idx);
+
+        if self.is_right_left_red(node_idx) {
+            let right_idx = self.bst.nodes[node_idx]
+                .right
+                .expect("A red grandchild has a parent");
+            self.rotate_right(right_idx);
+
+            let node_idx = self.rotate_left(node_idx);
+            self.flip_colors(node_idx);
+            return node_idx;
+        }
+
+        node_idx
+    }
+
+    // Borrows from the left sibling so that the right child, or one of its children, is red
+    fn move_red_right(&mut self, node_idx: Index) -> Index {
+        self.flip_colors(node_idx);
+
+        if self.is_left_left_red(node_idx) {
+            let node_idx = self.rotate_right(node_idx);
+            self.flip_colors(node_idx);
+            return node_idx;
+        }
+
+        node_idx
+    }
+
+    // Turns a right-leaning red link into a left-leaning one, returning the subtree's new root
+    fn rotate_left(&mut self, node_idx: Index) -> Index {
+        let right_idx = self.bst.nodes[node_idx]
+            .right
+            .expect("A right rotation target exists");
+        self.bst.rotate_node_left(right_idx);
+
+        self.colors.insert(right_idx, self.colors[&node_idx]);
+        self.colors.insert(node_idx, TreeColors::Red);
+        right_idx
+    }
+
+    // Turns a left-leaning red link into a right-leaning one, returning the subtree's new root
+    fn rotate_right(&mut self, node_idx: Index) -> Index {
+        let left_idx = self.bst.nodes[node_idx]
+            .left
+            .expect("A left rotation target exists");
+        self.bst.rotate_node_right(left_idx);
+
+        self.colors.insert(left_idx, self.colors[&node_idx]);
+        self.colors.insert(node_idx, TreeColors::Red);
+        left_idx
+    }
+
+    // Flips a node and both of its children, which splits (or rebuilds) a 4-node
+    fn flip_colors(&mut self, node_idx: Index) {
+        let node = &self.bst.nodes[node_idx];
+        let node_idxs = [Some(node_idx), node.left, node.right];
+
+        for flip_idx in node_idxs.iter().flatten() {
+            let color = match self.colors[flip_idx] {
+                TreeColors::Red => TreeColors::Black,
+                TreeColors::Black => TreeColors::Red,
+            };
+            self.colors.insert(*flip_idx, color);
+        }
+    }
+
+    fn set_root_black(&mut self) {
+        if let Some(root_idx) = self.bst.root {
+            self.colors.insert(root_idx, TreeColors::Black);
+        }
+    }
+
+    // Terminating nodes are black
+    fn is_red(&self, node_idx_opt: Option<Index>) -> bool {
+        node_idx_opt.is_some_and(|node_idx| self.colors[&node_idx] == TreeColors::Red)
+    }
+
+    fn is_left_left_red(&self, node_idx: Index) -> bool {
+        let left_idx_opt = self.bst.nodes[node_idx].left;
+        left_idx_opt.is_some_and(|left_idx| self.is_red(self.bst.nodes[left_idx].left))
+    }
+
+    fn is_right_left_red(&self, node_idx: Index) -> bool {
+        let right_idx_opt = self.bst.nodes[node_idx].right;
+        right_idx_opt.is_some_and(|right_idx| self.is_red(self.bst.nodes[right_idx].left))
+    }
+
+    // Checks every left-leaning red-black tree invariant: ordering, parent links, a black
+    //  root, no red right links, no two reds in a row and equal black heights.
+    pub fn is_valid(&self) -> bool {
+        if self.colors.len() != self.bst.nodes.len() {
+            return false;
+        }
+
+        if let Some(root_idx) = self.bst.root {
+            if self.bst.nodes[root_idx].parent.is_some() || self.is_red(Some(root_idx)) {
+                return false;
+            }
+        }
+
+        let values: Vec<&T> = self.bst.iter().collect();
+        values.len() == self.bst.nodes.len()
+            && values.windows(2).all(|pair| pair[0] <= pair[1])
+            && self.get_valid_black_height(self.bst.root).is_some()
+    }
+
+    // Recursive function that returns a subtree's black height, or None if anything
+    //  under it breaks the left-leaning red-black properties
+    fn get_valid_black_height(&self, node_idx_opt: Option<Index>) -> Option<usize> {
+        let node_idx = match node_idx_opt {
+            Some(node_idx) => node_idx,
+            None => return Some(0),
+        };
+
+        let node = &self.bst.nodes[node_idx];
+        for child_idx in node.left.iter().chain(node.right.iter()) {
+            if self.bst.nodes[*child_idx].parent != node_idx_opt {
+                return None;
+            }
+        }
+
+        if self.is_red(node.right) || (self.is_red(node_idx_opt) && self.is_red(node.left)) {
+            return None;
+        }
+
+        let left_height = self.get_valid_black_height(node.left)?;
+        let right_height = self.get_valid_black_height(node.right)?;
+
+        if left_height != right_height {
+            return None;
+        }
+
+        if self.is_red(node_idx_opt) {
+            Some(left_height)
+        } else {
+            Some(left_height + 1)
+        }
+    }
+
+    // Create a new iterator w/ a stack for DFS taversal
+    pub fn dfs_iter(&self) -> DfsIter<'_, T> {
+        self.bst.dfs_iter()
+    }
+
+    // Create a new iterator w/ a queue for BFS traversal
+    pub fn bfs_iter(&self) -> BfsIter<'_, T> {
+        self.bst.bfs_iter()
+    }
+
+    // Create a new iterator that visits values in sorted order
+    pub fn iter(&self) -> InorderIter<'_, T> {
+        self.bst.iter()
+    }
+}
+
+impl<T: PartialOrd + Display + Default> SearchTree<T> for LlrbTree<T> {
+    type Iter<'a>
+        = InorderIter<'a, T>
+    where
+        T: 'a;
+
+    fn insert(&mut self, item: T) {
+        LlrbTree::insert(self, item)
+    }
+
+    fn remove(&mut self, item: &T) -> Result<(), NodeNotFoundErr> {
+        LlrbTree::remove(self, item)
+    }
+
+    fn contains(&self, item: &T) -> bool {
+        LlrbTree::contains(self, item)
+    }
+
+    fn len(&self) -> usize {
+        self.get_size()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.bst.iter()
+    }
+
+    fn min(&self) -> Option<&T> {
+        self.bst.get_min()
+    }
+
+    fn max(&self) -> Option<&T> {
+        self.bst.get_max()
+    }
+
+    fn successor(&self, item: &T) -> Option<&T> {
+        self.bst.get_successor(item)
+    }
+
+    fn predecessor(&self, item: &T) -> Option<&T> {
+        self.bst.get_predecessor(item)
+    }
+}
@@ -0,0 +1,295 @@
+pub use super::base_tree::Node;
+pub use generational_arena::Index;
+
+use alloc::vec::Vec;
+use core::fmt::Display;
+use core::iter::Enumerate;
+use core::ops;
+use core::slice;
+use generational_arena::Arena;
+
+use super::slots::{fill_slot, free_slot, get2_slots_mut, get_slot, get_slot_mut, Slot, SlotArray};
+
+// Storage for a tree's nodes, which hands out an `Index` for every node it stores and
+//  looks nodes up by it. The tree logic only ever goes through this trait, so backends can
+//  be swapped without changing how any tree balances itself.
+//
+// Indexing with a handle that no longer points to a stored node panics, just like it does
+//  with the arena. Whether a stale handle is caught at all depends on the backend, though.
+pub trait NodeStore<T: Display>:
+    Default + ops::Index<Index, Output = Node<T>> + ops::IndexMut<Index>
+{
+    type Iter<'a>: Iterator<Item = (Index, &'a Node<T>)>
+    where
+        Self: 'a,
+        T: 'a;
+
+    fn insert(&mut self, node: Node<T>) -> Index;
+
+    fn remove(&mut self, node_idx: Index) -> Option<Node<T>>;
+
+    // Mutable access to two different nodes at once. Panics when both handles are the same.
+    fn get2_mut(
+        &mut self,
+        first_idx: Index,
+        second_idx: Index,
+    ) -> (Option<&mut Node<T>>, Option<&mut Node<T>>);
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Visits every stored node in no particular order
+    fn iter(&self) -> Self::Iter<'_>;
+}
+
+// The default backend. Every handle carries a generation, so a handle to a removed node
+//  can never reach a node inserted after it, at the cost of a generation check per lookup.
+pub type ArenaStore<T> = Arena<Node<T>>;
+
+impl<T: Display> NodeStore<T> for ArenaStore<T> {
+    type Iter<'a>
+        = generational_arena::Iter<'a, Node<T>>
+    where
+        T: 'a;
+
+    fn insert(&mut self, node: Node<T>) -> Index {
+        Arena::insert(self, node)
+    }
+
+    fn remove(&mut self, node_idx: Index) -> Option<Node<T>> {
+        Arena::remove(self, node_idx)
+    }
+
+    fn get2_mut(
+        &mut self,
+        first_idx: Index,
+        second_idx: Index,
+    ) -> (Option<&mut Node<T>>, Option<&mut Node<T>>) {
+        Arena::get2_mut(self, first_idx, second_idx)
+    }
+
+    fn len(&self) -> usize {
+        Arena::len(self)
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        Arena::iter(self)
+    }
+}
+
+// Handles for the slab and array backends are plain slot positions, with no generation
+fn to_index(pos: usize) -> Index {
+    Index::from_raw_parts(pos, 0)
+}
+
+fn to_pos(node_idx: Index) -> usize {
+    node_idx.into_raw_parts().0
+}
+
+fn get_node<T: Display>(slots: &[Slot<Node<T>>], node_idx: Index) -> &Node<T> {
+    get_slot(slots, to_pos(node_idx)).expect("No node is stored for this index")
+}
+
+fn get_node_mut<T: Display>(slots: &mut [Slot<Node<T>>], node_idx: Index) -> &mut Node<T> {
+    get_slot_mut(slots, to_pos(node_idx)).expect("No node is stored for this index")
+}
+
+pub struct SlotIter<'a, T: Display> {
+    slots: Enumerate<slice::Iter<'a, Slot<Node<T>>>>,
+}
+
+impl<'a, T: Display> Iterator for SlotIter<'a, T> {
+    type Item = (Index, &'a Node<T>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.slots.find_map(|(pos, contents)| match contents {
+            Slot::Occupied(node) => Some((to_index(pos), node)),
+            Slot::Free(_) => None,
+        })
+    }
+}
+
+// Nodes kept in a growable `Vec`, reusing the slots of removed nodes. Lookups are a plain
+//  bounds-checked index, with no generation to compare, so a stale handle silently reaches
+//  whichever node took over its slot.
+#[derive(Clone)]
+pub struct SlabStore<T: Display> {
+    slots: Vec<Slot<Node<T>>>,
+    free_head: Option<usize>,
+    len: usize,
+}
+
+impl<T: Display> Default for SlabStore<T> {
+    fn default() -> Self {
+        SlabStore::new()
+    }
+}
+
+impl<T: Display> SlabStore<T> {
+    pub fn new() -> Self {
+        SlabStore::with_capacity(0)
+    }
+
+    pub fn with_capacity(capacity: usize) -> Self {
+        SlabStore {
+            slots: Vec::with_capacity(capacity),
+            free_head: None,
+            len: 0,
+        }
+    }
+}
+
+impl<T: Display> ops::Index<Index> for SlabStore<T> {
+    type Output = Node<T>;
+
+    fn index(&self, node_idx: Index) -> &Node<T> {
+        get_node(&self.slots, node_idx)
+    }
+}
+
+impl<T: Display> ops::IndexMut<Index> for SlabStore<T> {
+    fn index_mut(&mut self, node_idx: Index) -> &mut Node<T> {
+        get_node_mut(&mut self.slots, node_idx)
+    }
+}
+
+impl<T: Display> NodeStore<T> for SlabStore<T> {
+    type Iter<'a>
+        = SlotIter<'a, T>
+    where
+        T: 'a;
+
+    fn insert(&mut self, node: Node<T>) -> Index {
+        self.len += 1;
+
+        if self.free_head.is_none() {
+            self.slots.push(Slot::Occupied(node));
+            return to_index(self.slots.len() - 1);
+        }
+
+        let pos = fill_slot(&mut self.slots, &mut self.free_head, node)
+            .expect("The free list was just checked to be non-empty");
+        to_index(pos)
+    }
+
+    fn remove(&mut self, node_idx: Index) -> Option<Node<T>> {
+        let node = free_slot(&mut self.slots, &mut self.free_head, to_pos(node_idx))?;
+        self.len -= 1;
+
+        Some(node)
+    }
+
+    fn get2_mut(
+        &mut self,
+        first_idx: Index,
+        second_idx: Index,
+    ) -> (Option<&mut Node<T>>, Option<&mut Node<T>>) {
+        get2_slots_mut(&mut self.slots, to_pos(first_idx), to_pos(second_idx))
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        SlotIter {
+            slots: self.slots.iter().enumerate(),
+        }
+    }
+}
+
+// Nodes kept inline in an array of `N` slots, which is allocated once along with the tree
+//  and never grows. Like the slab, stale handles aren't detected. Inserting into a full
+//  store panics, so check `is_full` first when the tree's size isn't bounded up front, or
+//  use `RedBlackTree::try_insert`.
+//
+// Only the nodes are kept inline. `RedBlackTree` still tracks colors in a `HashMap`, so a
+//  red-black tree on this store allocates as it grows; `static_tree::StaticRedBlackTree`
+//  is the one that never allocates.
+#[derive(Clone)]
+pub struct ArrayStore<T: Display, const N: usize> {
+    slots: SlotArray<Node<T>, N>,
+    len: usize,
+}
+
+impl<T: Display, const N: usize> Default for ArrayStore<T, N> {
+    fn default() -> Self {
+        ArrayStore::new()
+    }
+}
+
+impl<T: Display, const N: usize> ArrayStore<T, N> {
+    pub fn new() -> Self {
+        ArrayStore {
+            slots: SlotArray::new(),
+            len: 0,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.slots.is_full()
+    }
+}
+
+impl<T: Display, const N: usize> ops::Index<Index> for ArrayStore<T, N> {
+    type Output = Node<T>;
+
+    fn index(&self, node_idx: Index) -> &Node<T> {
+        get_node(&self.slots.slots, node_idx)
+    }
+}
+
+impl<T: Display, const N: usize> ops::IndexMut<Index> for ArrayStore<T, N> {
+    fn index_mut(&mut self, node_idx: Index) -> &mut Node<T> {
+        get_node_mut(&mut self.slots.slots, node_idx)
+    }
+}
+
+impl<T: Display, const N: usize> NodeStore<T> for ArrayStore<T, N> {
+    type Iter<'a>
+        = SlotIter<'a, T>
+    where
+        T: 'a;
+
+    fn insert(&mut self, node: Node<T>) -> Index {
+        let pos = self
+            .slots
+            .fill(node)
+            .unwrap_or_else(|| panic!("Can't store more than {} nodes", N));
+        self.len += 1;
+
+        to_index(pos)
+    }
+
+    fn remove(&mut self, node_idx: Index) -> Option<Node<T>> {
+        let node = self.slots.free(to_pos(node_idx))?;
+        self.len -= 1;
+
+        Some(node)
+    }
+
+    fn get2_mut(
+        &mut self,
+        first_idx: Index,
+        second_idx: Index,
+    ) -> (Option<&mut Node<T>>, Option<&mut Node<T>>) {
+        get2_slots_mut(&mut self.slots.slots, to_pos(first_idx), to_pos(second_idx))
+    }
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        SlotIter {
+            slots: self.slots.slots.iter().enumerate(),
+        }
+    }
+}
@@ -0,0 +1,614 @@
+use super::red_black_tree::TreeColors;
+use super::search_tree::SearchTree;
+use super::tree_errs::NodeNotFoundErr;
+
+use std::cmp::PartialOrd;
+use std::ops::Bound;
+use std::sync::Arc;
+
+type Link<T> = Option<Arc<PersistentNode<T>>>;
+
+struct PersistentNode<T> {
+    color: TreeColors,
+    left: Link<T>,
+    data: T,
+    right: Link<T>,
+}
+
+// A red-black tree where every version stays valid after it has been changed. Nodes are
+//  never modified once built, so `insert` and `remove` copy only the nodes on the path
+//  from the root down to the change, and share every other node with the old version.
+//  That makes cloning a version O(1), and every update O(log n) in both time and memory.
+//
+// Nodes live behind `Arc` rather than in an arena, so that versions can be sent across
+//  threads and each node is freed once the last version using it is dropped. Insertion
+//  balances the way Okasaki describes, removal follows Kahrs:
+//  https://www.cs.kent.ac.uk/people/staff/smk/redblack/rb.html
+pub struct PersistentRedBlackTree<T: PartialOrd + Clone> {
+    root: Link<T>,
+    len: usize,
+}
+
+// Cloning a version only bumps the root's reference count
+impl<T: PartialOrd + Clone> Clone for PersistentRedBlackTree<T> {
+    fn clone(&self) -> Self {
+        PersistentRedBlackTree {
+            root: self.root.clone(),
+            len: self.len,
+        }
+    }
+}
+
+impl<T: PartialOrd + Clone> Default for PersistentRedBlackTree<T> {
+    fn default() -> Self {
+        PersistentRedBlackTree::new()
+    }
+}
+
+impl<T: PartialOrd + Clone> PersistentRedBlackTree<T> {
+    pub fn new() -> Self {
+        PersistentRedBlackTree { root: None, len: 0 }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.len
+    }
+
+    // Returns a new version holding the item as well. Like the other trees, duplicates
+    //  are allowed and go to the right of equal values.
+    pub fn insert(&self, item: T) -> Self {
+        PersistentRedBlackTree {
+            root: blacken(insert_into(&self.root, item)),
+            len: self.len + 1,
+        }
+    }
+
+    // Returns a new version without (one copy of) the item
+    pub fn remove(&self, item: &T) -> Result<Self, NodeNotFoundErr> {
+        if !self.contains(item) {
+            return Err(NodeNotFoundErr);
+        }
+
+        Ok(PersistentRedBlackTree {
+            root: blacken(remove_from(&self.root, item)),
+            len: self.len - 1,
+        })
+    }
+
+    pub fn contains(&self, item: &T) -> bool {
+        let mut cur_node_opt = self.root.as_deref();
+
+        while let Some(cur_node) = cur_node_opt {
+            if *item < cur_node.data {
+                cur_node_opt = cur_node.left.as_deref();
+            } else if cur_node.data < *item {
+                cur_node_opt = cur_node.right.as_deref();
+            } else {
+                // Incomparable values, like NaN, are neither smaller, larger nor equal
+                return cur_node.data == *item;
+            }
+        }
+
+        false
+    }
+
+    // True when both versions are the very same tree, which is how an unchanged version
+    //  can be told apart from an equal one in O(1)
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        match (&self.root, &other.root) {
+            (Some(root), Some(other_root)) => Arc::ptr_eq(root, other_root),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    pub fn get_min(&self) -> Option<&T> {
+        let mut cur_node = self.root.as_deref()?;
+        while let Some(left) = cur_node.left.as_deref() {
+            cur_node = left;
+        }
+
+        Some(&cur_node.data)
+    }
+
+    pub fn get_max(&self) -> Option<&T> {
+        let mut cur_node = self.root.as_deref()?;
+        while let Some(right) = cur_node.right.as_deref() {
+            cur_node = right;
+        }
+
+        Some(&cur_node.data)
+    }
+
+    // Smallest value strictly greater than `item`
+    pub fn get_successor(&self, item: &T) -> Option<&T> {
+        let mut successor = None;
+        let mut cur_node_opt = self.root.as_deref();
+
+        while let Some(cur_node) = cur_node_opt {
+            if *item < cur_node.data {
+                successor = Some(&cur_node.data);
+                cur_node_opt = cur_node.left.as_deref();
+            } else {
+                cur_node_opt = cur_node.right.as_deref();
+            }
+        }
+
+        successor
+    }
+
+    // Largest value strictly less than `item`
+    pub fn get_predecessor(&self, item: &T) -> Option<&T> {
+        let mut predecessor = None;
+        let mut cur_node_opt = self.root.as_deref();
+
+        while let Some(cur_node) = cur_node_opt {
+            if cur_node.data < *item {
+                predecessor = Some(&cur_node.data);
+                cur_node_opt = cur_node.right.as_deref();
+            } else {
+                cur_node_opt = cur_node.left.as_deref();
+            }
+        }
+
+        predecessor
+    }
+
+    // Walks the whole tree to check that the root is black, that values come out in order,
+    //  that `len` matches the node count and that no red node has a red child or a path
+    //  comes up short on black nodes. There are no parent links to check, since nodes are
+    //  shared between versions.
+    pub fn is_valid(&self) -> bool {
+        if is_red(&self.root) {
+            return false;
+        }
+
+        let values: Vec<&T> = self.iter().collect();
+
+        values.len() == self.len
+            && values.windows(2).all(|pair| pair[0] <= pair[1])
+            && get_valid_black_height(&self.root).is_some()
+    }
+
+    // Create a new iterator that visits values in sorted order
+    pub fn iter(&self) -> Iter<'_, T> {
+        let mut iter = Iter {
+            node_stack: Vec::new(),
+        };
+        iter.push_left_spine(self.root.as_deref());
+        iter
+    }
+
+    // Create a new iterator that starts at the first value within the start bound. Only
+    //  the path down to that value is pushed, so this costs O(log n) up front.
+    pub(crate) fn iter_from(&self, start: Bound<&T>) -> Iter<'_, T> {
+        let mut iter = Iter {
+            node_stack: Vec::new(),
+        };
+        let mut cur_node_opt = self.root.as_deref();
+
+        while let Some(cur_node) = cur_node_opt {
+            let is_in_bound = match start {
+                Bound::Included(start) => *start <= cur_node.data,
+                Bound::Excluded(start) => *start < cur_node.data,
+                Bound::Unbounded => true,
+            };
+
+            if is_in_bound {
+                iter.node_stack.push(cur_node);
+                cur_node_opt = cur_node.left.as_deref();
+            } else {
+                cur_node_opt = cur_node.right.as_deref();
+            }
+        }
+
+        iter
+    }
+}
+
+pub struct Iter<'a, T> {
+    node_stack: Vec<&'a PersistentNode<T>>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    fn push_left_spine(&mut self, node_opt: Option<&'a PersistentNode<T>>) {
+        let mut cur_node_opt = node_opt;
+        while let Some(cur_node) = cur_node_opt {
+            self.node_stack.push(cur_node);
+            cur_node_opt = cur_node.left.as_deref();
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.node_stack.pop()?;
+        self.push_left_spine(node.right.as_deref());
+
+        Some(&node.data)
+    }
+}
+
+fn new_node<T>(color: TreeColors, left: Link<T>, data: T, right: Link<T>) -> Link<T> {
+    Some(Arc::new(PersistentNode {
+        color,
+        left,
+        data,
+        right,
+    }))
+}
+
+fn red<T>(left: Link<T>, data: T, right: Link<T>) -> Link<T> {
+    new_node(TreeColors::Red, left, data, right)
+}
+
+fn black<T>(left: Link<T>, data: T, right: Link<T>) -> Link<T> {
+    new_node(TreeColors::Black, left, data, right)
+}
+
+fn is_red<T>(link: &Link<T>) -> bool {
+    link.as_ref()
+        .is_some_and(|node| node.color == TreeColors::Red)
+}
+
+// Unlike `!is_red`, terminating nodes don't count
+fn is_black_node<T>(link: &Link<T>) -> bool {
+    link.as_ref()
+        .is_some_and(|node| node.color == TreeColors::Black)
+}
+
+// Copies a node with a different color, sharing its children
+fn recolor<T: Clone>(link: Link<T>, color: TreeColors) -> Link<T> {
+    match link {
+        Some(node) if node.color != color => new_node(
+            color,
+            node.left.clone(),
+            node.data.clone(),
+            node.right.clone(),
+        ),
+        _ => link,
+    }
+}
+
+fn blacken<T: Clone>(link: Link<T>) -> Link<T> {
+    recolor(link, TreeColors::Black)
+}
+
+fn redden<T: Clone>(link: Link<T>) -> Link<T> {
+    recolor(link, TreeColors::Red)
+}
+
+// Recursive function that copies the path down to where the item belongs. Black nodes
+//  rebalance on the way back up, while red nodes are left for their black parent to fix.
+fn insert_into<T: PartialOrd + Clone>(link: &Link<T>, item: T) -> Link<T> {
+    let node = match link {
+        Some(node) => node,
+        None => return red(None, item, None),
+    };
+
+    let data = node.data.clone();
+    match (node.color, item < node.data) {
+        (TreeColors::Black, true) => {
+            balance(insert_into(&node.left, item), data, node.right.clone())
+        }
+        (TreeColors::Black, false) => {
+            balance(node.left.clone(), data, insert_into(&node.right, item))
+        }
+        (TreeColors::Red, true) => red(insert_into(&node.left, item), data, node.right.clone()),
+        (TreeColors::Red, false) => red(node.left.clone(), data, insert_into(&node.right, item)),
+    }
+}
+
+// Builds a black node, unless one of its children is red with a red child of its own. That
+//  red-red pair and its parent are then rearranged into a red node with two black children,
+//  which pushes the violation up a level. Two red children are split up the same way.
+fn balance<T: Clone>(left: Link<T>, data: T, right: Link<T>) -> Link<T> {
+    if is_red(&left) && is_red(&right) {
+        return red(blacken(left), data, blacken(right));
+    }
+
+    if let Some(left_node) = left.as_ref().filter(|node| node.color == TreeColors::Red) {
+        if let Some(left_left) = left_node
+            .left
+            .as_ref()
+            .filter(|node| node.color == TreeColors::Red)
+        {
+            return red(
+                black(
+                    left_left.left.clone(),
+                    left_left.data.clone(),
+                    left_left.right.clone(),
+                ),
+                left_node.data.clone(),
+                black(left_node.right.clone(), data, right),
+            );
+        }
+
+        if let Some(left_right) = left_node
+            .right
+            .as_ref()
+            .filter(|node| node.color == TreeColors::Red)
+        {
+            return red(
+                black(
+                    left_node.left.clone(),
+                    left_node.data.clone(),
+                    left_right.left.clone(),
+                ),
+                left_right.data.clone(),
+                black(left_right.right.clone(), data, right),
+            );
+        }
+    }
+
+    if let Some(right_node) = right.as_ref().filter(|node| node.color == TreeColors::Red) {
+        if let Some(right_right) = right_node
+            .right
+            .as_ref()
+            .filter(|node| node.color == TreeColors::Red)
+        {
+            return red(
+                black(left, data, right_node.left.clone()),
+                right_node.data.clone(),
+                black(
+                    right_right.left.clone(),
+                    right_right.data.clone(),
+                    right_right.right.clone(),
+                ),
+            );
+        }
+
+        if let Some(right_left) = right_node
+            .left
+            .as_ref()
+            .filter(|node| node.color == TreeColors::Red)
+        {
+            return red(
+                black(left, data, right_left.left.clone()),
+                right_left.data.clone(),
+                black(
+                    right_left.right.clone(),
+                    right_node.data.clone(),
+                    right_node.right.clone(),
+                ),
+            );
+        }
+    }
+
+    black(left, data, right)
+}
+
+// Recursive function that copies the path down to the item and fuses its children in its
+//  place. Removing from under a black node leaves that side one black node short, which
+//  `balance_left` and `balance_right` make up for on the way back up.
+fn remove_from<T: PartialOrd + Clone>(link: &Link<T>, item: &T) -> Link<T> {
+    let node = link.as_ref()?;
+    let data = node.data.clone();
+
+    if *item < node.data {
+        let left = remove_from(&node.left, item);
+        if is_black_node(&node.left) {
+            balance_left(left, data, node.right.clone())
+        } else {
+            red(left, data, node.right.clone())
+        }
+    } else if node.data < *item {
+        let right = remove_from(&node.right, item);
+        if is_black_node(&node.right) {
+            balance_right(node.left.clone(), data, right)
+        } else {
+            red(node.left.clone(), data, right)
+        }
+    } else if node.data == *item {
+        fuse(node.left.clone(), node.right.clone())
+    } else {
+        // `remove` checks `contains` first, so an incomparable item never gets this far
+        link.clone()
+    }
+}
+
+// Rebuilds a node whose left subtree is one black node short of its right one
+fn balance_left<T: Clone>(left: Link<T>, data: T, right: Link<T>) -> Link<T> {
+    if is_red(&left) {
+        return red(blacken(left), data, right);
+    }
+
+    let right_node = right
+        .as_ref()
+        .expect("Proper tree coloring ensures that the taller side is never empty");
+
+    match right_node.color {
+        TreeColors::Black => balance(left, data, redden(right)),
+        TreeColors::Red => {
+            let right_left = right_node
+                .left
+                .as_ref()
+                .filter(|node| node.color == TreeColors::Black)
+                .expect("Proper tree coloring ensures that a red node's children are black");
+
+            red(
+                black(left, data, right_left.left.clone()),
+                right_left.data.clone(),
+                balance(
+                    right_left.right.clone(),
+                    right_node.data.clone(),
+                    redden(right_node.right.clone()),
+                ),
+            )
+        }
+    }
+}
+
+// Mirror of `balance_left`, for a right subtree that is one black node short
+fn balance_right<T: Clone>(left: Link<T>, data: T, right: Link<T>) -> Link<T> {
+    if is_red(&right) {
+        return red(left, data, blacken(right));
+    }
+
+    let left_node = left
+        .as_ref()
+        .expect("Proper tree coloring ensures that the taller side is never empty");
+
+    match left_node.color {
+        TreeColors::Black => balance(redden(left), data, right),
+        TreeColors::Red => {
+            let left_right = left_node
+                .right
+                .as_ref()
+                .filter(|node| node.color == TreeColors::Black)
+                .expect("Proper tree coloring ensures that a red node's children are black");
+
+            red(
+                balance(
+                    redden(left_node.left.clone()),
+                    left_node.data.clone(),
+                    left_right.left.clone(),
+                ),
+                left_right.data.clone(),
+                black(left_right.right.clone(), data, right),
+            )
+        }
+    }
+}
+
+// Recursive function that joins the two subtrees of a removed node, every value on the
+//  left being less than or equal to every value on the right
+fn fuse<T: Clone>(left: Link<T>, right: Link<T>) -> Link<T> {
+    let (left_node, right_node) = match (&left, &right) {
+        (None, _) => return right,
+        (_, None) => return left,
+        (Some(left_node), Some(right_node)) => (left_node, right_node),
+    };
+
+    match (left_node.color, right_node.color) {
+        (TreeColors::Black, TreeColors::Red) => red(
+            fuse(left.clone(), right_node.left.clone()),
+            right_node.data.clone(),
+            right_node.right.clone(),
+        ),
+        (TreeColors::Red, TreeColors::Black) => red(
+            left_node.left.clone(),
+            left_node.data.clone(),
+            fuse(left_node.right.clone(), right.clone()),
+        ),
+        (TreeColors::Red, TreeColors::Red) => {
+            let middle = fuse(left_node.right.clone(), right_node.left.clone());
+            match middle.as_ref().filter(|node| node.color == TreeColors::Red) {
+                Some(middle_node) => red(
+                    red(
+                        left_node.left.clone(),
+                        left_node.data.clone(),
+                        middle_node.left.clone(),
+                    ),
+                    middle_node.data.clone(),
+                    red(
+                        middle_node.right.clone(),
+                        right_node.data.clone(),
+                        right_node.right.clone(),
+                    ),
+                ),
+                None => red(
+                    left_node.left.clone(),
+                    left_node.data.clone(),
+                    red(middle, right_node.data.clone(), right_node.right.clone()),
+                ),
+            }
+        }
+        (TreeColors::Black, TreeColors::Black) => {
+            let middle = fuse(left_node.right.clone(), right_node.left.clone());
+            match middle.as_ref().filter(|node| node.color == TreeColors::Red) {
+                Some(middle_node) => red(
+                    black(
+                        left_node.left.clone(),
+                        left_node.data.clone(),
+                        middle_node.left.clone(),
+                    ),
+                    middle_node.data.clone(),
+                    black(
+                        middle_node.right.clone(),
+                        right_node.data.clone(),
+                        right_node.right.clone(),
+                    ),
+                ),
+                None => balance_left(
+                    left_node.left.clone(),
+                    left_node.data.clone(),
+                    black(middle, right_node.data.clone(), right_node.right.clone()),
+                ),
+            }
+        }
+    }
+}
+
+// Recursive function that returns a subtree's black height, or None if any path
+//  through it breaks the red-black properties
+fn get_valid_black_height<T>(link: &Link<T>) -> Option<usize> {
+    let node = match link {
+        Some(node) => node,
+        None => return Some(0),
+    };
+
+    if node.color == TreeColors::Red && (is_red(&node.left) || is_red(&node.right)) {
+        return None;
+    }
+
+    let left_height = get_valid_black_height(&node.left)?;
+    let right_height = get_valid_black_height(&node.right)?;
+
+    if left_height != right_height {
+        return None;
+    }
+
+    match node.color {
+        TreeColors::Black => Some(left_height + 1),
+        TreeColors::Red => Some(left_height),
+    }
+}
+
+// Updating through the trait replaces the version in place, while any clones taken
+//  beforehand keep the old one
+impl<T: PartialOrd + Clone> SearchTree<T> for PersistentRedBlackTree<T> {
+    type Iter<'a>
+        = Iter<'a, T>
+    where
+        T: 'a;
+
+    fn insert(&mut self, item: T) {
+        *self = PersistentRedBlackTree::insert(self, item);
+    }
+
+    fn remove(&mut self, item: &T) -> Result<(), NodeNotFoundErr> {
+        *self = PersistentRedBlackTree::remove(self, item)?;
+        Ok(())
+    }
+
+    fn contains(&self, item: &T) -> bool {
+        PersistentRedBlackTree::contains(self, item)
+    }
+
+    fn len(&self) -> usize {
+        self.get_size()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        PersistentRedBlackTree::iter(self)
+    }
+
+    fn min(&self) -> Option<&T> {
+        self.get_min()
+    }
+
+    fn max(&self) -> Option<&T> {
+        self.get_max()
+    }
+
+    fn successor(&self, item: &T) -> Option<&T> {
+        self.get_successor(item)
+    }
+
+    fn predecessor(&self, item: &T) -> Option<&T> {
+        self.get_predecessor(item)
+    }
+}
@@ -0,0 +1,643 @@
+use super::rb_balance::{get_inorder_successor, get_subtree_min, link_children, RedBlackLinks};
+
+use alloc::boxed::Box;
+use alloc::collections::VecDeque;
+use alloc::vec;
+use alloc::vec::Vec;
+use core::marker::PhantomData;
+use core::ops::{Bound, RangeBounds};
+use core::ptr::{self, NonNull};
+
+// Performance focused versions of `binary_search_tree::BinarySearchTree` and
+//  `red_black_tree::RedBlackTree`, with the same API. Both wrap `RawTree`, the pointer-linked
+//  counterpart of `base_tree::InternalBinarySearchTree`.
+mod binary_search_tree;
+mod red_black_tree;
+
+pub use self::binary_search_tree::BinarySearchTree;
+pub use self::red_black_tree::RedBlackTree;
+
+// Set in a node's parent pointer when the node is red
+const RED_BIT: usize = 1;
+
+type Link<T> = Option<NonNull<RawNode<T>>>;
+
+pub(crate) struct RawNode<T> {
+    data: T,
+    left: Link<T>,
+    right: Link<T>,
+    // The parent pointer, with the node's color packed into its lowest bit. Nodes hold
+    //  pointers themselves, so they're aligned well enough for that bit to always be free.
+    //  Nodes of the binary search tree are never red.
+    parent_and_color: *mut RawNode<T>,
+}
+
+// Nodes are boxed individually and linked by raw pointers, so following a link is a single
+//  load rather than an arena lookup with its generation and bounds checks, and a red-black
+//  node's color lives in its parent pointer instead of a separate hash map. Terminating
+//  nodes are `None`.
+//
+// All of the unsafe code is kept within this module and its two submodules, and relies on
+//  the tree owning every node reachable from `root`, and no others.
+pub(crate) struct RawTree<T> {
+    root: Link<T>,
+    len: usize,
+    _owns: PhantomData<Box<RawNode<T>>>,
+}
+
+// The tree owns its nodes outright, so sharing or sending it is as safe as doing so with T
+unsafe impl<T: Send> Send for RawTree<T> {}
+unsafe impl<T: Sync> Sync for RawTree<T> {}
+
+impl<T> Default for RawTree<T> {
+    fn default() -> Self {
+        RawTree::new()
+    }
+}
+
+// Every node passed in must be owned by a tree that outlives the reference
+unsafe fn node_ref<'a, T>(node: NonNull<RawNode<T>>) -> &'a RawNode<T> {
+    &*node.as_ptr()
+}
+
+impl<T> RawTree<T> {
+    fn new() -> Self {
+        RawTree {
+            root: None,
+            len: 0,
+            _owns: PhantomData,
+        }
+    }
+
+    // Allocates a detached, black node. It stays leaked until it's linked into a tree, which
+    //  then owns it.
+    fn new_node(data: T) -> NonNull<RawNode<T>> {
+        NonNull::from(Box::leak(Box::new(RawNode {
+            data,
+            left: None,
+            right: None,
+            parent_and_color: ptr::null_mut(),
+        })))
+    }
+
+    // Frees a node, handing back its value.
+    //
+    // Safety: the node has to come from `new_node`, and nothing may point to it anymore
+    unsafe fn free_node(node: NonNull<RawNode<T>>) -> T {
+        Box::from_raw(node.as_ptr()).data
+    }
+
+    // Every handle passed to these two came from the tree's own links, so it points to a live
+    //  node the tree owns, and the borrow of the tree keeps it that way
+    fn data(&self, node: NonNull<RawNode<T>>) -> &T {
+        unsafe { &node_ref(node).data }
+    }
+
+    fn data_mut(&mut self, node: NonNull<RawNode<T>>) -> &mut T {
+        unsafe { &mut (*node.as_ptr()).data }
+    }
+
+    // Create a new iterator that visits values in sorted order
+    fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            tree: self,
+            next_node: self.root.map(|root| get_subtree_min(self, root)),
+        }
+    }
+
+    // Create a new iterator w/ a stack for DFS taversal
+    fn dfs_iter(&self) -> DfsIter<'_, T> {
+        DfsIter {
+            node_stack: self.root.into_iter().collect(),
+            _tree: PhantomData,
+        }
+    }
+
+    // Create a new iterator w/ a queue for BFS traversal
+    fn bfs_iter(&self) -> BfsIter<'_, T> {
+        BfsIter {
+            node_queue: self.root.into_iter().collect(),
+            _tree: PhantomData,
+        }
+    }
+
+    // Number of levels in the tree, walking it one level at a time
+    fn get_height(&self) -> usize {
+        let mut height = 0;
+        let mut level: Vec<NonNull<RawNode<T>>> = self.root.into_iter().collect();
+
+        while !level.is_empty() {
+            height += 1;
+            level = level
+                .iter()
+                .flat_map(|node| self.left(*node).into_iter().chain(self.right(*node)))
+                .collect();
+        }
+
+        height
+    }
+
+    // Counts the nodes in the subtree under the given node
+    fn get_subtree_size(&self, node_opt: Link<T>) -> usize {
+        DfsIter {
+            node_stack: node_opt.into_iter().collect(),
+            _tree: PhantomData::<&RawNode<T>>,
+        }
+        .count()
+    }
+
+    // Counts the nodes of whichever of the two subtrees is smaller, along with whether that
+    //  was the first one. Both subtrees are walked in lockstep, so this only costs as much
+    //  as visiting the smaller one.
+    fn get_smaller_subtree_size(&self, first_opt: Link<T>, second_opt: Link<T>) -> (usize, bool) {
+        let subtree_iter = |node_opt: Link<T>| DfsIter {
+            node_stack: node_opt.into_iter().collect(),
+            _tree: PhantomData::<&RawNode<T>>,
+        };
+        let mut first_iter = subtree_iter(first_opt);
+        let mut second_iter = subtree_iter(second_opt);
+        let mut size = 0;
+
+        loop {
+            match (first_iter.next(), second_iter.next()) {
+                (Some(_), Some(_)) => size += 1,
+                (None, _) => return (size, true),
+                (Some(_), None) => return (size, false),
+            }
+        }
+    }
+
+    // Relinks the existing nodes of the subtree under `root` into a perfectly balanced
+    //  shape, in place of the old subtree. Returns the subtree's new root.
+    fn rebuild_subtree(&mut self, root: NonNull<RawNode<T>>) -> NonNull<RawNode<T>> {
+        let parent_opt = self.parent(root);
+        let size = self.get_subtree_size(Some(root));
+        let mut nodes = Vec::with_capacity(size);
+        let mut next_node = Some(get_subtree_min(self, root));
+
+        // The subtree's nodes are the first `size` nodes in order from its minimum
+        for _ in 0..size {
+            let node = next_node.expect("The subtree holds `size` nodes");
+            nodes.push(node);
+            next_node = get_inorder_successor(self, node);
+        }
+
+        let new_root = self
+            .link_balanced(&nodes)
+            .expect("A subtree always holds at least its own root");
+        self.set_parent(new_root, parent_opt);
+
+        match parent_opt {
+            Some(parent) if self.left(parent) == Some(root) => {
+                self.set_left(parent, Some(new_root))
+            }
+            Some(parent) => self.set_right(parent, Some(new_root)),
+            None => self.root = Some(new_root),
+        }
+
+        new_root
+    }
+
+    // Recursive function that links a sorted run of nodes into a balanced subtree, using
+    //  the middle node as the root. Returns the subtree's root, with its parent left unset.
+    fn link_balanced(&mut self, nodes: &[NonNull<RawNode<T>>]) -> Link<T> {
+        if nodes.is_empty() {
+            return None;
+        }
+
+        let mid = (nodes.len() - 1) / 2;
+        let left_opt = self.link_balanced(&nodes[..mid]);
+        let right_opt = self.link_balanced(&nodes[mid + 1..]);
+
+        link_children(self, nodes[mid], left_opt, right_opt);
+        Some(nodes[mid])
+    }
+
+    // Builds a perfectly balanced subtree out of the next `len` sorted items, returning its
+    //  detached root. Nodes at `red_depth` are colored red, and every other node black. The
+    //  caller accounts for the new nodes in `len`.
+    fn build_balanced(
+        &mut self,
+        items: &mut impl Iterator<Item = T>,
+        len: usize,
+        depth: usize,
+        red_depth: Option<usize>,
+    ) -> Link<T> {
+        if len == 0 {
+            return None;
+        }
+
+        // The left half is built first so that items are consumed in order
+        let left_len = (len - 1) / 2;
+        let left_opt = self.build_balanced(items, left_len, depth + 1, red_depth);
+
+        let data = items
+            .next()
+            .expect("Callers always supply at least `len` items");
+        let node = RawTree::new_node(data);
+
+        let right_opt = self.build_balanced(items, len - left_len - 1, depth + 1, red_depth);
+        link_children(self, node, left_opt, right_opt);
+        self.set_red(node, red_depth == Some(depth));
+
+        Some(node)
+    }
+
+    // Empties the tree, handing back every value in sorted order
+    fn drain_sorted(&mut self) -> Vec<T> {
+        self.drain_sorted_with_nodes()
+            .into_iter()
+            .map(|(_, data)| data)
+            .collect()
+    }
+
+    // Empties the tree, handing back every value in sorted order along with the node it
+    //  was taken from. The nodes are all freed, so they're only good for telling apart.
+    fn drain_sorted_with_nodes(&mut self) -> Vec<(NonNull<RawNode<T>>, T)> {
+        let mut nodes = Vec::with_capacity(self.len);
+        let mut next_node = self.root.map(|root| get_subtree_min(self, root));
+
+        while let Some(node) = next_node {
+            nodes.push(node);
+            next_node = get_inorder_successor(self, node);
+        }
+
+        self.root = None;
+        self.len = 0;
+        nodes
+            .into_iter()
+            // Safety: the tree no longer links to any of the nodes, and each one is freed once
+            .map(|node| (node, unsafe { RawTree::free_node(node) }))
+            .collect()
+    }
+}
+
+impl<T: PartialOrd> RawTree<T> {
+    // Links a detached node in as a leaf, after any values equal to its own
+    fn link_leaf(&mut self, new_node: NonNull<RawNode<T>>) {
+        let mut parent_opt = None;
+        let mut cur_node_opt = self.root;
+        let mut is_left = false;
+
+        while let Some(cur_node) = cur_node_opt {
+            parent_opt = Some(cur_node);
+            is_left = self.data(new_node) < self.data(cur_node);
+            cur_node_opt = if is_left {
+                self.left(cur_node)
+            } else {
+                self.right(cur_node)
+            };
+        }
+
+        self.set_parent(new_node, parent_opt);
+        match parent_opt {
+            Some(parent) if is_left => self.set_left(parent, Some(new_node)),
+            Some(parent) => self.set_right(parent, Some(new_node)),
+            None => self.root = Some(new_node),
+        }
+
+        self.len += 1;
+    }
+
+    fn find_node(&self, item: &T) -> Link<T> {
+        let mut cur_node_opt = self.root;
+
+        while let Some(cur_node) = cur_node_opt {
+            let data = self.data(cur_node);
+            if *data == *item {
+                break;
+            }
+
+            cur_node_opt = if *item < *data {
+                self.left(cur_node)
+            } else {
+                self.right(cur_node)
+            };
+        }
+
+        cur_node_opt
+    }
+
+    // Smallest value strictly greater than `item`
+    fn get_successor(&self, item: &T) -> Option<&T> {
+        let mut successor = None;
+        let mut cur_node_opt = self.root;
+
+        while let Some(cur_node) = cur_node_opt {
+            let data = self.data(cur_node);
+            if *item < *data {
+                successor = Some(data);
+                cur_node_opt = self.left(cur_node);
+            } else {
+                cur_node_opt = self.right(cur_node);
+            }
+        }
+
+        successor
+    }
+
+    // Largest value strictly less than `item`
+    fn get_predecessor(&self, item: &T) -> Option<&T> {
+        let mut predecessor = None;
+        let mut cur_node_opt = self.root;
+
+        while let Some(cur_node) = cur_node_opt {
+            let data = self.data(cur_node);
+            if *data < *item {
+                predecessor = Some(data);
+                cur_node_opt = self.right(cur_node);
+            } else {
+                cur_node_opt = self.left(cur_node);
+            }
+        }
+
+        predecessor
+    }
+
+    // Collects every node whose value falls inside `range`, in sorted order
+    fn get_range_nodes(&self, range: &impl RangeBounds<T>) -> Vec<NonNull<RawNode<T>>> {
+        let mut next_node = None;
+        let mut cur_node_opt = self.root;
+
+        // Find the first node past the start bound, the same way `get_successor` does
+        while let Some(cur_node) = cur_node_opt {
+            let data = self.data(cur_node);
+            let past_start = match range.start_bound() {
+                Bound::Included(start) => data >= start,
+                Bound::Excluded(start) => data > start,
+                Bound::Unbounded => true,
+            };
+
+            if past_start {
+                next_node = Some(cur_node);
+                cur_node_opt = self.left(cur_node);
+            } else {
+                cur_node_opt = self.right(cur_node);
+            }
+        }
+
+        let mut nodes = Vec::new();
+        while let Some(node) = next_node {
+            let data = self.data(node);
+            let past_end = match range.end_bound() {
+                Bound::Included(end) => data > end,
+                Bound::Excluded(end) => data >= end,
+                Bound::Unbounded => false,
+            };
+
+            if past_end {
+                break;
+            }
+            nodes.push(node);
+            next_node = get_inorder_successor(self, node);
+        }
+
+        nodes
+    }
+
+    // Collects every node whose value fails the `keep` check, in sorted order
+    fn get_rejected_nodes(&self, mut keep: impl FnMut(&T) -> bool) -> Vec<NonNull<RawNode<T>>> {
+        let mut nodes = Vec::new();
+        let mut next_node = self.root.map(|root| get_subtree_min(self, root));
+
+        while let Some(node) = next_node {
+            if !keep(self.data(node)) {
+                nodes.push(node);
+            }
+            next_node = get_inorder_successor(self, node);
+        }
+
+        nodes
+    }
+
+    // Splits the subtree under `root_opt` into two detached subtrees: one holding every
+    //  value less than `key`, and one holding the rest. We walk a single path down from
+    //  the root, hooking each visited node onto the right spine of the lesser tree or the
+    //  left spine of the greater tree, so no node is ever copied or rebalanced.
+    fn split_subtree(&mut self, root_opt: Link<T>, key: &T) -> (Link<T>, Link<T>) {
+        let mut lesser_root = None;
+        let mut greater_root = None;
+        let mut lesser_hook: Link<T> = None;
+        let mut greater_hook: Link<T> = None;
+        let mut cur_node_opt = root_opt;
+
+        while let Some(cur_node) = cur_node_opt {
+            if *self.data(cur_node) < *key {
+                // This node and its left subtree are all lesser, keep looking to the right
+                match lesser_hook {
+                    Some(hook) => self.set_right(hook, Some(cur_node)),
+                    None => lesser_root = Some(cur_node),
+                }
+                self.set_parent(cur_node, lesser_hook);
+                lesser_hook = Some(cur_node);
+                cur_node_opt = self.right(cur_node);
+            } else {
+                match greater_hook {
+                    Some(hook) => self.set_left(hook, Some(cur_node)),
+                    None => greater_root = Some(cur_node),
+                }
+                self.set_parent(cur_node, greater_hook);
+                greater_hook = Some(cur_node);
+                cur_node_opt = self.left(cur_node);
+            }
+        }
+
+        // The last hooked nodes may still point across the split, so cut them loose
+        if let Some(hook) = lesser_hook {
+            self.set_right(hook, None);
+        }
+
+        if let Some(hook) = greater_hook {
+            self.set_left(hook, None);
+        }
+
+        (lesser_root, greater_root)
+    }
+
+    // Checks that values are in order, that the root has no parent, that every child's
+    //  parent pointer leads back to it and that `len` matches the number of linked nodes
+    fn is_linked_in_order(&self) -> bool {
+        if self.root.is_some_and(|root| self.parent(root).is_some()) {
+            return false;
+        }
+
+        // Child links are checked first, since the in-order walk follows parent pointers
+        let mut node_count = 0;
+        let mut pending: Vec<NonNull<RawNode<T>>> = self.root.into_iter().collect();
+        while let Some(node) = pending.pop() {
+            for child in self.left(node).into_iter().chain(self.right(node)) {
+                if self.parent(child) != Some(node) {
+                    return false;
+                }
+                pending.push(child);
+            }
+            node_count += 1;
+        }
+
+        let values: Vec<&T> = self.iter().collect();
+        node_count == self.len
+            && values.len() == self.len
+            && values.windows(2).all(|pair| pair[0] <= pair[1])
+    }
+}
+
+// Safety: every handle the tree code passes in came from the tree's own links, so it points
+//  to a live node the tree owns, or to a detached node on its way in or out. Links are read
+//  and written through the raw pointer rather than a reference to the whole node, so that no
+//  reference outlives a single access.
+impl<T> RedBlackLinks for RawTree<T> {
+    type Handle = NonNull<RawNode<T>>;
+
+    fn root(&self) -> Link<T> {
+        self.root
+    }
+
+    fn set_root(&mut self, root_opt: Link<T>) {
+        self.root = root_opt;
+    }
+
+    fn left(&self, node: NonNull<RawNode<T>>) -> Link<T> {
+        unsafe { (*node.as_ptr()).left }
+    }
+
+    fn right(&self, node: NonNull<RawNode<T>>) -> Link<T> {
+        unsafe { (*node.as_ptr()).right }
+    }
+
+    fn parent(&self, node: NonNull<RawNode<T>>) -> Link<T> {
+        let parent_and_color = unsafe { (*node.as_ptr()).parent_and_color };
+        NonNull::new(parent_and_color.map_addr(|addr| addr & !RED_BIT))
+    }
+
+    fn set_left(&mut self, node: NonNull<RawNode<T>>, left_opt: Link<T>) {
+        unsafe { (*node.as_ptr()).left = left_opt }
+    }
+
+    fn set_right(&mut self, node: NonNull<RawNode<T>>, right_opt: Link<T>) {
+        unsafe { (*node.as_ptr()).right = right_opt }
+    }
+
+    fn set_parent(&mut self, node: NonNull<RawNode<T>>, parent_opt: Link<T>) {
+        let color_bit = self.is_red(node) as usize;
+        let parent_ptr = parent_opt.map_or(ptr::null_mut(), NonNull::as_ptr);
+        unsafe { (*node.as_ptr()).parent_and_color = parent_ptr.map_addr(|addr| addr | color_bit) }
+    }
+
+    fn is_red(&self, node: NonNull<RawNode<T>>) -> bool {
+        unsafe { (*node.as_ptr()).parent_and_color.addr() & RED_BIT != 0 }
+    }
+
+    fn set_red(&mut self, node: NonNull<RawNode<T>>, is_red: bool) {
+        unsafe {
+            let parent_and_color = &mut (*node.as_ptr()).parent_and_color;
+            *parent_and_color = parent_and_color.map_addr(|addr| {
+                if is_red {
+                    addr | RED_BIT
+                } else {
+                    addr & !RED_BIT
+                }
+            });
+        }
+    }
+}
+
+// Copies every node, colors included, without recursing. Each copy is linked in as soon as
+//  it's made, so if cloning a value panics, dropping the partial tree frees every copy.
+impl<T: Clone> Clone for RawTree<T> {
+    fn clone(&self) -> Self {
+        let mut cloned = RawTree::new();
+        let mut pending = vec![];
+        pending.extend(self.root.map(|root| (root, None, true)));
+
+        while let Some((node, new_parent_opt, is_left)) = pending.pop() {
+            let new_node = RawTree::new_node(self.data(node).clone());
+            cloned.set_parent(new_node, new_parent_opt);
+            cloned.set_red(new_node, self.is_red(node));
+
+            match new_parent_opt {
+                Some(new_parent) if is_left => cloned.set_left(new_parent, Some(new_node)),
+                Some(new_parent) => cloned.set_right(new_parent, Some(new_node)),
+                None => cloned.root = Some(new_node),
+            }
+
+            pending.extend(self.left(node).map(|left| (left, Some(new_node), true)));
+            pending.extend(self.right(node).map(|right| (right, Some(new_node), false)));
+        }
+
+        cloned.len = self.len;
+        cloned
+    }
+}
+
+// Frees every node without recursing, so that dropping can't overflow the stack
+impl<T> Drop for RawTree<T> {
+    fn drop(&mut self) {
+        let mut pending: Vec<NonNull<RawNode<T>>> = self.root.take().into_iter().collect();
+
+        while let Some(node) = pending.pop() {
+            // Safety: each node is reachable from the root exactly once, and is freed here
+            //  only after its children were taken off it
+            let node = unsafe { Box::from_raw(node.as_ptr()) };
+            pending.extend(node.left.into_iter().chain(node.right));
+        }
+    }
+}
+
+// Walks the tree in order by following parent pointers, so it needs no stack
+pub struct Iter<'a, T> {
+    tree: &'a RawTree<T>,
+    next_node: Link<T>,
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.next_node?;
+        self.next_node = get_inorder_successor(self.tree, node);
+
+        Some(self.tree.data(node))
+    }
+}
+
+pub struct DfsIter<'a, T> {
+    node_stack: Vec<NonNull<RawNode<T>>>,
+    _tree: PhantomData<&'a RawNode<T>>,
+}
+
+// Iterate through nodes using depth-first traversal
+impl<'a, T> Iterator for DfsIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.node_stack.pop()?;
+
+        // Safety: the iterator borrows the tree, so every node stays alive and unchanged
+        let node_ref = unsafe { node_ref(node) };
+        self.node_stack
+            .extend(node_ref.right.into_iter().chain(node_ref.left));
+
+        Some(&node_ref.data)
+    }
+}
+
+pub struct BfsIter<'a, T> {
+    node_queue: VecDeque<NonNull<RawNode<T>>>,
+    _tree: PhantomData<&'a RawNode<T>>,
+}
+
+// Iterate through nodes using breadth-first traversal
+impl<'a, T> Iterator for BfsIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.node_queue.pop_front()?;
+
+        // Safety: the iterator borrows the tree, so every node stays alive and unchanged
+        let node_ref = unsafe { node_ref(node) };
+        self.node_queue
+            .extend(node_ref.left.into_iter().chain(node_ref.right));
+
+        Some(&node_ref.data)
+    }
+}
@@ -0,0 +1,440 @@
+use super::{BfsIter, DfsIter, Iter, RawNode, RawTree};
+
+use crate::base_tree::merge_sorted;
+use crate::binary_search_tree::{plan_optimal_tree, Scapegoat};
+use crate::rb_balance::{
+    get_subtree_max, get_subtree_min, link_children, rotate_left, rotate_right, splice_node,
+    RedBlackLinks,
+};
+use crate::search_tree::SearchTree;
+use crate::tree_errs::{InvalidAlphaErr, InvalidProbabilityErr, NodeNotFoundErr};
+
+use alloc::vec::Vec;
+use core::cmp::PartialOrd;
+use core::fmt::Display;
+use core::mem::swap;
+use core::ops::RangeBounds;
+use core::ptr::NonNull;
+
+// Performance focused version of `binary_search_tree::BinarySearchTree`, with the same API,
+//  including the scapegoat mode and optimal trees. Nodes are linked by raw pointers rather
+//  than kept in an arena, so splitting off part of the tree or appending one that doesn't
+//  overlap never has to move nodes into another arena.
+#[derive(Default)]
+pub struct BinarySearchTree<T: PartialOrd + Display + Default> {
+    bst: RawTree<T>,
+    scapegoat: Option<Scapegoat>,
+}
+
+impl<T: PartialOrd + Display + Default> BinarySearchTree<T> {
+    pub fn new() -> Self {
+        BinarySearchTree {
+            bst: RawTree::new(),
+            scapegoat: None,
+        }
+    }
+
+    // Creates a tree that keeps itself balanced scapegoat-style. See
+    //  `binary_search_tree::BinarySearchTree::with_scapegoat_alpha`.
+    pub fn with_scapegoat_alpha(alpha: f64) -> Result<Self, InvalidAlphaErr> {
+        if !(0.5..1.0).contains(&alpha) {
+            return Err(InvalidAlphaErr);
+        }
+
+        Ok(BinarySearchTree {
+            bst: RawTree::new(),
+            scapegoat: Some(Scapegoat { alpha, max_size: 0 }),
+        })
+    }
+
+    // Builds the tree with the lowest expected search cost for a fixed set of values, given
+    //  how likely each one is to be looked up. Returns the tree along with its expected
+    //  cost. See `binary_search_tree::BinarySearchTree::from_access_probabilities`.
+    pub fn from_access_probabilities(
+        items: Vec<(T, f64)>,
+    ) -> Result<(Self, f64), InvalidProbabilityErr> {
+        let mut items = items;
+        let (roots, cost) = plan_optimal_tree(&mut items)?;
+        let len = items.len();
+
+        let mut tree = BinarySearchTree::new();
+        let nodes: Vec<NonNull<RawNode<T>>> = items
+            .into_iter()
+            .map(|(data, _)| RawTree::new_node(data))
+            .collect();
+
+        tree.bst.root = tree.link_optimal(&nodes, &roots, 0, len);
+        tree.bst.len = len;
+        Ok((tree, cost))
+    }
+
+    // Recursive function that links the nodes for the values in start..end under the root
+    //  picked for that range, returning the subtree's root
+    fn link_optimal(
+        &mut self,
+        nodes: &[NonNull<RawNode<T>>],
+        roots: &[usize],
+        start: usize,
+        end: usize,
+    ) -> Option<NonNull<RawNode<T>>> {
+        if start == end {
+            return None;
+        }
+
+        let root = roots[start * (nodes.len() + 1) + end];
+        let left_opt = self.link_optimal(nodes, roots, start, root);
+        let right_opt = self.link_optimal(nodes, roots, root + 1, end);
+
+        link_children(&mut self.bst, nodes[root], left_opt, right_opt);
+        Some(nodes[root])
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.bst.len
+    }
+
+    pub fn insert(&mut self, val: T) {
+        let new_leaf = RawTree::new_node(val);
+        self.bst.link_leaf(new_leaf);
+        self.rebuild_scapegoat(new_leaf);
+    }
+
+    // Scapegoat mode only: if the new leaf is deeper than alpha allows, walk back up
+    //  towards the root, counting subtree sizes as we go, until we find an ancestor
+    //  with a child holding more than alpha of its nodes. That ancestor is the scapegoat,
+    //  and its subtree gets rebuilt perfectly balanced.
+    fn rebuild_scapegoat(&mut self, new_leaf: NonNull<RawNode<T>>) {
+        let size = self.get_size();
+        let scapegoat = match self.scapegoat.as_mut() {
+            Some(scapegoat) => scapegoat,
+            None => return,
+        };
+        scapegoat.max_size = scapegoat.max_size.max(size);
+        let (alpha, max_depth) = (scapegoat.alpha, scapegoat.get_max_depth(size));
+
+        let mut depth = 0;
+        let mut cur_node = new_leaf;
+        while let Some(parent) = self.bst.parent(cur_node) {
+            depth += 1;
+            cur_node = parent;
+        }
+
+        if depth <= max_depth {
+            return;
+        }
+
+        let mut child = new_leaf;
+        let mut child_size = 1;
+
+        while let Some(parent) = self.bst.parent(child) {
+            let sibling_opt = if self.bst.left(parent) == Some(child) {
+                self.bst.right(parent)
+            } else {
+                self.bst.left(parent)
+            };
+            let parent_size = child_size + 1 + self.bst.get_subtree_size(sibling_opt);
+
+            if child_size as f64 > alpha * parent_size as f64 {
+                self.bst.rebuild_subtree(parent);
+                return;
+            }
+
+            child = parent;
+            child_size = parent_size;
+        }
+    }
+
+    // Scapegoat mode only: rebuilds the whole tree once it has shrunk below alpha of its
+    //  largest size since the last full rebuild, or, if the tree may have been deepened
+    //  by something other than an insert, once it's deeper than alpha allows.
+    fn rebuild_if_unbalanced(&mut self, may_have_deepened: bool) {
+        let size = self.get_size();
+        let scapegoat = match self.scapegoat.as_mut() {
+            Some(scapegoat) => scapegoat,
+            None => return,
+        };
+        scapegoat.max_size = scapegoat.max_size.max(size);
+
+        let too_sparse = (size as f64) < scapegoat.alpha * scapegoat.max_size as f64;
+        let too_deep =
+            may_have_deepened && self.bst.get_height() > scapegoat.get_max_depth(size) + 1;
+
+        if too_sparse || too_deep {
+            scapegoat.max_size = size;
+            if let Some(root) = self.bst.root {
+                self.bst.rebuild_subtree(root);
+            }
+        }
+    }
+
+    // Number of levels in the tree
+    pub fn get_height(&self) -> usize {
+        self.bst.get_height()
+    }
+
+    // Reshapes the tree to be perfectly balanced (every level full, apart from the last)
+    //  using the Day-Stout-Warren algorithm. Runs in O(n) time with O(1) extra space,
+    //  since it only rotates the existing nodes around.
+    pub fn rebalance(&mut self) {
+        let size = self.get_size();
+        self.tree_to_vine();
+
+        // Number of nodes in the largest perfect tree that fits, i.e. 2^floor(log2(n + 1)) - 1
+        let mut perfect_size = (1 << (usize::BITS - (size + 1).leading_zeros() - 1)) - 1;
+        self.compress_vine(size - perfect_size);
+
+        while perfect_size > 1 {
+            perfect_size /= 2;
+            self.compress_vine(perfect_size);
+        }
+
+        if let Some(scapegoat) = self.scapegoat.as_mut() {
+            scapegoat.max_size = size;
+        }
+    }
+
+    // Rotates every left child up until the tree is a "vine" of right children in sorted order
+    fn tree_to_vine(&mut self) {
+        let mut cur_node_opt = self.bst.root;
+
+        while let Some(cur_node) = cur_node_opt {
+            match self.bst.left(cur_node) {
+                Some(left) => {
+                    rotate_right(&mut self.bst, cur_node);
+                    cur_node_opt = Some(left);
+                }
+                None => cur_node_opt = self.bst.right(cur_node),
+            }
+        }
+    }
+
+    // Walks down the right spine, rotating every other node left under its right child,
+    //  `count` times
+    fn compress_vine(&mut self, count: usize) {
+        let mut cur_node_opt = self.bst.root;
+
+        for _ in 0..count {
+            let cur_node = match cur_node_opt {
+                Some(cur_node) => cur_node,
+                None => return,
+            };
+
+            let right = self
+                .bst
+                .right(cur_node)
+                .expect("The vine holds enough nodes for every compression");
+            rotate_left(&mut self.bst, cur_node);
+            cur_node_opt = self.bst.right(right);
+        }
+    }
+
+    pub fn contains(&self, item: &T) -> bool {
+        self.bst.find_node(item).is_some()
+    }
+
+    pub fn remove(&mut self, item: &T) -> Result<(), NodeNotFoundErr> {
+        let node_to_remove = self.bst.find_node(item).ok_or(NodeNotFoundErr)?;
+        drop(self.remove_node(node_to_remove));
+        self.rebuild_if_unbalanced(false);
+
+        Ok(())
+    }
+
+    // Unlinks a node from the tree, frees it and hands back its value. A node with two
+    //  children is replaced by its inorder successor, which leaves the same shape as the
+    //  arena-based tree copying the successor's value over and removing the successor.
+    fn remove_node(&mut self, node: NonNull<RawNode<T>>) -> T {
+        splice_node(&mut self.bst, node);
+        self.bst.len -= 1;
+
+        // Safety: the node was just unlinked, so nothing in the tree points to it anymore
+        unsafe { RawTree::free_node(node) }
+    }
+
+    // Removes every value that fails the `keep` check
+    pub fn retain(&mut self, keep: impl FnMut(&T) -> bool) {
+        let nodes = self.bst.get_rejected_nodes(keep);
+        self.remove_nodes(nodes, drop);
+    }
+
+    // Removes every value within `range`, handing them back in sorted order
+    pub fn drain_range(&mut self, range: impl RangeBounds<T>) -> Vec<T> {
+        let nodes = self.bst.get_range_nodes(&range);
+        let mut removed = Vec::with_capacity(nodes.len());
+
+        self.remove_nodes(nodes, |data| removed.push(data));
+        removed.reverse();
+        removed
+    }
+
+    // Removes every value within `range`, returning how many were removed. The values are
+    //  dropped as they come out of the tree rather than collected first.
+    pub fn remove_range(&mut self, range: impl RangeBounds<T>) -> usize {
+        let nodes = self.bst.get_range_nodes(&range);
+        let removed_count = nodes.len();
+
+        self.remove_nodes(nodes, drop);
+        removed_count
+    }
+
+    // Removes a sorted run of nodes, handing each removed value to `on_removed`, largest
+    //  first. Going backwards removes nodes in the same order as the arena-based tree does,
+    //  so both trees end up with the same shape.
+    fn remove_nodes(&mut self, nodes: Vec<NonNull<RawNode<T>>>, mut on_removed: impl FnMut(T)) {
+        for node in nodes.into_iter().rev() {
+            on_removed(self.remove_node(node));
+        }
+
+        self.rebuild_if_unbalanced(false);
+    }
+
+    // Moves every value greater than or equal to `key` into a new tree. The split walks a
+    //  single path down the tree, and both halves keep their nodes where they are, so the
+    //  only other cost is counting the smaller half to keep both sizes right.
+    pub fn split_off(&mut self, key: &T) -> Self {
+        let mut split_tree = BinarySearchTree {
+            bst: RawTree::new(),
+            scapegoat: self.scapegoat,
+        };
+        let size = self.get_size();
+        let (lesser_root, greater_root) = self.bst.split_subtree(self.bst.root, key);
+        let (smaller_size, lesser_is_smaller) =
+            self.bst.get_smaller_subtree_size(lesser_root, greater_root);
+
+        self.bst.root = lesser_root;
+        split_tree.bst.root = greater_root;
+        if lesser_is_smaller {
+            self.bst.len = smaller_size;
+            split_tree.bst.len = size - smaller_size;
+        } else {
+            self.bst.len = size - smaller_size;
+            split_tree.bst.len = smaller_size;
+        }
+
+        // Splitting never makes a node deeper, but either half may now be too sparse
+        if let Some(scapegoat) = split_tree.scapegoat.as_mut() {
+            scapegoat.max_size = 0;
+        }
+        split_tree.rebuild_if_unbalanced(false);
+        self.rebuild_if_unbalanced(false);
+
+        split_tree
+    }
+
+    // Moves every value out of `other` and into this tree. When the two trees don't overlap,
+    //  the smaller one is hung off the edge of the larger one as-is. Otherwise both trees
+    //  are merged in sorted order and rebuilt as a single balanced tree.
+    pub fn append(&mut self, other: &mut Self) {
+        if other.bst.root.is_none() {
+            return;
+        }
+
+        // Always hang the smaller tree off the larger one
+        if self.get_size() < other.get_size() {
+            swap(&mut self.bst, &mut other.bst);
+        }
+
+        let (root, other_root) = match (self.bst.root, other.bst.root) {
+            (Some(root), Some(other_root)) => (root, other_root),
+            _ => return,
+        };
+
+        let min_node = get_subtree_min(&self.bst, root);
+        let max_node = get_subtree_max(&self.bst, root);
+        let other_min = other.bst.data(get_subtree_min(&other.bst, other_root));
+        let other_max = other.bst.data(get_subtree_max(&other.bst, other_root));
+        let size = self.get_size() + other.get_size();
+
+        if *self.bst.data(max_node) <= *other_min {
+            other.bst.root = None;
+            other.bst.len = 0;
+
+            self.bst.set_right(max_node, Some(other_root));
+            self.bst.set_parent(other_root, Some(max_node));
+        } else if *other_max < *self.bst.data(min_node) {
+            other.bst.root = None;
+            other.bst.len = 0;
+
+            self.bst.set_left(min_node, Some(other_root));
+            self.bst.set_parent(other_root, Some(min_node));
+        } else {
+            let merged = merge_sorted(self.bst.drain_sorted(), other.bst.drain_sorted());
+            let merged_len = merged.len();
+
+            self.bst.root = self
+                .bst
+                .build_balanced(&mut merged.into_iter(), merged_len, 0, None);
+        }
+        self.bst.len = size;
+
+        // Hanging one tree off the other can leave it deeper than alpha allows
+        other.rebuild_if_unbalanced(false);
+        self.rebuild_if_unbalanced(true);
+    }
+
+    // Follows every pointer in the tree to check that values are in order, that `len`
+    //  matches the node count and that each child's parent pointer leads back to it
+    pub fn is_valid(&self) -> bool {
+        self.bst.is_linked_in_order()
+    }
+
+    // Create a new iterator that visits values in sorted order
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.bst.iter()
+    }
+
+    // Create a new iterator w/ a stack for DFS taversal
+    pub fn dfs_iter(&self) -> DfsIter<'_, T> {
+        self.bst.dfs_iter()
+    }
+
+    // Create a new iterator w/ a queue for BFS traversal
+    pub fn bfs_iter(&self) -> BfsIter<'_, T> {
+        self.bst.bfs_iter()
+    }
+}
+
+impl<T: PartialOrd + Display + Default> SearchTree<T> for BinarySearchTree<T> {
+    type Iter<'a>
+        = Iter<'a, T>
+    where
+        T: 'a;
+
+    fn insert(&mut self, item: T) {
+        BinarySearchTree::insert(self, item)
+    }
+
+    fn remove(&mut self, item: &T) -> Result<(), NodeNotFoundErr> {
+        BinarySearchTree::remove(self, item)
+    }
+
+    fn contains(&self, item: &T) -> bool {
+        BinarySearchTree::contains(self, item)
+    }
+
+    fn len(&self) -> usize {
+        self.get_size()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.bst.iter()
+    }
+
+    fn min(&self) -> Option<&T> {
+        let min_node = get_subtree_min(&self.bst, self.bst.root?);
+        Some(self.bst.data(min_node))
+    }
+
+    fn max(&self) -> Option<&T> {
+        let max_node = get_subtree_max(&self.bst, self.bst.root?);
+        Some(self.bst.data(max_node))
+    }
+
+    fn successor(&self, item: &T) -> Option<&T> {
+        self.bst.get_successor(item)
+    }
+
+    fn predecessor(&self, item: &T) -> Option<&T> {
+        self.bst.get_predecessor(item)
+    }
+}
@@ -0,0 +1,453 @@
+use super::{BfsIter, DfsIter, Iter, RawNode, RawTree};
+
+use crate::base_tree::merge_sorted;
+use crate::rb_balance::{
+    fix_red_red, get_black_height, get_subtree_max, get_subtree_min, get_valid_black_height,
+    group_nodes, join_subtrees, link_two_three_four, split_subtree, unlink_node, RedBlackLinks,
+};
+use crate::search_tree::SearchTree;
+use crate::set_ops::{Difference, Intersection, SymmetricDifference, Union};
+use crate::tree_errs::{MalformedTreeErr, NodeNotFoundErr};
+use crate::two_three_four::TwoThreeFourTree;
+
+use alloc::collections::VecDeque;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::PartialOrd;
+use core::fmt::{self, Display, Formatter};
+use core::mem::{swap, take};
+use core::ops::RangeBounds;
+use core::ptr::NonNull;
+
+// Performance focused version of `red_black_tree::RedBlackTree`, with the same API. Nodes
+//  are linked by raw pointers rather than kept in an arena, and each node's color is packed
+//  into its parent pointer. The balancing itself is the shared code in `rb_balance`.
+//
+// Terminating nodes are `None`, which stand in for the black sentinel leaves of the
+//  textbook algorithm (CLRS, chapter 13). As nodes never move between arenas, splitting and
+//  appending trees that don't overlap only restructure O(log n) nodes.
+#[derive(Clone)]
+pub struct RedBlackTree<T: PartialOrd + Display + Default> {
+    bst: RawTree<T>,
+}
+
+impl<T: PartialOrd + Display + Default> Default for RedBlackTree<T> {
+    fn default() -> Self {
+        RedBlackTree::new()
+    }
+}
+
+impl<T: PartialOrd + Display + Default> RedBlackTree<T> {
+    pub fn new() -> Self {
+        RedBlackTree {
+            bst: RawTree::new(),
+        }
+    }
+
+    // Builds the red-black tree that encodes a 2-3-4 tree. Every node becomes a black node,
+    //  with any extra keys hung off of it as red children.
+    pub fn from_two_three_four(tree: TwoThreeFourTree<T>) -> Result<Self, MalformedTreeErr> {
+        tree.validate()?;
+
+        let mut rb_tree = RedBlackTree::new();
+        rb_tree.bst.root = tree.root.map(|root| {
+            link_two_three_four(&mut rb_tree.bst, root, &mut |bst, data| {
+                bst.len += 1;
+                RawTree::new_node(data)
+            })
+        });
+
+        Ok(rb_tree)
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.bst.len
+    }
+
+    pub fn insert(&mut self, item: T) {
+        let new_node = RawTree::new_node(item);
+        self.bst.set_red(new_node, true);
+        self.bst.link_leaf(new_node);
+
+        fix_red_red(&mut self.bst, new_node);
+    }
+
+    pub fn contains(&self, item: &T) -> bool {
+        self.bst.find_node(item).is_some()
+    }
+
+    pub fn remove(&mut self, item: &T) -> Result<(), NodeNotFoundErr> {
+        let node = self.bst.find_node(item).ok_or(NodeNotFoundErr)?;
+        self.remove_node(node);
+
+        Ok(())
+    }
+
+    // Unlinks a node from the tree, frees it and hands back its value
+    fn remove_node(&mut self, node: NonNull<RawNode<T>>) -> T {
+        unlink_node(&mut self.bst, node);
+        self.bst.len -= 1;
+
+        // Safety: the node was just unlinked, so nothing in the tree points to it anymore
+        unsafe { RawTree::free_node(node) }
+    }
+
+    // Removes every value that fails the `keep` check
+    pub fn retain(&mut self, keep: impl FnMut(&T) -> bool) {
+        let nodes = self.bst.get_rejected_nodes(keep);
+        self.remove_nodes(nodes, drop);
+    }
+
+    // Removes every value within `range`, handing them back in sorted order
+    pub fn drain_range(&mut self, range: impl RangeBounds<T>) -> Vec<T> {
+        let nodes = self.bst.get_range_nodes(&range);
+        let mut removed = Vec::with_capacity(nodes.len());
+
+        self.remove_nodes(nodes, |data| removed.push(data));
+        removed.reverse();
+        removed
+    }
+
+    // Removes every value within `range`, returning how many were removed. The values are
+    //  dropped as they come out of the tree rather than collected first.
+    pub fn remove_range(&mut self, range: impl RangeBounds<T>) -> usize {
+        let nodes = self.bst.get_range_nodes(&range);
+        let removed_count = nodes.len();
+
+        self.remove_nodes(nodes, drop);
+        removed_count
+    }
+
+    // Removes a sorted run of nodes, handing each removed value to `on_removed`, largest
+    //  first. Each removal costs O(log n) rebalancing work, so once that adds up to more
+    //  than visiting every node once, we pull the surviving values out in order and
+    //  rebuild a balanced tree from them instead. Going backwards removes nodes in the same
+    //  order as the arena-based tree does, so both trees end up with the same shape.
+    fn remove_nodes(&mut self, nodes: Vec<NonNull<RawNode<T>>>, mut on_removed: impl FnMut(T)) {
+        let size = self.get_size();
+        let removal_cost = nodes.len() * (size.max(1).ilog2() as usize + 1);
+
+        if removal_cost < size {
+            for node in nodes.into_iter().rev() {
+                on_removed(self.remove_node(node));
+            }
+            return;
+        }
+
+        let mut kept = Vec::with_capacity(size - nodes.len());
+        let mut nodes_to_remove = nodes.into_iter().rev().peekable();
+
+        for (node, data) in self.bst.drain_sorted_with_nodes().into_iter().rev() {
+            if nodes_to_remove.peek() == Some(&node) {
+                nodes_to_remove.next();
+                on_removed(data);
+            } else {
+                kept.push(data);
+            }
+        }
+
+        kept.reverse();
+        self.rebuild_from_sorted(kept);
+    }
+
+    // Moves every value greater than or equal to `key` into a new tree. The tree is cut
+    //  apart by recursively splitting and re-joining subtrees along a single root-to-leaf
+    //  path, which restructures only O(log n) nodes. Both halves keep their nodes where they
+    //  are, so the only other cost is counting the smaller half to keep both sizes right.
+    pub fn split_off(&mut self, key: &T) -> Self {
+        let mut split_tree = RedBlackTree::new();
+        let root_opt = self.bst.root;
+
+        if root_opt.is_none() {
+            return split_tree;
+        }
+
+        let black_height = get_black_height(&self.bst, root_opt);
+        let (lesser, greater) =
+            split_subtree(&mut self.bst, root_opt, black_height, &mut |bst, node| {
+                *bst.data(node) < *key
+            });
+        let (smaller_size, lesser_is_smaller) =
+            self.bst.get_smaller_subtree_size(lesser.0, greater.0);
+        let size = self.get_size();
+
+        self.bst.root = lesser.0;
+        split_tree.bst.root = greater.0;
+        if lesser_is_smaller {
+            self.bst.len = smaller_size;
+            split_tree.bst.len = size - smaller_size;
+        } else {
+            self.bst.len = size - smaller_size;
+            split_tree.bst.len = smaller_size;
+        }
+
+        split_tree
+    }
+
+    // Moves every value out of `other` and into this tree. When the two trees don't overlap,
+    //  they're joined in O(log n). Otherwise both trees are merged in sorted order and rebuilt.
+    pub fn append(&mut self, other: &mut Self) {
+        if other.bst.root.is_none() {
+            return;
+        }
+
+        // Borrow the middle node from the larger tree, like the arena-based tree does, so
+        //  that both end up with the same shape
+        if self.get_size() < other.get_size() {
+            swap(self, other);
+        }
+
+        let (root, other_root) = match (self.bst.root, other.bst.root) {
+            (Some(root), Some(other_root)) => (root, other_root),
+            _ => return,
+        };
+
+        let min_node = get_subtree_min(&self.bst, root);
+        let max_node = get_subtree_max(&self.bst, root);
+        let other_min = other.bst.data(get_subtree_min(&other.bst, other_root));
+        let other_max = other.bst.data(get_subtree_max(&other.bst, other_root));
+        let size = self.get_size() + other.get_size();
+
+        if *self.bst.data(max_node) <= *other_min {
+            let other_height = get_black_height(&other.bst, Some(other_root));
+            other.bst.root = None;
+            other.bst.len = 0;
+
+            // Borrow our largest node to sit between the two trees
+            unlink_node(&mut self.bst, max_node);
+            let own_root_opt = self.bst.root;
+            let own_height = get_black_height(&self.bst, own_root_opt);
+
+            join_subtrees(
+                &mut self.bst,
+                (own_root_opt, own_height),
+                max_node,
+                (Some(other_root), other_height),
+            );
+        } else if *other_max < *self.bst.data(min_node) {
+            let other_height = get_black_height(&other.bst, Some(other_root));
+            other.bst.root = None;
+            other.bst.len = 0;
+
+            // Borrow our smallest node to sit between the two trees
+            unlink_node(&mut self.bst, min_node);
+            let own_root_opt = self.bst.root;
+            let own_height = get_black_height(&self.bst, own_root_opt);
+
+            join_subtrees(
+                &mut self.bst,
+                (Some(other_root), other_height),
+                min_node,
+                (own_root_opt, own_height),
+            );
+        } else {
+            let merged = merge_sorted(self.bst.drain_sorted(), other.bst.drain_sorted());
+            self.rebuild_from_sorted(merged);
+        }
+
+        self.bst.len = size;
+    }
+
+    // Replaces the (empty) tree with a perfectly balanced one. Every level is black apart
+    //  from the last one when it is only partially filled, which is colored red so that all
+    //  paths keep the same black height.
+    fn rebuild_from_sorted(&mut self, items: Vec<T>) {
+        let len = items.len();
+        let red_depth = if (len + 1).is_power_of_two() {
+            None
+        } else {
+            Some(len.ilog2() as usize)
+        };
+
+        self.bst.root = self
+            .bst
+            .build_balanced(&mut items.into_iter(), len, 0, red_depth);
+        self.bst.len = len;
+    }
+
+    // Views the tree as the 2-3-4 tree it encodes, where every black node is merged with
+    //  its red children into a single node of up to three keys. Print the result for a
+    //  level by level picture of the tree.
+    pub fn to_two_three_four(&self) -> TwoThreeFourTree<&T> {
+        TwoThreeFourTree {
+            root: self.bst.root.map(|root| group_nodes(&self.bst, root)),
+        }
+        .map(|node| self.bst.data(node))
+    }
+
+    // Same as `to_two_three_four`, but moves the values out of the tree
+    pub fn into_two_three_four(mut self) -> TwoThreeFourTree<T> {
+        let nodes = TwoThreeFourTree {
+            root: self.bst.root.map(|root| group_nodes(&self.bst, root)),
+        };
+        nodes.map(|node| take(self.bst.data_mut(node)))
+    }
+
+    pub fn get_min(&self) -> Option<&T> {
+        let min_node = get_subtree_min(&self.bst, self.bst.root?);
+        Some(self.bst.data(min_node))
+    }
+
+    pub fn get_max(&self) -> Option<&T> {
+        let max_node = get_subtree_max(&self.bst, self.bst.root?);
+        Some(self.bst.data(max_node))
+    }
+
+    // Smallest value strictly greater than `item`
+    pub fn get_successor(&self, item: &T) -> Option<&T> {
+        self.bst.get_successor(item)
+    }
+
+    // Largest value strictly less than `item`
+    pub fn get_predecessor(&self, item: &T) -> Option<&T> {
+        self.bst.get_predecessor(item)
+    }
+
+    // Follows every pointer in the tree to check that values are in order, that `len`
+    //  matches the node count, that each child's parent pointer leads back to it, that the
+    //  root is black and that no red node has a red child or a path comes up short on
+    //  black nodes.
+    pub fn is_valid(&self) -> bool {
+        if self.bst.root.is_some_and(|root| self.bst.is_red(root)) {
+            return false;
+        }
+
+        self.bst.is_linked_in_order() && get_valid_black_height(&self.bst, self.bst.root).is_some()
+    }
+
+    // Create a new iterator that visits values in sorted order
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.bst.iter()
+    }
+
+    // Lazily visits, in order, the values found in either tree
+    pub fn union<'a>(&'a self, other: &'a Self) -> Union<Iter<'a, T>> {
+        Union {
+            first: self.iter().peekable(),
+            second: other.iter().peekable(),
+        }
+    }
+
+    // Lazily visits, in order, the values found in both trees
+    pub fn intersection<'a>(&'a self, other: &'a Self) -> Intersection<Iter<'a, T>> {
+        Intersection {
+            first: self.iter().peekable(),
+            second: other.iter().peekable(),
+        }
+    }
+
+    // Lazily visits, in order, the values found in this tree but not in `other`
+    pub fn difference<'a>(&'a self, other: &'a Self) -> Difference<Iter<'a, T>> {
+        Difference {
+            first: self.iter().peekable(),
+            second: other.iter().peekable(),
+        }
+    }
+
+    // Lazily visits, in order, the values found in exactly one of the two trees
+    pub fn symmetric_difference<'a>(&'a self, other: &'a Self) -> SymmetricDifference<Iter<'a, T>> {
+        SymmetricDifference {
+            first: self.iter().peekable(),
+            second: other.iter().peekable(),
+        }
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.get_size() <= other.get_size() && self.difference(other).next().is_none()
+    }
+
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.intersection(other).next().is_none()
+    }
+
+    // Create a new iterator w/ a stack for DFS taversal
+    pub fn dfs_iter(&self) -> DfsIter<'_, T> {
+        self.bst.dfs_iter()
+    }
+
+    // Create a new iterator w/ a queue for BFS traversal
+    pub fn bfs_iter(&self) -> BfsIter<'_, T> {
+        self.bst.bfs_iter()
+    }
+
+    // Private method for printing node diagnostic data. Nodes are named by their address.
+    fn node_to_str(&self, node: NonNull<RawNode<T>>) -> String {
+        let color = if self.bst.is_red(node) {
+            "Red"
+        } else {
+            "Black"
+        };
+        format!(
+            "I: {:?}, C:{}, Data: {}, L: {:?}, R: {:?}, P: {:?}",
+            node,
+            color,
+            self.bst.data(node),
+            self.bst.left(node),
+            self.bst.right(node),
+            self.bst.parent(node)
+        )
+    }
+}
+
+impl<T: PartialOrd + Display + Default> SearchTree<T> for RedBlackTree<T> {
+    type Iter<'a>
+        = Iter<'a, T>
+    where
+        T: 'a;
+
+    fn insert(&mut self, item: T) {
+        RedBlackTree::insert(self, item)
+    }
+
+    fn remove(&mut self, item: &T) -> Result<(), NodeNotFoundErr> {
+        RedBlackTree::remove(self, item)
+    }
+
+    fn contains(&self, item: &T) -> bool {
+        RedBlackTree::contains(self, item)
+    }
+
+    fn len(&self) -> usize {
+        self.get_size()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        RedBlackTree::iter(self)
+    }
+
+    fn min(&self) -> Option<&T> {
+        self.get_min()
+    }
+
+    fn max(&self) -> Option<&T> {
+        self.get_max()
+    }
+
+    fn successor(&self, item: &T) -> Option<&T> {
+        self.get_successor(item)
+    }
+
+    fn predecessor(&self, item: &T) -> Option<&T> {
+        self.get_predecessor(item)
+    }
+}
+
+impl<T: PartialOrd + Display + Default> Display for RedBlackTree<T> {
+    // Simple BFS traversing method that prints each node's information for diagnostic purposes
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let mut nodes: VecDeque<NonNull<RawNode<T>>> = self.bst.root.into_iter().collect();
+        let mut node_strs = Vec::new();
+
+        while let Some(node) = nodes.pop_front() {
+            node_strs.push(self.node_to_str(node));
+            nodes.extend(self.bst.left(node).into_iter().chain(self.bst.right(node)));
+        }
+
+        write!(f, "{}", node_strs.join("\n"))
+    }
+}
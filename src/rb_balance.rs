@@ -0,0 +1,584 @@
+#[cfg(feature = "alloc")]
+use super::two_three_four::TwoThreeFourNode;
+
+#[cfg(feature = "alloc")]
+use alloc::vec;
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+
+// The red-black balancing shared by every parent-linked red-black tree in the crate:
+//  `red_black_tree::RedBlackTree`, `raw::RedBlackTree` and `static_tree::StaticRedBlackTree`.
+//  Each of them stores its nodes and colors its own way, and implements `RedBlackLinks` to
+//  let this code follow and relink them. Terminating nodes are `None`, and count as black.
+//  Only needs `core`, so that the allocation-free tree can use it too. The splitting,
+//  joining and 2-3-4 grouping helpers further down are only used by the trees with a heap.
+//  `raw::BinarySearchTree` borrows the plain relinking and rotations as well, and simply
+//  never colors a node red.
+pub(crate) trait RedBlackLinks {
+    type Handle: Copy + PartialEq;
+
+    fn root(&self) -> Option<Self::Handle>;
+
+    fn set_root(&mut self, root_opt: Option<Self::Handle>);
+
+    fn left(&self, node: Self::Handle) -> Option<Self::Handle>;
+
+    fn right(&self, node: Self::Handle) -> Option<Self::Handle>;
+
+    fn parent(&self, node: Self::Handle) -> Option<Self::Handle>;
+
+    fn set_left(&mut self, node: Self::Handle, left_opt: Option<Self::Handle>);
+
+    fn set_right(&mut self, node: Self::Handle, right_opt: Option<Self::Handle>);
+
+    fn set_parent(&mut self, node: Self::Handle, parent_opt: Option<Self::Handle>);
+
+    fn is_red(&self, node: Self::Handle) -> bool;
+
+    fn set_red(&mut self, node: Self::Handle, is_red: bool);
+}
+
+// Terminating nodes are black
+fn is_red<L: RedBlackLinks>(tree: &L, node_opt: Option<L::Handle>) -> bool {
+    node_opt.is_some_and(|node| tree.is_red(node))
+}
+
+// Painting a terminating node black is a no-op
+fn paint_black<L: RedBlackLinks>(tree: &mut L, node_opt: Option<L::Handle>) {
+    if let Some(node) = node_opt {
+        tree.set_red(node, false);
+    }
+}
+
+pub(crate) fn get_subtree_min<L: RedBlackLinks>(tree: &L, node: L::Handle) -> L::Handle {
+    let mut cur_node = node;
+    while let Some(left) = tree.left(cur_node) {
+        cur_node = left;
+    }
+    cur_node
+}
+
+pub(crate) fn get_subtree_max<L: RedBlackLinks>(tree: &L, node: L::Handle) -> L::Handle {
+    let mut cur_node = node;
+    while let Some(right) = tree.right(cur_node) {
+        cur_node = right;
+    }
+    cur_node
+}
+
+// Node that follows `node` in order, found by following parent links rather than a stack
+pub(crate) fn get_inorder_successor<L: RedBlackLinks>(
+    tree: &L,
+    node: L::Handle,
+) -> Option<L::Handle> {
+    if let Some(right) = tree.right(node) {
+        return Some(get_subtree_min(tree, right));
+    }
+
+    // Climb until we come up from a left child, whose parent is next
+    let mut child = node;
+    let mut parent_opt = tree.parent(node);
+    while let Some(parent) = parent_opt {
+        if tree.left(parent) == Some(child) {
+            break;
+        }
+        child = parent;
+        parent_opt = tree.parent(parent);
+    }
+
+    parent_opt
+}
+
+// Puts `new_child_opt` where `old_child` hangs off its parent
+pub(crate) fn replace_child<L: RedBlackLinks>(
+    tree: &mut L,
+    old_child: L::Handle,
+    new_child_opt: Option<L::Handle>,
+) {
+    let parent_opt = tree.parent(old_child);
+
+    match parent_opt {
+        Some(parent) if tree.left(parent) == Some(old_child) => {
+            tree.set_left(parent, new_child_opt)
+        }
+        Some(parent) => tree.set_right(parent, new_child_opt),
+        None => tree.set_root(new_child_opt),
+    }
+
+    if let Some(new_child) = new_child_opt {
+        tree.set_parent(new_child, parent_opt);
+    }
+}
+
+// Rotates a node down to the left, its right child taking its place
+pub(crate) fn rotate_left<L: RedBlackLinks>(tree: &mut L, node: L::Handle) {
+    let right = tree
+        .right(node)
+        .expect("A node only rotates left when it has a right child");
+    let inner_opt = tree.left(right);
+
+    tree.set_right(node, inner_opt);
+    if let Some(inner) = inner_opt {
+        tree.set_parent(inner, Some(node));
+    }
+
+    replace_child(tree, node, Some(right));
+    tree.set_left(right, Some(node));
+    tree.set_parent(node, Some(right));
+}
+
+// Rotates a node down to the right, its left child taking its place
+pub(crate) fn rotate_right<L: RedBlackLinks>(tree: &mut L, node: L::Handle) {
+    let left = tree
+        .left(node)
+        .expect("A node only rotates right when it has a left child");
+    let inner_opt = tree.right(left);
+
+    tree.set_left(node, inner_opt);
+    if let Some(inner) = inner_opt {
+        tree.set_parent(inner, Some(node));
+    }
+
+    replace_child(tree, node, Some(left));
+    tree.set_right(left, Some(node));
+    tree.set_parent(node, Some(left));
+}
+
+// Rotates a node down toward its left side, or its right side
+fn rotate_toward<L: RedBlackLinks>(tree: &mut L, node: L::Handle, left: bool) {
+    if left {
+        rotate_left(tree, node);
+    } else {
+        rotate_right(tree, node);
+    }
+}
+
+// The child on one side of a node, or the other
+fn child_toward<L: RedBlackLinks>(tree: &L, node: L::Handle, left: bool) -> Option<L::Handle> {
+    if left {
+        tree.left(node)
+    } else {
+        tree.right(node)
+    }
+}
+
+// Restores the RB-properties after a red node has been linked into the tree, either by
+//  pushing the grandparent's black down to its children or by rotating. Returns true when
+//  a red root had to be blackened, which means the black height of the whole tree grew.
+pub(crate) fn fix_red_red<L: RedBlackLinks>(tree: &mut L, node: L::Handle) -> bool {
+    let mut node = node;
+
+    while let Some(mut parent) = tree.parent(node).filter(|parent| tree.is_red(*parent)) {
+        let grandparent = tree
+            .parent(parent)
+            .expect("A red parent is never the root, so there's always a grandparent");
+        let parent_is_left = tree.left(grandparent) == Some(parent);
+        let uncle_opt = child_toward(tree, grandparent, !parent_is_left);
+
+        if is_red(tree, uncle_opt) {
+            tree.set_red(parent, false);
+            paint_black(tree, uncle_opt);
+            tree.set_red(grandparent, true);
+            node = grandparent;
+            continue;
+        }
+
+        // Straighten out a zig-zag first, so that a single rotation fixes the rest
+        let node_is_left = tree.left(parent) == Some(node);
+        if node_is_left != parent_is_left {
+            node = parent;
+            rotate_toward(tree, node, parent_is_left);
+            parent = tree
+                .parent(node)
+                .expect("The rotation put a parent above the node");
+        }
+
+        tree.set_red(parent, false);
+        tree.set_red(grandparent, true);
+        rotate_toward(tree, grandparent, !parent_is_left);
+    }
+
+    let root_was_red = is_red(tree, tree.root());
+    paint_black(tree, tree.root());
+    root_was_red
+}
+
+// Splices a node out of the tree without rebalancing anything. A node with two children
+//  is replaced by its inorder successor, which is relinked in its place and takes on its
+//  color, rather than having its data swapped in, so that handles to every other node stay
+//  valid. Returns whether the position that was actually vacated held a red node, along
+//  with the child that moved up into it and that child's new parent. The node is left for
+//  the caller to free.
+pub(crate) fn splice_node<L: RedBlackLinks>(
+    tree: &mut L,
+    node: L::Handle,
+) -> (bool, Option<L::Handle>, Option<L::Handle>) {
+    let (left_opt, right_opt) = (tree.left(node), tree.right(node));
+
+    match (left_opt, right_opt) {
+        (Some(left), Some(right)) => {
+            let successor = get_subtree_min(tree, right);
+            let removed_red = tree.is_red(successor);
+            let child_opt = tree.right(successor);
+            let child_parent_opt;
+
+            if tree.parent(successor) == Some(node) {
+                child_parent_opt = Some(successor);
+            } else {
+                child_parent_opt = tree.parent(successor);
+                replace_child(tree, successor, child_opt);
+                tree.set_right(successor, Some(right));
+                tree.set_parent(right, Some(successor));
+            }
+
+            replace_child(tree, node, Some(successor));
+            tree.set_left(successor, Some(left));
+            tree.set_parent(left, Some(successor));
+            tree.set_red(successor, tree.is_red(node));
+
+            (removed_red, child_opt, child_parent_opt)
+        }
+        _ => {
+            let child_opt = left_opt.or(right_opt);
+            let child_parent_opt = tree.parent(node);
+            replace_child(tree, node, child_opt);
+
+            (tree.is_red(node), child_opt, child_parent_opt)
+        }
+    }
+}
+
+// Splices a node out of the tree and restores the RB-properties. The node is left for the
+//  caller to free.
+pub(crate) fn unlink_node<L: RedBlackLinks>(tree: &mut L, node: L::Handle) {
+    let (removed_red, child_opt, child_parent_opt) = splice_node(tree, node);
+
+    // Removing a black node shortens every path through it, so the child that took its
+    //  place carries an extra 'double black' that has to be pushed out of the tree
+    if !removed_red {
+        fix_double_black(tree, child_opt, child_parent_opt);
+    }
+}
+
+// Resolves a double black node by borrowing a black node from its sibling's side of the
+//  tree, or by pushing the double black up to the parent when the sibling has nothing to
+//  give. The node itself may be a terminating node, so its parent is passed along.
+pub(crate) fn fix_double_black<L: RedBlackLinks>(
+    tree: &mut L,
+    node_opt: Option<L::Handle>,
+    parent_opt: Option<L::Handle>,
+) {
+    let mut node_opt = node_opt;
+    let mut parent_opt = parent_opt;
+    let sibling_missing = "Proper tree coloring ensures that a double black node has a sibling";
+
+    while node_opt != tree.root() && !is_red(tree, node_opt) {
+        let parent = parent_opt.expect("Only the root has no parent");
+        let node_is_left = tree.left(parent) == node_opt;
+        let mut sibling = child_toward(tree, parent, !node_is_left).expect(sibling_missing);
+
+        // Red sibling: rotate it above the parent so we get a black sibling instead
+        if tree.is_red(sibling) {
+            tree.set_red(sibling, false);
+            tree.set_red(parent, true);
+            rotate_toward(tree, parent, node_is_left);
+            sibling = child_toward(tree, parent, !node_is_left).expect(sibling_missing);
+        }
+
+        let near_nephew_opt = child_toward(tree, sibling, node_is_left);
+        let mut far_nephew_opt = child_toward(tree, sibling, !node_is_left);
+
+        if !is_red(tree, near_nephew_opt) && !is_red(tree, far_nephew_opt) {
+            // Nothing to borrow, so push the double black up a level
+            tree.set_red(sibling, true);
+            node_opt = Some(parent);
+            parent_opt = tree.parent(parent);
+            continue;
+        }
+
+        // Make sure the red nephew is on the far side before the final rotation
+        if !is_red(tree, far_nephew_opt) {
+            paint_black(tree, near_nephew_opt);
+            tree.set_red(sibling, true);
+            rotate_toward(tree, sibling, !node_is_left);
+            far_nephew_opt = Some(sibling);
+            sibling = child_toward(tree, parent, !node_is_left).expect(sibling_missing);
+        }
+
+        tree.set_red(sibling, tree.is_red(parent));
+        tree.set_red(parent, false);
+        paint_black(tree, far_nephew_opt);
+        rotate_toward(tree, parent, node_is_left);
+        node_opt = tree.root();
+    }
+
+    paint_black(tree, node_opt);
+}
+
+// Recursive function that returns a subtree's black height, or None if any path through it
+//  breaks the red-black properties or a child doesn't link back to its parent
+pub(crate) fn get_valid_black_height<L: RedBlackLinks>(
+    tree: &L,
+    node_opt: Option<L::Handle>,
+) -> Option<usize> {
+    let node = match node_opt {
+        Some(node) => node,
+        None => return Some(0),
+    };
+
+    for child in tree.left(node).into_iter().chain(tree.right(node)) {
+        if tree.parent(child) != node_opt || (tree.is_red(node) && tree.is_red(child)) {
+            return None;
+        }
+    }
+
+    let left_height = get_valid_black_height(tree, tree.left(node))?;
+    let right_height = get_valid_black_height(tree, tree.right(node))?;
+
+    if left_height != right_height {
+        return None;
+    }
+
+    Some(left_height + !tree.is_red(node) as usize)
+}
+
+// Counts the black nodes on the path from a node down to its leftmost terminating node
+#[cfg(feature = "alloc")]
+pub(crate) fn get_black_height<L: RedBlackLinks>(tree: &L, node_opt: Option<L::Handle>) -> usize {
+    let mut black_height = 0;
+    let mut cur_node_opt = node_opt;
+
+    while let Some(cur_node) = cur_node_opt {
+        if !tree.is_red(cur_node) {
+            black_height += 1;
+        }
+        cur_node_opt = tree.left(cur_node);
+    }
+
+    black_height
+}
+
+#[cfg(feature = "alloc")]
+pub(crate) fn link_children<L: RedBlackLinks>(
+    tree: &mut L,
+    node: L::Handle,
+    left_opt: Option<L::Handle>,
+    right_opt: Option<L::Handle>,
+) {
+    tree.set_left(node, left_opt);
+    tree.set_right(node, right_opt);
+
+    for child in left_opt.into_iter().chain(right_opt) {
+        tree.set_parent(child, Some(node));
+    }
+}
+
+// Recursive function that splits a detached, black-rooted subtree into a subtree of the
+//  nodes `is_lesser` picks out and a subtree of the rest, which have to come after them in
+//  order. The root is set aside, one of its children is split further and the root is then
+//  used to join the pieces back up with its other child. Each piece is returned along with
+//  its black height.
+#[cfg(feature = "alloc")]
+#[allow(clippy::type_complexity)]
+pub(crate) fn split_subtree<L: RedBlackLinks>(
+    tree: &mut L,
+    root_opt: Option<L::Handle>,
+    black_height: usize,
+    is_lesser: &mut impl FnMut(&L, L::Handle) -> bool,
+) -> ((Option<L::Handle>, usize), (Option<L::Handle>, usize)) {
+    let root = match root_opt {
+        Some(root) => root,
+        None => return ((None, 0), (None, 0)),
+    };
+
+    let (left_opt, right_opt) = (tree.left(root), tree.right(root));
+    tree.set_left(root, None);
+    tree.set_right(root, None);
+
+    // Children of a black node are one black node shorter, unless we blacken them
+    let left = detach_as_black_root(tree, left_opt, black_height - 1);
+    let right = detach_as_black_root(tree, right_opt, black_height - 1);
+
+    if is_lesser(tree, root) {
+        let (lesser, greater) = split_subtree(tree, right.0, right.1, is_lesser);
+        (join_subtrees(tree, left, root, lesser), greater)
+    } else {
+        let (lesser, greater) = split_subtree(tree, left.0, left.1, is_lesser);
+        (lesser, join_subtrees(tree, greater, root, right))
+    }
+}
+
+// Cuts a subtree loose from its parent and makes sure its root is black, adjusting
+//  its black height to match
+#[cfg(feature = "alloc")]
+fn detach_as_black_root<L: RedBlackLinks>(
+    tree: &mut L,
+    node_opt: Option<L::Handle>,
+    black_height: usize,
+) -> (Option<L::Handle>, usize) {
+    match node_opt {
+        Some(node) => {
+            tree.set_parent(node, None);
+
+            if tree.is_red(node) {
+                tree.set_red(node, false);
+                (node_opt, black_height + 1)
+            } else {
+                (node_opt, black_height)
+            }
+        }
+        None => (None, black_height),
+    }
+}
+
+// Joins two detached, black-rooted subtrees around a detached middle node whose value
+//  sits between theirs. The middle node is linked in as a red node along the spine of
+//  the taller tree, at the first black node matching the shorter tree's black height,
+//  and the regular insertion recoloring then takes care of any red-red violation.
+//  Returns the new root along with its black height. The tree's root is left pointing at
+//  the joined subtree.
+#[cfg(feature = "alloc")]
+pub(crate) fn join_subtrees<L: RedBlackLinks>(
+    tree: &mut L,
+    left: (Option<L::Handle>, usize),
+    mid: L::Handle,
+    right: (Option<L::Handle>, usize),
+) -> (Option<L::Handle>, usize) {
+    let (left_opt, left_height) = left;
+    let (right_opt, right_height) = right;
+
+    if left_height == right_height {
+        link_children(tree, mid, left_opt, right_opt);
+        tree.set_parent(mid, None);
+        tree.set_red(mid, false);
+        tree.set_root(Some(mid));
+
+        return (Some(mid), left_height + 1);
+    }
+
+    let left_is_taller = left_height > right_height;
+    let (mut cur_opt, mut cur_height, target_height) = if left_is_taller {
+        (left_opt, left_height, right_height)
+    } else {
+        (right_opt, right_height, left_height)
+    };
+    let mut parent_opt = None;
+
+    while is_red(tree, cur_opt) || cur_height != target_height {
+        let cur = cur_opt.expect("Black heights count down to a terminating node at zero");
+        if !tree.is_red(cur) {
+            cur_height -= 1;
+        }
+
+        parent_opt = cur_opt;
+        cur_opt = child_toward(tree, cur, !left_is_taller);
+    }
+
+    let parent = parent_opt.expect("The taller tree's root is always above the join point");
+
+    if left_is_taller {
+        link_children(tree, mid, cur_opt, right_opt);
+        tree.set_right(parent, Some(mid));
+        tree.set_root(left_opt);
+    } else {
+        link_children(tree, mid, left_opt, cur_opt);
+        tree.set_left(parent, Some(mid));
+        tree.set_root(right_opt);
+    }
+
+    tree.set_parent(mid, Some(parent));
+    tree.set_red(mid, true);
+
+    let root_blackened = fix_red_red(tree, mid);
+    let joined_height = left_height.max(right_height) + root_blackened as usize;
+
+    (tree.root(), joined_height)
+}
+
+// Recursive function that merges a black node with its red children, returning the
+//  handles of the merged nodes in sorted order
+#[cfg(feature = "alloc")]
+pub(crate) fn group_nodes<L: RedBlackLinks>(
+    tree: &L,
+    node: L::Handle,
+) -> TwoThreeFourNode<L::Handle> {
+    let mut keys = vec![];
+    let mut children = vec![];
+
+    for (child_opt, is_left) in [(tree.left(node), true), (tree.right(node), false)] {
+        if !is_left {
+            keys.push(node);
+        }
+
+        match child_opt {
+            Some(child) if tree.is_red(child) => {
+                keys.push(child);
+                children.extend([tree.left(child), tree.right(child)]);
+            }
+            _ => children.push(child_opt),
+        }
+    }
+
+    // Equal black heights mean either every child is there or none of them are
+    let children = children
+        .into_iter()
+        .flatten()
+        .map(|child| group_nodes(tree, child))
+        .collect();
+
+    TwoThreeFourNode { keys, children }
+}
+
+// Recursive function that links a valid 2-3-4 subtree into the tree, returning its root.
+//  `new_node` hands back a new, detached node holding the given value.
+#[cfg(feature = "alloc")]
+pub(crate) fn link_two_three_four<L: RedBlackLinks, T>(
+    tree: &mut L,
+    node: TwoThreeFourNode<T>,
+    new_node: &mut impl FnMut(&mut L, T) -> L::Handle,
+) -> L::Handle {
+    let key_count = node.keys.len();
+    let mut children: Vec<Option<L::Handle>> = node
+        .children
+        .into_iter()
+        .map(|child| Some(link_two_three_four(tree, child, new_node)))
+        .collect();
+
+    if children.is_empty() {
+        children.resize(key_count + 1, None);
+    }
+
+    let mut keys = node.keys.into_iter();
+    let mut children = children.into_iter();
+    let mut next_key = || keys.next().expect("Validated 2-3-4 nodes hold 1-3 keys");
+    let mut next_child = || {
+        children
+            .next()
+            .expect("Validated 2-3-4 nodes hold enough children")
+    };
+    let mut link_new_node = |tree: &mut L, data, left_opt, right_opt, is_red| {
+        let node = new_node(tree, data);
+        link_children(tree, node, left_opt, right_opt);
+        tree.set_parent(node, None);
+        tree.set_red(node, is_red);
+        node
+    };
+
+    // With two keys, the smaller one leans left as a red child
+    let left_opt = if key_count >= 2 {
+        let (data, left_opt, right_opt) = (next_key(), next_child(), next_child());
+        Some(link_new_node(tree, data, left_opt, right_opt, true))
+    } else {
+        next_child()
+    };
+
+    let data = next_key();
+    let right_opt = if key_count == 3 {
+        let (data, left_opt, right_opt) = (next_key(), next_child(), next_child());
+        Some(link_new_node(tree, data, left_opt, right_opt, true))
+    } else {
+        next_child()
+    };
+
+    link_new_node(tree, data, left_opt, right_opt, false)
+}
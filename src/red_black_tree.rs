@@ -1,30 +1,37 @@
 use generational_arena::Index;
 use std::collections::{HashMap, VecDeque};
 
-use super::base_tree::{BfsIter, ChildSide, DfsIter, InternalBinarySearchTree, Node};
-use super::tree_errs::NodeNotFoundErr;
+use super::base_tree::{
+    merge_sorted, BfsIter, DfsIter, InorderIter, InternalBinarySearchTree, Node,
+};
+use super::node_store::{ArenaStore, ArrayStore, NodeStore};
+use super::rb_balance::{
+    fix_double_black, fix_red_red, get_black_height, get_valid_black_height, group_nodes,
+    join_subtrees, link_two_three_four, split_subtree, RedBlackLinks,
+};
+use super::search_tree::SearchTree;
+use super::set_ops::{Difference, Intersection, SymmetricDifference, Union};
+use super::tree_errs::{CapacityExceededErr, MalformedTreeErr, NodeNotFoundErr};
+use super::two_three_four::TwoThreeFourTree;
 
 use std::cmp::PartialOrd;
 use std::fmt::Display;
+use std::mem::{swap, take};
+use std::ops::RangeBounds;
 
-#[derive(Default)]
-pub struct RedBlackTree<T: PartialOrd + Display + Default> {
-    bst: InternalBinarySearchTree<T>,
+#[derive(Default, Clone)]
+pub struct RedBlackTree<T: PartialOrd + Display + Default, S = ArenaStore<T>> {
+    pub(crate) bst: InternalBinarySearchTree<T, S>,
     colors: HashMap<Index, TreeColors>,
 }
 
-#[derive(Debug, Copy, Clone, PartialEq)]
+#[derive(Debug, Copy, Clone, PartialEq, Default)]
 pub(crate) enum TreeColors {
+    #[default]
     Red,
     Black,
 }
 
-impl Default for TreeColors {
-    fn default() -> TreeColors {
-        TreeColors::Red
-    }
-}
-
 impl<T: PartialOrd + Display + Default> RedBlackTree<T> {
     pub fn new() -> Self {
         RedBlackTree {
@@ -33,6 +40,30 @@ impl<T: PartialOrd + Display + Default> RedBlackTree<T> {
         }
     }
 
+    // Builds the red-black tree that encodes a 2-3-4 tree. Every node becomes a black node,
+    //  with any extra keys hung off of it as red children.
+    pub fn from_two_three_four(tree: TwoThreeFourTree<T>) -> Result<Self, MalformedTreeErr> {
+        tree.validate()?;
+
+        let mut rb_tree = RedBlackTree::new();
+        rb_tree.bst.root = tree.root.map(|root| {
+            link_two_three_four(&mut rb_tree, root, &mut |rb_tree, data| {
+                rb_tree.bst.nodes.insert(Node {
+                    data,
+                    left: None,
+                    right: None,
+                    parent: None,
+                })
+            })
+        });
+
+        Ok(rb_tree)
+    }
+}
+
+// Everything else works with any node store. Trees backed by another store start out
+//  through `Default`, e.g. `RedBlackTree::<T, SlabStore<T>>::default()`.
+impl<T: PartialOrd + Display + Default, S: NodeStore<T>> RedBlackTree<T, S> {
     pub fn get_size(&self) -> usize {
         self.bst.nodes.len()
     }
@@ -45,235 +76,383 @@ impl<T: PartialOrd + Display + Default> RedBlackTree<T> {
             parent: None,
         };
 
-        let new_leaf_idx = Some(self.bst.insert_node(leaf));
-        self.set_node_color(new_leaf_idx, TreeColors::Red);
-        self.recolor_nodes(new_leaf_idx.unwrap());
+        let new_leaf_idx = self.bst.insert_node(leaf);
+        self.set_node_color(Some(new_leaf_idx), TreeColors::Red);
+        fix_red_red(self, new_leaf_idx);
     }
 
-    fn recolor_nodes(&mut self, node_idx: Index) {
-        // If node is root, color it black then return. Tree has been recolored successfully
-        if self.bst.root == Some(node_idx) {
-            self.set_node_color(Some(node_idx), TreeColors::Black);
-            return;
+    fn get_node_color(&self, node_opt: Option<Index>) -> TreeColors {
+        if let Some(node_idx) = node_opt {
+            self.colors[&node_idx]
+        } else {
+            // Terminating nodes are black in color by default
+            TreeColors::Black
         }
+    }
 
-        let mut parent_idx_opt = self.bst.nodes[node_idx].parent;
-
-        // Parent or current node color is black, which means that the
-        //  RB-property has been upheld
-        if self.get_node_color(parent_idx_opt) == TreeColors::Black
-            || self.get_node_color(Some(node_idx)) == TreeColors::Black
-        {
-            return;
+    fn set_node_color(&mut self, node_idx_opt: Option<Index>, color: TreeColors) {
+        if let Some(node_idx) = node_idx_opt {
+            self.colors.insert(node_idx, color);
+        } else if color == TreeColors::Red {
+            panic!(
+                "Proper tree structure ensures that recoloring a terminating node red cannot occur"
+            );
         }
+        // Terminating nodes are already black, so do nothing
+    }
 
-        let parent_idx = parent_idx_opt.unwrap();
-        let grandparent_idx_opt = self.bst.nodes[parent_idx].parent;
-        let grandparent_idx = grandparent_idx_opt
-            .expect("Proper tree coloring ensures that a grandparent exists when checking uncle node colors");
+    pub fn contains(&self, item: &T) -> bool {
+        self.bst.find_node_index(item).is_some()
+    }
 
-        let grandparent_node = &self.bst.nodes[grandparent_idx];
+    pub fn remove(&mut self, item: &T) -> Result<(), NodeNotFoundErr> {
+        let node_idx_to_remove = self.bst.find_node_index(item).ok_or(NodeNotFoundErr)?;
+        self.remove_node(node_idx_to_remove);
 
-        let mut parent_child_side = ChildSide::Right;
-        let uncle_idx_opt = if grandparent_node.left == parent_idx_opt {
-            parent_child_side = ChildSide::Left;
-            grandparent_node.right
-        } else {
-            grandparent_node.left
-        };
+        Ok(())
+    }
 
-        match self.get_node_color(uncle_idx_opt) {
-            TreeColors::Red => {
-                // Simplest case: 'push' black color from grandparent down to its children
-                self.set_node_color(grandparent_idx_opt, TreeColors::Red);
-                self.set_node_color(uncle_idx_opt, TreeColors::Black);
-                self.set_node_color(parent_idx_opt, TreeColors::Black);
-            }
-            TreeColors::Black => {
-                let node_child_side =
-                    if self.bst.nodes[parent_idx_opt.unwrap()].left == Some(node_idx) {
-                        ChildSide::Left
-                    } else {
-                        ChildSide::Right
-                    };
-                // 4 possible cases here:
-                match (parent_child_side, node_child_side) {
-                    // 1: parent is left child, node is left child
-                    (ChildSide::Left, ChildSide::Left) => {
-                        self.rotate_node_right(parent_idx);
-                    }
-                    // 2: parent is left child, node is right child
-                    (ChildSide::Left, ChildSide::Right) => {
-                        self.rotate_node_left(node_idx);
-                        self.rotate_node_right(node_idx);
-                        parent_idx_opt = Some(node_idx);
-                    }
-                    // 3: mirror of 2
-                    (ChildSide::Right, ChildSide::Left) => {
-                        self.rotate_node_right(node_idx);
-                        self.rotate_node_left(node_idx);
-                        parent_idx_opt = Some(node_idx);
-                    }
-                    // 5: mirror of 1
-                    (ChildSide::Right, ChildSide::Right) => {
-                        self.rotate_node_left(parent_idx);
-                    }
-                }
-
-                // Swap grandparent & parent colors, and we're done!
-                let grandparent_color = self.get_node_color(grandparent_idx_opt);
-                let parent_color = self.get_node_color(parent_idx_opt);
-
-                self.set_node_color(parent_idx_opt, grandparent_color);
-                self.set_node_color(grandparent_idx_opt, parent_color);
-            }
-        };
+    // Unlinks a node from the tree, drops it from the arena and hands back its value
+    pub(crate) fn remove_node(&mut self, node_idx: Index) -> T {
+        let unlinked_idx = self.unlink_node(node_idx);
+        self.colors.remove(&unlinked_idx);
+
+        self.bst
+            .nodes
+            .remove(unlinked_idx)
+            .expect("Exclusive access during mutation ensures that a node exists for every index")
+            .data
+    }
 
-        if let Some(grandparent_idx) = grandparent_idx_opt {
-            self.recolor_nodes(grandparent_idx);
+    // Splices a node out of the tree and restores the RB-properties, but leaves it allocated
+    //  in the arena so that it can be reused. Returns the index of the node that was
+    //  actually unlinked, which now holds the removed value.
+    fn unlink_node(&mut self, node_idx: Index) -> Index {
+        let (target_idx, parent_idx_opt, child_idx_opt) = self.bst.unlink_node(node_idx);
+
+        // Removing a black node shortens every path through it, so the replacement
+        //  carries an extra 'double black' that has to be pushed out of the tree
+        if self.get_node_color(Some(target_idx)) == TreeColors::Black {
+            fix_double_black(self, child_idx_opt, parent_idx_opt);
         }
+
+        target_idx
     }
 
-    // When rotating a node right, we set the parent's left child equal to the
-    //  target node's right child. Then we set the parent as the right child
-    //  of the target. Finally we fix all of the parent references, et voila.
-    fn rotate_node_right(&mut self, node_idx: Index) {
-        let node = &mut self.bst.nodes[node_idx];
-        let parent_idx = node.parent.expect(
-            "Proper tree structure ensures that a rotation occurs only on nodes with parents",
-        );
+    // Removes every value that fails the `keep` check
+    pub fn retain(&mut self, keep: impl FnMut(&T) -> bool) {
+        let node_idxs = self.bst.get_rejected_indices(keep);
+        self.remove_nodes(node_idxs, drop);
+    }
 
-        // Make parent node a child of the rotating node
-        let right_node_idx_opt = node.right;
-        node.right = Some(parent_idx);
+    // Removes every value within `range`, handing them back in sorted order
+    pub fn drain_range(&mut self, range: impl RangeBounds<T>) -> Vec<T> {
+        let node_idxs = self.bst.get_range_indices(&range);
+        let mut removed = Vec::with_capacity(node_idxs.len());
 
-        // Make parent node a child of the rotating node
-        let parent_node = &mut self.bst.nodes[parent_idx];
-        let grandparent_idx_opt = parent_node.parent;
+        self.remove_nodes(node_idxs, |data| removed.push(data));
+        removed.reverse();
+        removed
+    }
 
-        // Update parent's 'parent' and 'left' children accordingly
-        parent_node.parent = Some(node_idx);
-        parent_node.left = right_node_idx_opt;
+    // Removes every value within `range`, returning how many were removed. The values are
+    //  dropped as they come out of the tree rather than collected first.
+    pub fn remove_range(&mut self, range: impl RangeBounds<T>) -> usize {
+        let node_idxs = self.bst.get_range_indices(&range);
+        let removed_count = node_idxs.len();
 
-        // Update right child node with new parent idx
-        if let Some(right_node_idx) = right_node_idx_opt {
-            let right_node = &mut self.bst.nodes[right_node_idx];
-            right_node.parent = Some(parent_idx);
+        self.remove_nodes(node_idxs, drop);
+        removed_count
+    }
+
+    // Removes a sorted run of nodes, handing each removed value to `on_removed`, largest
+    //  first. Each removal costs O(log n) rebalancing work, so once that adds up to more
+    //  than visiting every node once, we pull the surviving values out in order and
+    //  rebuild a balanced tree from them instead.
+    fn remove_nodes(&mut self, node_idxs: Vec<Index>, mut on_removed: impl FnMut(T)) {
+        let size = self.get_size();
+        let removal_cost = node_idxs.len() * (size.max(1).ilog2() as usize + 1);
+
+        if removal_cost < size {
+            // Largest first, since unlinking a node with two children frees its inorder
+            //  successor's slot, which going backwards guarantees was already dealt with
+            for node_idx in node_idxs.into_iter().rev() {
+                on_removed(self.remove_node(node_idx));
+            }
+            return;
         }
 
-        // Set node's parent to the grandparent
-        self.bst.nodes[node_idx].parent = grandparent_idx_opt;
+        let mut kept = Vec::with_capacity(size - node_idxs.len());
+        let mut node_idxs_to_remove = node_idxs.into_iter().rev().peekable();
 
-        // If grandparent exists, we replace its child idx with the parent with the target node
-        if let Some(grandparent_idx) = grandparent_idx_opt {
-            let grandparent_node = &mut self.bst.nodes[grandparent_idx];
-            if grandparent_node.left == Some(parent_idx) {
-                grandparent_node.left = Some(node_idx);
+        for (node_idx, data) in self.bst.drain_sorted_with_indices().into_iter().rev() {
+            if node_idxs_to_remove.peek() == Some(&node_idx) {
+                node_idxs_to_remove.next();
+                on_removed(data);
             } else {
-                grandparent_node.right = Some(node_idx);
+                kept.push(data);
             }
-        } else {
-            self.bst.root = Some(node_idx);
         }
+
+        kept.reverse();
+        self.colors.clear();
+        self.rebuild_from_sorted(kept);
     }
 
-    // When rotating a node left, we set the parent's right child equal to the
-    //  target node's left child. Then we set the parent as the left child
-    //  of the target. Finally we fix all of the parent references, et voila.
-    fn rotate_node_left(&mut self, node_idx: Index) {
-        let node = &mut self.bst.nodes[node_idx];
-        let parent_idx = node.parent.expect(
-            "Proper tree structure ensures that a rotation occurs only on nodes with parents",
-        );
+    // Moves every value greater than or equal to `key` into a new tree. The tree is cut
+    //  apart by recursively splitting and re-joining subtrees along a single root-to-leaf
+    //  path, which restructures only O(log n) nodes. Every tree owns its own node store,
+    //  though, so one of the two halves then has to be moved into a new one. Whichever half
+    //  is smaller gets moved, which makes splitting off either end of the tree cheap.
+    pub fn split_off(&mut self, key: &T) -> Self {
+        let mut split_tree = RedBlackTree::default();
+        let root_idx_opt = self.bst.root;
+
+        if root_idx_opt.is_none() {
+            return split_tree;
+        }
+
+        let black_height = get_black_height(self, root_idx_opt);
+        let (lesser, greater) =
+            split_subtree(self, root_idx_opt, black_height, &mut |tree, node_idx| {
+                tree.bst.nodes[node_idx].data < *key
+            });
+        let greater_is_smaller = self.bst.is_smaller_subtree(greater.0, lesser.0);
+        let (kept, moved) = if greater_is_smaller {
+            (lesser, greater)
+        } else {
+            (greater, lesser)
+        };
+
+        self.bst.root = kept.0;
+        if let Some(moved_root_idx) = moved.0 {
+            split_tree.bst.root = Some(self.move_subtree_to(moved_root_idx, &mut split_tree));
+        }
 
-        let left_node_idx_opt = node.left;
-        node.left = Some(parent_idx);
+        // The lesser half was the one moved, so trade places to keep it in this tree
+        if !greater_is_smaller {
+            swap(self, &mut split_tree);
+        }
 
-        let parent_node = &mut self.bst.nodes[parent_idx];
-        let grandparent_idx_opt = parent_node.parent;
+        split_tree
+    }
 
-        // Update parent's 'parent' and 'left' children accordingly
-        parent_node.parent = Some(node_idx);
-        parent_node.right = left_node_idx_opt;
+    // Moves every value out of `other` and into this tree. When the two trees don't overlap,
+    //  the smaller tree is joined onto the larger one in O(log n), apart from moving its
+    //  nodes across arenas. Otherwise both trees are merged in sorted order and rebuilt.
+    pub fn append(&mut self, other: &mut Self) {
+        if other.bst.root.is_none() {
+            return;
+        }
 
-        // If rotating node had a left child, update its parent index
-        if let Some(left_node_idx) = left_node_idx_opt {
-            let left_node = &mut self.bst.nodes[left_node_idx];
-            left_node.parent = Some(parent_idx);
+        // Always move the smaller tree's nodes across arenas
+        if self.get_size() < other.get_size() {
+            swap(self, other);
         }
 
-        // Set node's parent to the grandparent
-        self.bst.nodes[node_idx].parent = grandparent_idx_opt;
+        let (root_idx, other_root_idx) = match (self.bst.root, other.bst.root) {
+            (Some(root_idx), Some(other_root_idx)) => (root_idx, other_root_idx),
+            _ => return,
+        };
 
-        // If grandparent exists, we replace its child idx with the parent with the target node
-        if let Some(grandparent_idx) = grandparent_idx_opt {
-            let grandparent_node = &mut self.bst.nodes[grandparent_idx];
-            if grandparent_node.left == Some(parent_idx) {
-                grandparent_node.left = Some(node_idx);
-            } else {
-                grandparent_node.right = Some(node_idx);
-            }
+        let min_idx = self.bst.get_subtree_min(root_idx);
+        let max_idx = self.bst.get_subtree_max(root_idx);
+        let other_min = &other.bst.nodes[other.bst.get_subtree_min(other_root_idx)].data;
+        let other_max = &other.bst.nodes[other.bst.get_subtree_max(other_root_idx)].data;
+
+        if self.bst.nodes[max_idx].data <= *other_min {
+            other.bst.root = None;
+            let moved_root_idx = other.move_subtree_to(other_root_idx, self);
+            let moved_height = get_black_height(self, Some(moved_root_idx));
+
+            // Borrow our largest node to sit between the two trees
+            let mid_idx = self.unlink_node(max_idx);
+            let own_root_idx_opt = self.bst.root;
+            let own_height = get_black_height(self, own_root_idx_opt);
+
+            join_subtrees(
+                self,
+                (own_root_idx_opt, own_height),
+                mid_idx,
+                (Some(moved_root_idx), moved_height),
+            );
+        } else if *other_max < self.bst.nodes[min_idx].data {
+            other.bst.root = None;
+            let moved_root_idx = other.move_subtree_to(other_root_idx, self);
+            let moved_height = get_black_height(self, Some(moved_root_idx));
+
+            // Borrow our smallest node to sit between the two trees
+            let mid_idx = self.unlink_node(min_idx);
+            let own_root_idx_opt = self.bst.root;
+            let own_height = get_black_height(self, own_root_idx_opt);
+
+            join_subtrees(
+                self,
+                (Some(moved_root_idx), moved_height),
+                mid_idx,
+                (own_root_idx_opt, own_height),
+            );
         } else {
-            self.bst.root = Some(node_idx);
+            let merged = merge_sorted(self.bst.drain_sorted(), other.bst.drain_sorted());
+            self.colors.clear();
+            other.colors.clear();
+
+            self.rebuild_from_sorted(merged);
         }
     }
 
-    fn get_node_color(&self, node_opt: Option<Index>) -> TreeColors {
-        if let Some(node_idx) = node_opt {
-            self.colors[&node_idx]
-        } else {
-            // Terminating nodes are black in color by default
-            TreeColors::Black
-        }
+    // Moves a detached subtree, colors included, into another tree's arena
+    fn move_subtree_to(&mut self, root_idx: Index, dst: &mut Self) -> Index {
+        let colors = &mut self.colors;
+        let dst_colors = &mut dst.colors;
+
+        self.bst
+            .move_subtree(root_idx, &mut dst.bst, |node_idx, new_node_idx| {
+                let color = colors
+                    .remove(&node_idx)
+                    .expect("Every node in the tree has a color");
+                dst_colors.insert(new_node_idx, color);
+            })
     }
 
-    fn set_node_color(&mut self, node_idx_opt: Option<Index>, color: TreeColors) {
-        if let Some(node_idx) = node_idx_opt {
-            self.colors.insert(node_idx, color);
-        } else if color == TreeColors::Red {
-            panic!(
-                "Proper tree structure ensures that recoloring a terminating node red cannot occur"
-            );
-        }
-        // Terminating nodes are already black, so do nothing
+    // Replaces the (empty) tree with a perfectly balanced one. Every level is black apart
+    //  from the last one when it is only partially filled, which is colored red so that all
+    //  paths keep the same black height.
+    fn rebuild_from_sorted(&mut self, items: Vec<T>) {
+        let len = items.len();
+        let red_depth = if (len + 1).is_power_of_two() {
+            None
+        } else {
+            Some(len.ilog2() as usize)
+        };
+
+        let colors = &mut self.colors;
+        self.bst.root =
+            self.bst
+                .build_balanced(&mut items.into_iter(), len, 0, &mut |node_idx, depth| {
+                    let color = if Some(depth) == red_depth {
+                        TreeColors::Red
+                    } else {
+                        TreeColors::Black
+                    };
+                    colors.insert(node_idx, color);
+                });
     }
 
-    pub fn contains(&self, item: &T) -> bool {
-        self.bst.find_node_index(item).is_some()
+    // Views the tree as the 2-3-4 tree it encodes, where every black node is merged with
+    //  its red children into a single node of up to three keys. Print the result for a
+    //  level by level picture of the tree.
+    pub fn to_two_three_four(&self) -> TwoThreeFourTree<&T> {
+        TwoThreeFourTree {
+            root: self.bst.root.map(|root_idx| group_nodes(self, root_idx)),
+        }
+        .map(|node_idx| &self.bst.nodes[node_idx].data)
     }
 
-    pub fn remove(&mut self, item: &T) -> Result<(), NodeNotFoundErr> {
-        unimplemented!();
+    // Same as `to_two_three_four`, but moves the values out of the tree
+    pub fn into_two_three_four(mut self) -> TwoThreeFourTree<T> {
+        let node_idxs = TwoThreeFourTree {
+            root: self.bst.root.map(|root_idx| group_nodes(&self, root_idx)),
+        };
+        node_idxs.map(|node_idx| take(&mut self.bst.nodes[node_idx].data))
     }
 
-    // Create a new iterator w/ a stack for DFS taversal
-    pub fn dfs_iter(&mut self) -> DfsIter<T> {
-        let mut node_idx_stack = Vec::new();
+    // Checks that values are in order, that the root is black with no parent, that every
+    //  child links back to its parent, that no red node has a red child and that every path
+    //  sees the same number of black nodes. The color map has to hold exactly one entry per
+    //  node, so a color left behind by a removal is caught too.
+    pub fn is_valid(&self) -> bool {
+        if self.colors.len() != self.bst.nodes.len() {
+            return false;
+        }
 
         if let Some(root_idx) = self.bst.root {
-            node_idx_stack.push(root_idx);
+            if self.bst.nodes[root_idx].parent.is_some()
+                || self.get_node_color(Some(root_idx)) != TreeColors::Black
+            {
+                return false;
+            }
+        }
+
+        let mut node_count = 0;
+        let mut prev_data = None;
+        let mut inorder_iter = InorderIter::new(self.bst.root, &self.bst.nodes);
+
+        while let Some(node_idx) = inorder_iter.next_index() {
+            let data = &self.bst.nodes[node_idx].data;
+            if prev_data.is_some_and(|prev| prev > data) {
+                return false;
+            }
+
+            prev_data = Some(data);
+            node_count += 1;
         }
 
-        DfsIter {
-            node_idx_stack,
-            nodes: &self.bst.nodes,
+        node_count == self.bst.nodes.len() && get_valid_black_height(self, self.bst.root).is_some()
+    }
+
+    // Create a new iterator that visits values in sorted order
+    pub fn iter(&self) -> InorderIter<'_, T, S> {
+        self.bst.iter()
+    }
+
+    // Lazily visits, in order, the values found in either tree
+    pub fn union<'a>(&'a self, other: &'a Self) -> Union<InorderIter<'a, T, S>> {
+        Union {
+            first: self.iter().peekable(),
+            second: other.iter().peekable(),
         }
     }
 
-    // Create a new iterator w/ a queue for BFS traversal
-    pub fn bfs_iter(&mut self) -> BfsIter<T> {
-        let mut node_idx_queue = VecDeque::new();
+    // Lazily visits, in order, the values found in both trees
+    pub fn intersection<'a>(&'a self, other: &'a Self) -> Intersection<InorderIter<'a, T, S>> {
+        Intersection {
+            first: self.iter().peekable(),
+            second: other.iter().peekable(),
+        }
+    }
 
-        if let Some(root_idx) = self.bst.root {
-            node_idx_queue.push_front(root_idx);
+    // Lazily visits, in order, the values found in this tree but not in `other`
+    pub fn difference<'a>(&'a self, other: &'a Self) -> Difference<InorderIter<'a, T, S>> {
+        Difference {
+            first: self.iter().peekable(),
+            second: other.iter().peekable(),
         }
+    }
 
-        BfsIter {
-            node_idx_queue,
-            nodes: &self.bst.nodes,
+    // Lazily visits, in order, the values found in exactly one of the two trees
+    pub fn symmetric_difference<'a>(
+        &'a self,
+        other: &'a Self,
+    ) -> SymmetricDifference<InorderIter<'a, T, S>> {
+        SymmetricDifference {
+            first: self.iter().peekable(),
+            second: other.iter().peekable(),
         }
     }
 
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.get_size() <= other.get_size() && self.difference(other).next().is_none()
+    }
+
+    pub fn is_superset(&self, other: &Self) -> bool {
+        other.is_subset(self)
+    }
+
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.intersection(other).next().is_none()
+    }
+
+    // Create a new iterator w/ a stack for DFS taversal
+    pub fn dfs_iter(&self) -> DfsIter<'_, T, S> {
+        self.bst.dfs_iter()
+    }
+
+    // Create a new iterator w/ a queue for BFS traversal
+    pub fn bfs_iter(&self) -> BfsIter<'_, T, S> {
+        self.bst.bfs_iter()
+    }
+
     // Private method for printing node diagnostic data
     fn node_to_str(&self, node_idx: Index) -> String {
         let node = &self.bst.nodes[node_idx];
@@ -289,7 +468,125 @@ impl<T: PartialOrd + Display + Default> RedBlackTree<T> {
     }
 }
 
-impl<T: PartialOrd + Display + Default> Display for RedBlackTree<T> {
+// A tree backed by an `ArrayStore` can only hold `N` values. `insert` panics past that,
+//  like the store itself, so trees whose size isn't bounded up front should go through
+//  `try_insert` instead. Only the nodes are kept inline: the colors still live in a
+//  `HashMap`, so the tree is fixed-capacity but still allocates as it grows.
+impl<T: PartialOrd + Display + Default, const N: usize> RedBlackTree<T, ArrayStore<T, N>> {
+    pub fn capacity(&self) -> usize {
+        self.bst.nodes.capacity()
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.bst.nodes.is_full()
+    }
+
+    // Inserts the item unless every slot is already taken
+    pub fn try_insert(&mut self, item: T) -> Result<(), CapacityExceededErr> {
+        if self.is_full() {
+            return Err(CapacityExceededErr);
+        }
+
+        self.insert(item);
+        Ok(())
+    }
+}
+
+// Colors stay in the color map, while the links are read and written through the node store
+impl<T: PartialOrd + Display + Default, S: NodeStore<T>> RedBlackLinks for RedBlackTree<T, S> {
+    type Handle = Index;
+
+    fn root(&self) -> Option<Index> {
+        self.bst.root
+    }
+
+    fn set_root(&mut self, root_idx_opt: Option<Index>) {
+        self.bst.root = root_idx_opt;
+    }
+
+    fn left(&self, node_idx: Index) -> Option<Index> {
+        self.bst.nodes[node_idx].left
+    }
+
+    fn right(&self, node_idx: Index) -> Option<Index> {
+        self.bst.nodes[node_idx].right
+    }
+
+    fn parent(&self, node_idx: Index) -> Option<Index> {
+        self.bst.nodes[node_idx].parent
+    }
+
+    fn set_left(&mut self, node_idx: Index, left_idx_opt: Option<Index>) {
+        self.bst.nodes[node_idx].left = left_idx_opt;
+    }
+
+    fn set_right(&mut self, node_idx: Index, right_idx_opt: Option<Index>) {
+        self.bst.nodes[node_idx].right = right_idx_opt;
+    }
+
+    fn set_parent(&mut self, node_idx: Index, parent_idx_opt: Option<Index>) {
+        self.bst.nodes[node_idx].parent = parent_idx_opt;
+    }
+
+    fn is_red(&self, node_idx: Index) -> bool {
+        self.get_node_color(Some(node_idx)) == TreeColors::Red
+    }
+
+    fn set_red(&mut self, node_idx: Index, is_red: bool) {
+        let color = if is_red {
+            TreeColors::Red
+        } else {
+            TreeColors::Black
+        };
+        self.set_node_color(Some(node_idx), color);
+    }
+}
+
+impl<T: PartialOrd + Display + Default, S: NodeStore<T>> SearchTree<T> for RedBlackTree<T, S> {
+    type Iter<'a>
+        = InorderIter<'a, T, S>
+    where
+        Self: 'a,
+        T: 'a;
+
+    fn insert(&mut self, item: T) {
+        RedBlackTree::insert(self, item)
+    }
+
+    fn remove(&mut self, item: &T) -> Result<(), NodeNotFoundErr> {
+        RedBlackTree::remove(self, item)
+    }
+
+    fn contains(&self, item: &T) -> bool {
+        RedBlackTree::contains(self, item)
+    }
+
+    fn len(&self) -> usize {
+        self.get_size()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.bst.iter()
+    }
+
+    fn min(&self) -> Option<&T> {
+        self.bst.get_min()
+    }
+
+    fn max(&self) -> Option<&T> {
+        self.bst.get_max()
+    }
+
+    fn successor(&self, item: &T) -> Option<&T> {
+        self.bst.get_successor(item)
+    }
+
+    fn predecessor(&self, item: &T) -> Option<&T> {
+        self.bst.get_predecessor(item)
+    }
+}
+
+impl<T: PartialOrd + Display + Default, S: NodeStore<T>> Display for RedBlackTree<T, S> {
     // Simple BFS traversing method that prints each node's information for diagnostic purposes
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         let mut nodes = VecDeque::new();
@@ -0,0 +1,35 @@
+use super::tree_errs::NodeNotFoundErr;
+
+// Common interface shared by every tree in the crate, so that callers can swap tree
+//  implementations behind a generic parameter. Values come back in sorted order from
+//  `iter`, and the navigation methods accept values that aren't in the tree themselves.
+pub trait SearchTree<T> {
+    type Iter<'a>: Iterator<Item = &'a T>
+    where
+        Self: 'a,
+        T: 'a;
+
+    fn insert(&mut self, item: T);
+
+    fn remove(&mut self, item: &T) -> Result<(), NodeNotFoundErr>;
+
+    fn contains(&self, item: &T) -> bool;
+
+    fn len(&self) -> usize;
+
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn iter(&self) -> Self::Iter<'_>;
+
+    fn min(&self) -> Option<&T>;
+
+    fn max(&self) -> Option<&T>;
+
+    // Smallest value strictly greater than `item`
+    fn successor(&self, item: &T) -> Option<&T>;
+
+    // Largest value strictly less than `item`
+    fn predecessor(&self, item: &T) -> Option<&T>;
+}
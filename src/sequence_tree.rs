@@ -0,0 +1,452 @@
+use super::red_black_tree::TreeColors;
+use super::tree_errs::PositionOutOfBoundsErr;
+
+use std::cmp::Ordering;
+use std::collections::VecDeque;
+
+type Link<T> = Option<Box<SequenceNode<T>>>;
+
+struct SequenceNode<T> {
+    color: TreeColors,
+    left: Link<T>,
+    data: T,
+    right: Link<T>,
+    // Number of nodes in this subtree, and the number of black nodes on every path from
+    //  this node down to a leaf, this node included
+    size: usize,
+    black_height: usize,
+}
+
+// A sequence stored as a red-black tree with implicit keys: rather than being compared
+//  against a stored value, every node's position is worked out from the subtree sizes
+//  along the path to it. An in order walk visits the values in sequence order, which
+//  means that inserting or removing anywhere in the sequence only touches O(log n) nodes,
+//  where a `Vec` has to shift everything after the change.
+//
+// Every node owns its children and caches its subtree size and black height, so that
+//  two trees can be joined around a middle value without looking past the path where
+//  they meet. Splitting is a series of joins along a single root-to-leaf path, and
+//  `insert_at`, `remove_at`, `split_at` and `concat` are all built from these two, each
+//  taking O(log n) time. The balancing follows "Just Join for Parallel Ordered Sets" by
+//  Blelloch, Ferizovic and Sun: https://arxiv.org/abs/1602.02120
+pub struct SequenceTree<T> {
+    root: Link<T>,
+}
+
+impl<T> Default for SequenceTree<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SequenceTree<T> {
+    pub fn new() -> Self {
+        SequenceTree { root: None }
+    }
+
+    pub fn get_size(&self) -> usize {
+        get_size(&self.root)
+    }
+
+    pub fn get(&self, pos: usize) -> Option<&T> {
+        let mut cur_node_opt = self.root.as_deref();
+        let mut pos = pos;
+
+        // Walks down from the root, skipping over left subtrees that end before `pos`
+        while let Some(cur_node) = cur_node_opt {
+            let left_size = get_size(&cur_node.left);
+
+            match pos.cmp(&left_size) {
+                Ordering::Less => cur_node_opt = cur_node.left.as_deref(),
+                Ordering::Equal => return Some(&cur_node.data),
+                Ordering::Greater => {
+                    pos -= left_size + 1;
+                    cur_node_opt = cur_node.right.as_deref();
+                }
+            }
+        }
+
+        None
+    }
+
+    // Values can be changed in place, since their position doesn't depend on them
+    pub fn get_mut(&mut self, pos: usize) -> Option<&mut T> {
+        let mut cur_node_opt = self.root.as_deref_mut();
+        let mut pos = pos;
+
+        while let Some(cur_node) = cur_node_opt {
+            let left_size = get_size(&cur_node.left);
+
+            match pos.cmp(&left_size) {
+                Ordering::Less => cur_node_opt = cur_node.left.as_deref_mut(),
+                Ordering::Equal => return Some(&mut cur_node.data),
+                Ordering::Greater => {
+                    pos -= left_size + 1;
+                    cur_node_opt = cur_node.right.as_deref_mut();
+                }
+            }
+        }
+
+        None
+    }
+
+    // Inserts a value so that it ends up at `pos`, shifting everything after it along.
+    //  Inserting at the end of the sequence is allowed, much like `Vec::insert`.
+    pub fn insert_at(&mut self, pos: usize, item: T) -> Result<(), PositionOutOfBoundsErr> {
+        if pos > self.get_size() {
+            return Err(PositionOutOfBoundsErr);
+        }
+
+        let (front, back) = split(self.root.take(), pos);
+        self.root = Some(blacken(join(front, item, back)));
+        Ok(())
+    }
+
+    // Appends a value to the end of the sequence
+    pub fn push(&mut self, item: T) {
+        let _ = self.insert_at(self.get_size(), item);
+    }
+
+    // Removes the value at `pos`, shifting everything after it back
+    pub fn remove_at(&mut self, pos: usize) -> Result<T, PositionOutOfBoundsErr> {
+        if pos >= self.get_size() {
+            return Err(PositionOutOfBoundsErr);
+        }
+
+        let (front, back) = split(self.root.take(), pos);
+        let (item, rest) = match back {
+            Some(back) => split_first(*back),
+            None => unreachable!("A position within the sequence always has a value"),
+        };
+        self.root = join_all(front, rest).map(blacken);
+        Ok(item)
+    }
+
+    // Moves every value from `pos` onwards into a new sequence, in O(log n) time
+    pub fn split_at(&mut self, pos: usize) -> Result<Self, PositionOutOfBoundsErr> {
+        if pos > self.get_size() {
+            return Err(PositionOutOfBoundsErr);
+        }
+
+        let (front, back) = split(self.root.take(), pos);
+        self.root = front.map(blacken);
+
+        Ok(SequenceTree {
+            root: back.map(blacken),
+        })
+    }
+
+    // Moves every value out of `other` and onto the end of this sequence, in O(log n) time
+    pub fn concat(&mut self, other: &mut Self) {
+        self.root = join_all(self.root.take(), other.root.take()).map(blacken);
+    }
+
+    // Checks that the root is black, that no red node has a red child, that every path
+    //  down from a node passes the same number of black nodes, and that every node's
+    //  cached size and black height match its children
+    pub fn is_valid(&self) -> bool {
+        !is_red(&self.root) && get_valid_black_height(&self.root).is_some()
+    }
+
+    // Create a new iterator w/ a stack for DFS taversal
+    pub fn dfs_iter(&self) -> DfsIter<'_, T> {
+        DfsIter {
+            node_stack: self.root.as_deref().into_iter().collect(),
+        }
+    }
+
+    // Create a new iterator w/ a queue for BFS traversal
+    pub fn bfs_iter(&self) -> BfsIter<'_, T> {
+        BfsIter {
+            node_queue: self.root.as_deref().into_iter().collect(),
+        }
+    }
+
+    // Create a new iterator that visits values in sequence order
+    pub fn iter(&self) -> Iter<'_, T> {
+        let mut iter = Iter {
+            node_stack: Vec::new(),
+        };
+        iter.push_left_spine(self.root.as_deref());
+        iter
+    }
+}
+
+pub struct Iter<'a, T> {
+    node_stack: Vec<&'a SequenceNode<T>>,
+}
+
+impl<'a, T> Iter<'a, T> {
+    fn push_left_spine(&mut self, node_opt: Option<&'a SequenceNode<T>>) {
+        let mut cur_node_opt = node_opt;
+        while let Some(cur_node) = cur_node_opt {
+            self.node_stack.push(cur_node);
+            cur_node_opt = cur_node.left.as_deref();
+        }
+    }
+}
+
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.node_stack.pop()?;
+        self.push_left_spine(node.right.as_deref());
+
+        Some(&node.data)
+    }
+}
+
+pub struct DfsIter<'a, T> {
+    node_stack: Vec<&'a SequenceNode<T>>,
+}
+
+// Iterate through nodes using depth-first traversal
+impl<'a, T> Iterator for DfsIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.node_stack.pop()?;
+        self.node_stack.extend(node.right.as_deref());
+        self.node_stack.extend(node.left.as_deref());
+
+        Some(&node.data)
+    }
+}
+
+pub struct BfsIter<'a, T> {
+    node_queue: VecDeque<&'a SequenceNode<T>>,
+}
+
+// Iterate through nodes using breadth-first traversal
+impl<'a, T> Iterator for BfsIter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node = self.node_queue.pop_front()?;
+        self.node_queue.extend(node.left.as_deref());
+        self.node_queue.extend(node.right.as_deref());
+
+        Some(&node.data)
+    }
+}
+
+fn new_node<T>(color: TreeColors, left: Link<T>, data: T, right: Link<T>) -> Box<SequenceNode<T>> {
+    let size = 1 + get_size(&left) + get_size(&right);
+    let black_height = match color {
+        TreeColors::Black => get_black_height(&left) + 1,
+        TreeColors::Red => get_black_height(&left),
+    };
+
+    Box::new(SequenceNode {
+        color,
+        left,
+        data,
+        right,
+        size,
+        black_height,
+    })
+}
+
+fn get_size<T>(link: &Link<T>) -> usize {
+    link.as_ref().map_or(0, |node| node.size)
+}
+
+fn get_black_height<T>(link: &Link<T>) -> usize {
+    link.as_ref().map_or(0, |node| node.black_height)
+}
+
+fn is_red<T>(link: &Link<T>) -> bool {
+    link.as_ref()
+        .is_some_and(|node| node.color == TreeColors::Red)
+}
+
+// Turning the root of a tree black never breaks the red-black properties, it only adds
+//  one to the black height
+fn blacken<T>(mut node: Box<SequenceNode<T>>) -> Box<SequenceNode<T>> {
+    if node.color == TreeColors::Red {
+        node.color = TreeColors::Black;
+        node.black_height += 1;
+    }
+    node
+}
+
+// Builds a tree holding every value of `left`, then `data`, then every value of `right`.
+//  Only the taller tree is walked, down its spine facing the other tree, until a black
+//  node of the shorter tree's black height turns up to take its place.
+fn join<T>(left: Link<T>, data: T, right: Link<T>) -> Box<SequenceNode<T>> {
+    let left = left.map(blacken);
+    let right = right.map(blacken);
+
+    let joined = match get_black_height(&left).cmp(&get_black_height(&right)) {
+        Ordering::Greater => join_right(left, data, right),
+        Ordering::Less => join_left(left, data, right),
+        Ordering::Equal => new_node(TreeColors::Red, left, data, right),
+    };
+
+    // A red root can be left over with a red child, which is fixed by turning it black
+    if joined.color == TreeColors::Red && (is_red(&joined.left) || is_red(&joined.right)) {
+        blacken(joined)
+    } else {
+        joined
+    }
+}
+
+// Recursive function that joins a black-rooted `right` tree onto the right spine of the
+//  taller `left` tree. The joining node goes in red, and a red node with a red right
+//  child under a black parent is rotated up on the way back, pushing the violation up
+//  the spine until the root is reached.
+fn join_right<T>(left: Link<T>, data: T, right: Link<T>) -> Box<SequenceNode<T>> {
+    let left = match left {
+        Some(left)
+            if left.color == TreeColors::Red || left.black_height > get_black_height(&right) =>
+        {
+            left
+        }
+        left => return new_node(TreeColors::Red, left, data, right),
+    };
+
+    let SequenceNode {
+        color,
+        left: left_left,
+        data: left_data,
+        right: left_right,
+        ..
+    } = *left;
+    let joined = join_right(left_right, data, right);
+
+    if color == TreeColors::Black && joined.color == TreeColors::Red && is_red(&joined.right) {
+        let SequenceNode {
+            left: middle,
+            data: joined_data,
+            right: joined_right,
+            ..
+        } = *joined;
+
+        new_node(
+            TreeColors::Red,
+            Some(new_node(TreeColors::Black, left_left, left_data, middle)),
+            joined_data,
+            joined_right.map(blacken),
+        )
+    } else {
+        new_node(color, left_left, left_data, Some(joined))
+    }
+}
+
+// Mirror of `join_right`, for a `left` tree that is shorter than the `right` one
+fn join_left<T>(left: Link<T>, data: T, right: Link<T>) -> Box<SequenceNode<T>> {
+    let right = match right {
+        Some(right)
+            if right.color == TreeColors::Red || right.black_height > get_black_height(&left) =>
+        {
+            right
+        }
+        right => return new_node(TreeColors::Red, left, data, right),
+    };
+
+    let SequenceNode {
+        color,
+        left: right_left,
+        data: right_data,
+        right: right_right,
+        ..
+    } = *right;
+    let joined = join_left(left, data, right_left);
+
+    if color == TreeColors::Black && joined.color == TreeColors::Red && is_red(&joined.left) {
+        let SequenceNode {
+            left: joined_left,
+            data: joined_data,
+            right: middle,
+            ..
+        } = *joined;
+
+        new_node(
+            TreeColors::Red,
+            joined_left.map(blacken),
+            joined_data,
+            Some(new_node(TreeColors::Black, middle, right_data, right_right)),
+        )
+    } else {
+        new_node(color, Some(joined), right_data, right_right)
+    }
+}
+
+// Joins two trees without a value in between, by taking the first value of `right`
+fn join_all<T>(left: Link<T>, right: Link<T>) -> Link<T> {
+    match right {
+        Some(right) => {
+            let (first, rest) = split_first(*right);
+            Some(join(left, first, rest))
+        }
+        None => left,
+    }
+}
+
+// Recursive function that splits a tree into its first `pos` values and the rest. Every
+//  node along the way is taken apart and its children joined onto either side, which
+//  adds up to O(log n) since each join only costs the difference in black heights.
+fn split<T>(link: Link<T>, pos: usize) -> (Link<T>, Link<T>) {
+    let node = match link {
+        Some(node) => node,
+        None => return (None, None),
+    };
+
+    let SequenceNode {
+        left, data, right, ..
+    } = *node;
+    let left_size = get_size(&left);
+
+    if pos <= left_size {
+        let (front, back) = split(left, pos);
+        (front, Some(join(back, data, right)))
+    } else {
+        let (front, back) = split(right, pos - left_size - 1);
+        (Some(join(left, data, front)), back)
+    }
+}
+
+// Recursive function that takes the first value out of a tree, returning the rest
+fn split_first<T>(node: SequenceNode<T>) -> (T, Link<T>) {
+    let SequenceNode {
+        left, data, right, ..
+    } = node;
+
+    match left {
+        Some(left) => {
+            let (first, rest) = split_first(*left);
+            (first, Some(join(rest, data, right)))
+        }
+        None => (data, right),
+    }
+}
+
+// Recursive function that returns a subtree's black height, or None if any path
+//  through it breaks the red-black properties or a node's cached values are off
+fn get_valid_black_height<T>(link: &Link<T>) -> Option<usize> {
+    let node = match link {
+        Some(node) => node,
+        None => return Some(0),
+    };
+
+    if node.color == TreeColors::Red && (is_red(&node.left) || is_red(&node.right)) {
+        return None;
+    }
+
+    let left_height = get_valid_black_height(&node.left)?;
+    let right_height = get_valid_black_height(&node.right)?;
+    let black_height = match node.color {
+        TreeColors::Black => left_height + 1,
+        TreeColors::Red => left_height,
+    };
+
+    if left_height != right_height
+        || node.black_height != black_height
+        || node.size != 1 + get_size(&node.left) + get_size(&node.right)
+    {
+        return None;
+    }
+
+    Some(black_height)
+}
@@ -0,0 +1,128 @@
+use core::cmp::Ordering;
+use core::iter::Peekable;
+
+// Lazy set operations over two trees. Every iterator walks both trees in order at the
+//  same time, so values come out sorted and nothing is collected up front. They're generic
+//  over the trees' in-order iterator, so that the arena-based and the `raw` trees can
+//  share them.
+//
+// Trees may hold duplicate values, in which case each value is matched up with at most
+//  one equal value from the other tree, the same way a sorted merge would pair them.
+
+// Compares the next values of both iterators. A finished iterator sorts after everything,
+//  so the other iterator's remaining values are drained last.
+fn cmp_next<'a, T: PartialOrd + 'a, I: Iterator<Item = &'a T>>(
+    first: &mut Peekable<I>,
+    second: &mut Peekable<I>,
+) -> Option<Ordering> {
+    match (first.peek(), second.peek()) {
+        (None, None) => None,
+        (Some(_), None) => Some(Ordering::Less),
+        (None, Some(_)) => Some(Ordering::Greater),
+        (Some(first_val), Some(second_val)) => {
+            Some(first_val.partial_cmp(second_val).unwrap_or(Ordering::Equal))
+        }
+    }
+}
+
+// Values found in either tree. Values found in both are only yielded once, from the first tree.
+pub struct Union<I: Iterator> {
+    pub(crate) first: Peekable<I>,
+    pub(crate) second: Peekable<I>,
+}
+
+impl<'a, T: PartialOrd + 'a, I: Iterator<Item = &'a T>> Iterator for Union<I> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        match cmp_next(&mut self.first, &mut self.second)? {
+            Ordering::Less => self.first.next(),
+            Ordering::Greater => self.second.next(),
+            Ordering::Equal => {
+                self.second.next();
+                self.first.next()
+            }
+        }
+    }
+}
+
+// Values found in both trees, yielded from the first tree
+pub struct Intersection<I: Iterator> {
+    pub(crate) first: Peekable<I>,
+    pub(crate) second: Peekable<I>,
+}
+
+impl<'a, T: PartialOrd + 'a, I: Iterator<Item = &'a T>> Iterator for Intersection<I> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            // Once either tree runs out there is nothing left in common
+            self.first.peek()?;
+            self.second.peek()?;
+
+            match cmp_next(&mut self.first, &mut self.second)? {
+                Ordering::Less => {
+                    self.first.next();
+                }
+                Ordering::Greater => {
+                    self.second.next();
+                }
+                Ordering::Equal => {
+                    self.second.next();
+                    return self.first.next();
+                }
+            }
+        }
+    }
+}
+
+// Values found in the first tree but not in the second
+pub struct Difference<I: Iterator> {
+    pub(crate) first: Peekable<I>,
+    pub(crate) second: Peekable<I>,
+}
+
+impl<'a, T: PartialOrd + 'a, I: Iterator<Item = &'a T>> Iterator for Difference<I> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match cmp_next(&mut self.first, &mut self.second)? {
+                Ordering::Less => return self.first.next(),
+                Ordering::Greater => {
+                    // Nothing left to take from the first tree
+                    self.first.peek()?;
+                    self.second.next();
+                }
+                Ordering::Equal => {
+                    self.first.next();
+                    self.second.next();
+                }
+            }
+        }
+    }
+}
+
+// Values found in exactly one of the two trees
+pub struct SymmetricDifference<I: Iterator> {
+    pub(crate) first: Peekable<I>,
+    pub(crate) second: Peekable<I>,
+}
+
+impl<'a, T: PartialOrd + 'a, I: Iterator<Item = &'a T>> Iterator for SymmetricDifference<I> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match cmp_next(&mut self.first, &mut self.second)? {
+                Ordering::Less => return self.first.next(),
+                Ordering::Greater => return self.second.next(),
+                Ordering::Equal => {
+                    self.first.next();
+                    self.second.next();
+                }
+            }
+        }
+    }
+}
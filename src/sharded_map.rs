@@ -0,0 +1,423 @@
+use super::base_tree::InorderIter;
+use super::red_black_tree::RedBlackTree;
+use super::tree_errs::{IncomparableKeyErr, NodeNotFoundErr};
+
+use std::cmp::{Ordering, PartialOrd};
+use std::collections::VecDeque;
+use std::fmt::{Display, Formatter, Result};
+use std::ops::Bound;
+use std::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+use std::sync::{RwLock, RwLockReadGuard, RwLockWriteGuard};
+use std::thread;
+
+// Shards holding more entries than this are split in two
+const DEFAULT_MAX_SHARD_SIZE: usize = 4096;
+
+// Key-value pair stored in the shards' trees, ordered by key alone
+#[derive(Default, Clone)]
+struct MapEntry<K, V> {
+    key: K,
+    value: V,
+}
+
+impl<K: PartialOrd, V> PartialEq for MapEntry<K, V> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<K: PartialOrd, V> PartialOrd for MapEntry<K, V> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        self.key.partial_cmp(&other.key)
+    }
+}
+
+impl<K: Display, V: Display> Display for MapEntry<K, V> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "{}: {}", self.key, self.value)
+    }
+}
+
+type ShardTree<K, V> = RedBlackTree<MapEntry<K, V>>;
+
+// Holds every key from `lower` (inclusive) up to the next shard's lower bound
+struct Shard<K: PartialOrd + Display + Default, V: Display + Default> {
+    lower: Option<K>,
+    tree: RwLock<ShardTree<K, V>>,
+    // Set while the tree is being copied for a split, during which writers have to back
+    //  off so that the copy stays current
+    is_splitting: AtomicBool,
+}
+
+impl<K: PartialOrd + Display + Default, V: Display + Default> Shard<K, V> {
+    fn new(lower: Option<K>, tree: ShardTree<K, V>) -> Self {
+        Shard {
+            lower,
+            tree: RwLock::new(tree),
+            is_splitting: AtomicBool::new(false),
+        }
+    }
+
+    fn read(&self) -> RwLockReadGuard<'_, ShardTree<K, V>> {
+        self.tree
+            .read()
+            .expect("A thread panicked while holding a shard lock")
+    }
+
+    fn write(&self) -> RwLockWriteGuard<'_, ShardTree<K, V>> {
+        self.tree
+            .write()
+            .expect("A thread panicked while holding a shard lock")
+    }
+}
+
+// An ordered map split by key ranges across several red-black trees, each behind its own
+//  lock, so that writes to different parts of the key space don't wait on one another.
+//  Keys are unique: inserting an existing key replaces its value. Shards are found by
+//  comparing keys, so a key that can't be compared with itself (such as a NaN) is
+//  rejected rather than stored.
+//
+// A second lock guards the list of shards itself. Regular operations only take it for
+//  reading. A shard that has grown past the maximum size is copied and split in two while
+//  only holding read locks, and the write lock is then held just long enough to swap the
+//  halves in. Shards are never merged back together.
+pub struct ShardedMap<K, V>
+where
+    K: PartialOrd + Display + Default + Clone,
+    V: Display + Default + Clone,
+{
+    shards: RwLock<Vec<Shard<K, V>>>,
+    max_shard_size: usize,
+}
+
+impl<K, V> Default for ShardedMap<K, V>
+where
+    K: PartialOrd + Display + Default + Clone,
+    V: Display + Default + Clone,
+{
+    fn default() -> Self {
+        ShardedMap::new()
+    }
+}
+
+impl<K, V> ShardedMap<K, V>
+where
+    K: PartialOrd + Display + Default + Clone,
+    V: Display + Default + Clone,
+{
+    pub fn new() -> Self {
+        ShardedMap::with_max_shard_size(DEFAULT_MAX_SHARD_SIZE)
+    }
+
+    pub fn with_max_shard_size(max_shard_size: usize) -> Self {
+        ShardedMap {
+            shards: RwLock::new(vec![Shard::new(None, RedBlackTree::new())]),
+            max_shard_size: max_shard_size.max(1),
+        }
+    }
+
+    // Starts out with a shard for every range between the split points, for when the key
+    //  distribution is known up front and writers shouldn't contend until shards fill up.
+    //  The split points have to be ordered among themselves, so any pair that can't be
+    //  compared (such as a NaN) is rejected.
+    pub fn with_split_points(
+        mut split_points: Vec<K>,
+        max_shard_size: usize,
+    ) -> std::result::Result<Self, IncomparableKeyErr> {
+        let mut is_comparable = split_points
+            .iter()
+            .all(|split_point| split_point.partial_cmp(split_point).is_some());
+        split_points.sort_by(|key, other| {
+            key.partial_cmp(other).unwrap_or_else(|| {
+                is_comparable = false;
+                Ordering::Equal
+            })
+        });
+
+        if !is_comparable {
+            return Err(IncomparableKeyErr);
+        }
+        split_points.dedup_by(|key, other| key == other);
+
+        let map = ShardedMap::with_max_shard_size(max_shard_size);
+        map.write_shards().extend(
+            split_points
+                .into_iter()
+                .map(|split_point| Shard::new(Some(split_point), RedBlackTree::new())),
+        );
+
+        Ok(map)
+    }
+
+    fn read_shards(&self) -> RwLockReadGuard<'_, Vec<Shard<K, V>>> {
+        self.shards
+            .read()
+            .expect("A thread panicked while holding the shard list lock")
+    }
+
+    fn write_shards(&self) -> RwLockWriteGuard<'_, Vec<Shard<K, V>>> {
+        self.shards
+            .write()
+            .expect("A thread panicked while holding the shard list lock")
+    }
+
+    // Position of the shard whose range holds the key. The first shard has no lower
+    //  bound, so there always is one.
+    fn find_shard_pos(shards: &[Shard<K, V>], key: &K) -> usize {
+        let following_pos =
+            shards.partition_point(|shard| shard.lower.as_ref().is_none_or(|lower| *lower <= *key));
+
+        following_pos - 1
+    }
+
+    // Entry that compares equal to any entry with the same key
+    fn probe(key: &K) -> MapEntry<K, V> {
+        MapEntry {
+            key: key.clone(),
+            value: V::default(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.read_shards()
+            .iter()
+            .map(|shard| shard.read().get_size())
+            .sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn shard_count(&self) -> usize {
+        self.read_shards().len()
+    }
+
+    // Inserts or replaces the key's value, returning the value it replaced
+    pub fn insert(&self, key: K, value: V) -> std::result::Result<Option<V>, IncomparableKeyErr> {
+        if key.partial_cmp(&key).is_none() {
+            return Err(IncomparableKeyErr);
+        }
+
+        let (old_value, shard_size) = self.write_shard(&key, |tree| {
+            match tree.bst.find_node_index(&Self::probe(&key)) {
+                Some(node_idx) => {
+                    let entry = &mut tree.bst.nodes[node_idx].data;
+                    (Some(std::mem::replace(&mut entry.value, value)), 0)
+                }
+                None => {
+                    tree.insert(MapEntry {
+                        key: key.clone(),
+                        value,
+                    });
+                    (None, tree.get_size())
+                }
+            }
+        });
+
+        if shard_size > self.max_shard_size {
+            self.split_shard(&key);
+        }
+
+        Ok(old_value)
+    }
+
+    pub fn remove(&self, key: &K) -> std::result::Result<V, NodeNotFoundErr> {
+        self.write_shard(key, |tree| {
+            let node_idx = tree
+                .bst
+                .find_node_index(&Self::probe(key))
+                .ok_or(NodeNotFoundErr)?;
+            Ok(tree.remove_node(node_idx).value)
+        })
+    }
+
+    // Runs a write against the shard holding the key. While that shard is being copied
+    //  for a split, every lock is let go of and the write tried again, which also lets
+    //  the split take the shard list's write lock once the copy is done.
+    fn write_shard<R>(&self, key: &K, write: impl FnOnce(&mut ShardTree<K, V>) -> R) -> R {
+        loop {
+            let shards = self.read_shards();
+            let shard = &shards[Self::find_shard_pos(&shards, key)];
+            let mut tree = shard.write();
+
+            if !shard.is_splitting.load(AtomicOrdering::Relaxed) {
+                return write(&mut tree);
+            }
+
+            drop(tree);
+            drop(shards);
+            thread::yield_now();
+        }
+    }
+
+    // Copy of the key's value, since the shard can't stay locked once this returns
+    pub fn get(&self, key: &K) -> Option<V> {
+        let shards = self.read_shards();
+        let tree = shards[Self::find_shard_pos(&shards, key)].read();
+
+        let node_idx = tree.bst.find_node_index(&Self::probe(key))?;
+        Some(tree.bst.nodes[node_idx].data.value.clone())
+    }
+
+    pub fn contains_key(&self, key: &K) -> bool {
+        let shards = self.read_shards();
+        let tree = shards[Self::find_shard_pos(&shards, key)].read();
+
+        tree.bst.find_node_index(&Self::probe(key)).is_some()
+    }
+
+    // Splits the shard holding the key at its median entry. The halves are built from a
+    //  copy of the tree while only holding read locks, so lookups carry on throughout and
+    //  only writers to this one shard wait. The shard list's write lock is then taken just
+    //  long enough to swap the halves in.
+    fn split_shard(&self, key: &K) {
+        let (lower, lower_tree, median, upper_tree) = {
+            let shards = self.read_shards();
+            let shard = &shards[Self::find_shard_pos(&shards, key)];
+            let tree = shard.read();
+
+            // Another thread may have split it already, or be busy splitting it
+            if tree.get_size() <= self.max_shard_size
+                || shard.is_splitting.swap(true, AtomicOrdering::Relaxed)
+            {
+                return;
+            }
+
+            let median = tree
+                .iter()
+                .nth(tree.get_size() / 2)
+                .expect("The shard holds more than the median's position")
+                .clone();
+            let mut lower_tree = tree.clone();
+            let upper_tree = lower_tree.split_off(&median);
+
+            (shard.lower.clone(), lower_tree, median, upper_tree)
+        };
+
+        let old_tree = {
+            // Writers to the shard back off until it's split, but other shards may have
+            //  been split in the meantime, moving it along the list
+            let mut shards = self.write_shards();
+            let shard_pos = shards
+                .iter()
+                .position(|shard| shard.lower == lower)
+                .expect("Shards are never removed");
+            let shard = &mut shards[shard_pos];
+
+            let tree = shard
+                .tree
+                .get_mut()
+                .expect("A thread panicked while holding a shard lock");
+            let old_tree = std::mem::replace(tree, lower_tree);
+            shard.is_splitting.store(false, AtomicOrdering::Relaxed);
+
+            shards.insert(shard_pos + 1, Shard::new(Some(median.key), upper_tree));
+            old_tree
+        };
+
+        // The old tree is only dropped once the shard list is unlocked
+        drop(old_tree);
+    }
+
+    // Checks that every shard is a valid red-black tree holding only keys within its range
+    pub fn is_valid(&self) -> bool {
+        let shards = self.read_shards();
+
+        shards.iter().enumerate().all(|(shard_pos, shard)| {
+            let tree = shard.read();
+            let upper = shards
+                .get(shard_pos + 1)
+                .and_then(|next_shard| next_shard.lower.as_ref());
+
+            let in_range = tree.iter().all(|entry| {
+                shard.lower.as_ref().is_none_or(|lower| *lower <= entry.key)
+                    && upper.is_none_or(|upper| entry.key < *upper)
+            });
+            let is_unique = tree
+                .iter()
+                .zip(tree.iter().skip(1))
+                .all(|(entry, next_entry)| entry.key < next_entry.key);
+
+            tree.is_valid() && in_range && is_unique
+        })
+    }
+
+    // Iterates over copies of the entries in key order, reading one shard at a time. As
+    //  with `ChunkedIter`, writes in between shards show up if they land ahead of the
+    //  iterator, and shards being split along the way are handled too.
+    pub fn iter(&self) -> ShardedIter<'_, K, V> {
+        ShardedIter {
+            source: self,
+            chunk: VecDeque::new(),
+            last: None,
+            is_done: false,
+        }
+    }
+}
+
+pub struct ShardedIter<'a, K, V>
+where
+    K: PartialOrd + Display + Default + Clone,
+    V: Display + Default + Clone,
+{
+    source: &'a ShardedMap<K, V>,
+    chunk: VecDeque<(K, V)>,
+    // Largest key read so far, which the next shard read picks up after
+    last: Option<MapEntry<K, V>>,
+    is_done: bool,
+}
+
+impl<'a, K, V> ShardedIter<'a, K, V>
+where
+    K: PartialOrd + Display + Default + Clone,
+    V: Display + Default + Clone,
+{
+    // Copies out every entry after the last key from the shard holding it, moving on to
+    //  later shards while they come up empty
+    fn read_chunk(&mut self) {
+        let shards = self.source.read_shards();
+        let mut shard_pos = match &self.last {
+            Some(last) => ShardedMap::find_shard_pos(&shards, &last.key),
+            None => 0,
+        };
+
+        while self.chunk.is_empty() && shard_pos < shards.len() {
+            let tree = shards[shard_pos].read();
+            let start = match &self.last {
+                Some(last) => Bound::Excluded(last),
+                None => Bound::Unbounded,
+            };
+
+            self.chunk.extend(
+                InorderIter::from_start_bound(tree.bst.root, &tree.bst.nodes, start)
+                    .map(|entry| (entry.key.clone(), entry.value.clone())),
+            );
+            shard_pos += 1;
+        }
+
+        self.is_done = shard_pos == shards.len();
+        if let Some((key, value)) = self.chunk.back() {
+            self.last = Some(MapEntry {
+                key: key.clone(),
+                value: value.clone(),
+            });
+        }
+    }
+}
+
+impl<'a, K, V> Iterator for ShardedIter<'a, K, V>
+where
+    K: PartialOrd + Display + Default + Clone,
+    V: Display + Default + Clone,
+{
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.chunk.is_empty() && !self.is_done {
+            self.read_chunk();
+        }
+
+        self.chunk.pop_front()
+    }
+}
@@ -0,0 +1,117 @@
+// Nodes stored by their position in a run of slots, as the slab and array node stores and
+//  `StaticRedBlackTree` all do. Free slots form a linked list through the position of the
+//  next free slot, so both filling and freeing a slot are O(1). Only needs `core`.
+#[derive(Clone)]
+pub(crate) enum Slot<N> {
+    Occupied(N),
+    Free(Option<usize>),
+}
+
+pub(crate) fn get_slot<N>(slots: &[Slot<N>], pos: usize) -> Option<&N> {
+    match slots.get(pos) {
+        Some(Slot::Occupied(node)) => Some(node),
+        _ => None,
+    }
+}
+
+pub(crate) fn get_slot_mut<N>(slots: &mut [Slot<N>], pos: usize) -> Option<&mut N> {
+    match slots.get_mut(pos) {
+        Some(Slot::Occupied(node)) => Some(node),
+        _ => None,
+    }
+}
+
+// Takes the node out of its slot, which is pushed onto the free list
+pub(crate) fn free_slot<N>(
+    slots: &mut [Slot<N>],
+    free_head: &mut Option<usize>,
+    pos: usize,
+) -> Option<N> {
+    match slots.get_mut(pos) {
+        Some(contents @ Slot::Occupied(_)) => {
+            match core::mem::replace(contents, Slot::Free(*free_head)) {
+                Slot::Occupied(node) => {
+                    *free_head = Some(pos);
+                    Some(node)
+                }
+                Slot::Free(_) => unreachable!("The slot was just checked to be occupied"),
+            }
+        }
+        _ => None,
+    }
+}
+
+// Fills the slot at the head of the free list, handing back its position
+pub(crate) fn fill_slot<N>(
+    slots: &mut [Slot<N>],
+    free_head: &mut Option<usize>,
+    node: N,
+) -> Option<usize> {
+    let pos = (*free_head)?;
+
+    match core::mem::replace(&mut slots[pos], Slot::Occupied(node)) {
+        Slot::Free(next_free) => *free_head = next_free,
+        Slot::Occupied(_) => unreachable!("Only free slots are kept on the free list"),
+    }
+
+    Some(pos)
+}
+
+// Mutable access to the nodes in two different slots. Panics when both are the same.
+#[cfg(feature = "alloc")]
+pub(crate) fn get2_slots_mut<N>(
+    slots: &mut [Slot<N>],
+    first_pos: usize,
+    second_pos: usize,
+) -> (Option<&mut N>, Option<&mut N>) {
+    assert_ne!(first_pos, second_pos, "Can't borrow the same node twice");
+
+    // Split right before the later slot, so that each half holds one of the two
+    let (lower_pos, upper_pos) = (first_pos.min(second_pos), first_pos.max(second_pos));
+    let (lower, upper) = slots.split_at_mut(upper_pos.min(slots.len()));
+    let (lower_node, upper_node) = (get_slot_mut(lower, lower_pos), get_slot_mut(upper, 0));
+
+    if first_pos < second_pos {
+        (lower_node, upper_node)
+    } else {
+        (upper_node, lower_node)
+    }
+}
+
+// A fixed number of slots kept inline, which never grows
+#[derive(Clone)]
+pub(crate) struct SlotArray<N, const LEN: usize> {
+    pub(crate) slots: [Slot<N>; LEN],
+    free_head: Option<usize>,
+}
+
+impl<N, const LEN: usize> SlotArray<N, LEN> {
+    // Every slot starts out free, linked to the one after it
+    pub(crate) fn new() -> Self {
+        SlotArray {
+            slots: core::array::from_fn(|pos| Slot::Free(Some(pos + 1).filter(|next| *next < LEN))),
+            free_head: Some(0).filter(|_| LEN > 0),
+        }
+    }
+
+    pub(crate) fn is_full(&self) -> bool {
+        self.free_head.is_none()
+    }
+
+    pub(crate) fn get(&self, pos: usize) -> Option<&N> {
+        get_slot(&self.slots, pos)
+    }
+
+    pub(crate) fn get_mut(&mut self, pos: usize) -> Option<&mut N> {
+        get_slot_mut(&mut self.slots, pos)
+    }
+
+    // Stores the node in a free slot, or returns None when every slot is taken
+    pub(crate) fn fill(&mut self, node: N) -> Option<usize> {
+        fill_slot(&mut self.slots, &mut self.free_head, node)
+    }
+
+    pub(crate) fn free(&mut self, pos: usize) -> Option<N> {
+        free_slot(&mut self.slots, &mut self.free_head, pos)
+    }
+}
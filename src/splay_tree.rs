@@ -0,0 +1,205 @@
+use generational_arena::Index;
+
+use super::base_tree::{BfsIter, DfsIter, InorderIter, InternalBinarySearchTree, Node};
+use super::search_tree::SearchTree;
+use super::tree_errs::NodeNotFoundErr;
+
+use core::cmp::PartialOrd;
+use core::fmt::Display;
+
+// Splay trees keep no balancing data at all. Instead, every access rotates the accessed
+//  node all the way up to the root, which gives amortised O(log n) operations and makes
+//  repeated access to the same few values very cheap. The catch is that lookups restructure
+//  the tree, so `find` needs `&mut self`. Use `peek` to look a value up without splaying.
+#[derive(Default)]
+pub struct SplayTree<T: PartialOrd + Display + Default> {
+    bst: InternalBinarySearchTree<T>,
+}
+
+impl<T: PartialOrd + Display + Default> SplayTree<T> {
+    pub fn new() -> Self {
+        SplayTree {
+            bst: InternalBinarySearchTree::new(),
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.bst.nodes.len()
+    }
+
+    pub fn insert(&mut self, item: T) {
+        let leaf = Node {
+            data: item,
+            left: None,
+            right: None,
+            parent: None,
+        };
+
+        let new_leaf_idx = self.bst.insert_node(leaf);
+        self.splay(new_leaf_idx, None);
+    }
+
+    // Looks up a value and splays it to the root. When the value is missing, the last node
+    //  on the search path is splayed instead, so that misses get amortised as well.
+    pub fn find(&mut self, item: &T) -> Option<&T> {
+        let mut last_node_opt = None;
+        let mut cur_node_opt = self.bst.root;
+
+        while let Some(cur_node_idx) = cur_node_opt {
+            let cur_node = &self.bst.nodes[cur_node_idx];
+            last_node_opt = cur_node_opt;
+
+            if cur_node.data == *item {
+                break;
+            }
+
+            cur_node_opt = if *item < cur_node.data {
+                cur_node.left
+            } else {
+                cur_node.right
+            };
+        }
+
+        self.splay(last_node_opt?, None);
+        let found_idx = cur_node_opt?;
+        Some(&self.bst.nodes[found_idx].data)
+    }
+
+    // Looks up a value without restructuring the tree
+    pub fn peek(&self, item: &T) -> Option<&T> {
+        self.bst
+            .find_node_index(item)
+            .map(|node_idx| &self.bst.nodes[node_idx].data)
+    }
+
+    pub fn contains(&self, item: &T) -> bool {
+        self.peek(item).is_some()
+    }
+
+    // Splays the node to the root, then joins its two subtrees back together by splaying
+    //  the largest node of the left subtree up underneath it. That node can't have a right
+    //  child, so the right subtree can be hung off of it once the removed node is gone.
+    pub fn remove(&mut self, item: &T) -> Result<(), NodeNotFoundErr> {
+        let node_idx_to_remove = self.bst.find_node_index(item).ok_or(NodeNotFoundErr)?;
+        self.splay(node_idx_to_remove, None);
+
+        let node_to_remove = &self.bst.nodes[node_idx_to_remove];
+        let right_idx_opt = node_to_remove.right;
+
+        let new_root_opt = match node_to_remove.left {
+            Some(left_idx) => {
+                let left_max_idx = self.bst.get_subtree_max(left_idx);
+                self.splay(left_max_idx, Some(node_idx_to_remove));
+
+                self.bst.nodes[left_max_idx].right = right_idx_opt;
+                if let Some(right_idx) = right_idx_opt {
+                    self.bst.nodes[right_idx].parent = Some(left_max_idx);
+                }
+
+                Some(left_max_idx)
+            }
+            None => right_idx_opt,
+        };
+
+        if let Some(new_root_idx) = new_root_opt {
+            self.bst.nodes[new_root_idx].parent = None;
+        }
+        self.bst.root = new_root_opt;
+
+        let _ =
+            self.bst.nodes.remove(node_idx_to_remove).expect(
+                "Exclusive access during mutation ensures that a node exists for every index",
+            );
+
+        Ok(())
+    }
+
+    // Rotates a node up until its parent is `stop_parent` (or it becomes the root). Nodes
+    //  move up two levels at a time: when the node and its parent lean the same way, the
+    //  parent is rotated first (zig-zig), otherwise the node is rotated twice (zig-zag).
+    //  Rotating the parent first is what roughly halves the depth of the whole access path.
+    fn splay(&mut self, node_idx: Index, stop_parent: Option<Index>) {
+        while let Some(parent_idx) = self.bst.nodes[node_idx].parent {
+            if Some(parent_idx) == stop_parent {
+                break;
+            }
+
+            let grandparent_idx_opt = self.bst.nodes[parent_idx].parent;
+            match grandparent_idx_opt {
+                Some(grandparent_idx) if grandparent_idx_opt != stop_parent => {
+                    let node_is_left = self.bst.nodes[parent_idx].left == Some(node_idx);
+                    let parent_is_left = self.bst.nodes[grandparent_idx].left == Some(parent_idx);
+
+                    if node_is_left == parent_is_left {
+                        self.bst.rotate_node_up(parent_idx);
+                        self.bst.rotate_node_up(node_idx);
+                    } else {
+                        self.bst.rotate_node_up(node_idx);
+                        self.bst.rotate_node_up(node_idx);
+                    }
+                }
+                // The parent is the last step, so a single rotation finishes the splay
+                _ => self.bst.rotate_node_up(node_idx),
+            }
+        }
+    }
+
+    // Create a new iterator w/ a stack for DFS taversal
+    pub fn dfs_iter(&self) -> DfsIter<'_, T> {
+        self.bst.dfs_iter()
+    }
+
+    // Create a new iterator w/ a queue for BFS traversal
+    pub fn bfs_iter(&self) -> BfsIter<'_, T> {
+        self.bst.bfs_iter()
+    }
+
+    // Create a new iterator that visits values in sorted order
+    pub fn iter(&self) -> InorderIter<'_, T> {
+        self.bst.iter()
+    }
+}
+
+impl<T: PartialOrd + Display + Default> SearchTree<T> for SplayTree<T> {
+    type Iter<'a>
+        = InorderIter<'a, T>
+    where
+        T: 'a;
+
+    fn insert(&mut self, item: T) {
+        SplayTree::insert(self, item)
+    }
+
+    fn remove(&mut self, item: &T) -> Result<(), NodeNotFoundErr> {
+        SplayTree::remove(self, item)
+    }
+
+    // Shared lookups can't splay, so this behaves like `peek`
+    fn contains(&self, item: &T) -> bool {
+        SplayTree::contains(self, item)
+    }
+
+    fn len(&self) -> usize {
+        self.get_size()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.bst.iter()
+    }
+
+    fn min(&self) -> Option<&T> {
+        self.bst.get_min()
+    }
+
+    fn max(&self) -> Option<&T> {
+        self.bst.get_max()
+    }
+
+    fn successor(&self, item: &T) -> Option<&T> {
+        self.bst.get_successor(item)
+    }
+
+    fn predecessor(&self, item: &T) -> Option<&T> {
+        self.bst.get_predecessor(item)
+    }
+}
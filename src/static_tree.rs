@@ -0,0 +1,339 @@
+use super::rb_balance::{
+    fix_red_red, get_inorder_successor, get_subtree_max, get_subtree_min, get_valid_black_height,
+    unlink_node, RedBlackLinks,
+};
+use super::search_tree::SearchTree;
+use super::slots::SlotArray;
+use super::tree_errs::{CapacityExceededErr, NodeNotFoundErr};
+
+use core::cmp::PartialOrd;
+
+#[derive(Clone)]
+struct StaticNode<T> {
+    data: T,
+    left: Option<usize>,
+    right: Option<usize>,
+    parent: Option<usize>,
+    is_red: bool,
+}
+
+// A red-black tree that never allocates: all `N` nodes live inline in the tree itself, and
+//  nodes link to each other by their position in that array. The slots and their free
+//  list are the same ones `node_store::ArrayStore` is built on. Once every slot is in use,
+//  inserting fails with a `CapacityExceededErr` rather than growing the tree.
+//
+// The balancing is the one `red_black_tree::RedBlackTree` uses, from `rb_balance`, but
+//  each node keeps its own color instead of a `HashMap` holding them, so this tree only
+//  needs `core`. For the same reason it walks the tree by following parent links instead of
+//  keeping a stack, and only offers in-order iteration.
+#[derive(Clone)]
+pub struct StaticRedBlackTree<T: PartialOrd, const N: usize> {
+    slots: SlotArray<StaticNode<T>, N>,
+    root: Option<usize>,
+    len: usize,
+}
+
+impl<T: PartialOrd, const N: usize> Default for StaticRedBlackTree<T, N> {
+    fn default() -> Self {
+        StaticRedBlackTree::new()
+    }
+}
+
+impl<T: PartialOrd, const N: usize> StaticRedBlackTree<T, N> {
+    pub fn new() -> Self {
+        StaticRedBlackTree {
+            slots: SlotArray::new(),
+            root: None,
+            len: 0,
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.len
+    }
+
+    pub fn capacity(&self) -> usize {
+        N
+    }
+
+    pub fn is_full(&self) -> bool {
+        self.slots.is_full()
+    }
+
+    fn node(&self, node_pos: usize) -> &StaticNode<T> {
+        self.slots
+            .get(node_pos)
+            .expect("Only occupied slots are linked into the tree")
+    }
+
+    fn node_mut(&mut self, node_pos: usize) -> &mut StaticNode<T> {
+        self.slots
+            .get_mut(node_pos)
+            .expect("Only occupied slots are linked into the tree")
+    }
+
+    pub fn insert(&mut self, item: T) -> Result<(), CapacityExceededErr> {
+        if self.is_full() {
+            return Err(CapacityExceededErr);
+        }
+
+        let mut parent_opt = None;
+        let mut cur_pos_opt = self.root;
+        let mut is_left = false;
+
+        while let Some(cur_pos) = cur_pos_opt {
+            let cur_node = self.node(cur_pos);
+            parent_opt = Some(cur_pos);
+            is_left = item < cur_node.data;
+            cur_pos_opt = if is_left {
+                cur_node.left
+            } else {
+                cur_node.right
+            };
+        }
+
+        let new_node = StaticNode {
+            data: item,
+            left: None,
+            right: None,
+            parent: parent_opt,
+            is_red: true,
+        };
+        let new_pos = self
+            .slots
+            .fill(new_node)
+            .expect("The tree was just checked to have a free slot");
+
+        match parent_opt {
+            Some(parent_pos) if is_left => self.node_mut(parent_pos).left = Some(new_pos),
+            Some(parent_pos) => self.node_mut(parent_pos).right = Some(new_pos),
+            None => self.root = Some(new_pos),
+        }
+
+        self.len += 1;
+        fix_red_red(self, new_pos);
+
+        Ok(())
+    }
+
+    fn find_node_pos(&self, item: &T) -> Option<usize> {
+        let mut cur_pos_opt = self.root;
+
+        while let Some(cur_pos) = cur_pos_opt {
+            let cur_node = self.node(cur_pos);
+            if cur_node.data == *item {
+                return Some(cur_pos);
+            }
+
+            cur_pos_opt = if *item < cur_node.data {
+                cur_node.left
+            } else {
+                cur_node.right
+            };
+        }
+
+        None
+    }
+
+    pub fn contains(&self, item: &T) -> bool {
+        self.find_node_pos(item).is_some()
+    }
+
+    pub fn remove(&mut self, item: &T) -> Result<(), NodeNotFoundErr> {
+        let node_pos = self.find_node_pos(item).ok_or(NodeNotFoundErr)?;
+        unlink_node(self, node_pos);
+
+        self.len -= 1;
+        self.slots
+            .free(node_pos)
+            .expect("The node was just found in the tree");
+        Ok(())
+    }
+
+    pub fn get_min(&self) -> Option<&T> {
+        let min_pos = get_subtree_min(self, self.root?);
+        Some(&self.node(min_pos).data)
+    }
+
+    pub fn get_max(&self) -> Option<&T> {
+        let max_pos = get_subtree_max(self, self.root?);
+        Some(&self.node(max_pos).data)
+    }
+
+    // Smallest value strictly greater than `item`
+    pub fn get_successor(&self, item: &T) -> Option<&T> {
+        let mut successor = None;
+        let mut cur_pos_opt = self.root;
+
+        while let Some(cur_pos) = cur_pos_opt {
+            let cur_node = self.node(cur_pos);
+            if *item < cur_node.data {
+                successor = Some(&cur_node.data);
+                cur_pos_opt = cur_node.left;
+            } else {
+                cur_pos_opt = cur_node.right;
+            }
+        }
+
+        successor
+    }
+
+    // Largest value strictly less than `item`
+    pub fn get_predecessor(&self, item: &T) -> Option<&T> {
+        let mut predecessor = None;
+        let mut cur_pos_opt = self.root;
+
+        while let Some(cur_pos) = cur_pos_opt {
+            let cur_node = self.node(cur_pos);
+            if cur_node.data < *item {
+                predecessor = Some(&cur_node.data);
+                cur_pos_opt = cur_node.right;
+            } else {
+                cur_pos_opt = cur_node.left;
+            }
+        }
+
+        predecessor
+    }
+
+    // Checks the slots the tree links together: values are in order, every child's parent
+    //  position points back at it, the root is black and has no parent, no red node has a
+    //  red child and every path sees the same number of black nodes. The number of linked
+    //  nodes also has to match `len`.
+    pub fn is_valid(&self) -> bool {
+        if let Some(root_pos) = self.root {
+            if self.node(root_pos).parent.is_some() || self.node(root_pos).is_red {
+                return false;
+            }
+        }
+
+        let mut node_count = 0;
+        let mut prev_data = None;
+        for data in self.iter() {
+            if prev_data.is_some_and(|prev| prev > data) {
+                return false;
+            }
+
+            prev_data = Some(data);
+            node_count += 1;
+        }
+
+        node_count == self.len && get_valid_black_height(self, self.root).is_some()
+    }
+
+    // Create a new iterator that visits values in sorted order
+    pub fn iter(&self) -> Iter<'_, T, N> {
+        Iter {
+            tree: self,
+            next_pos: self.root.map(|root_pos| get_subtree_min(self, root_pos)),
+        }
+    }
+}
+
+impl<T: PartialOrd, const N: usize> RedBlackLinks for StaticRedBlackTree<T, N> {
+    type Handle = usize;
+
+    fn root(&self) -> Option<usize> {
+        self.root
+    }
+
+    fn set_root(&mut self, root_opt: Option<usize>) {
+        self.root = root_opt;
+    }
+
+    fn left(&self, node_pos: usize) -> Option<usize> {
+        self.node(node_pos).left
+    }
+
+    fn right(&self, node_pos: usize) -> Option<usize> {
+        self.node(node_pos).right
+    }
+
+    fn parent(&self, node_pos: usize) -> Option<usize> {
+        self.node(node_pos).parent
+    }
+
+    fn set_left(&mut self, node_pos: usize, left_opt: Option<usize>) {
+        self.node_mut(node_pos).left = left_opt;
+    }
+
+    fn set_right(&mut self, node_pos: usize, right_opt: Option<usize>) {
+        self.node_mut(node_pos).right = right_opt;
+    }
+
+    fn set_parent(&mut self, node_pos: usize, parent_opt: Option<usize>) {
+        self.node_mut(node_pos).parent = parent_opt;
+    }
+
+    fn is_red(&self, node_pos: usize) -> bool {
+        self.node(node_pos).is_red
+    }
+
+    fn set_red(&mut self, node_pos: usize, is_red: bool) {
+        self.node_mut(node_pos).is_red = is_red;
+    }
+}
+
+// Walks the tree in order by following parent links, so it needs no stack
+pub struct Iter<'a, T: PartialOrd, const N: usize> {
+    tree: &'a StaticRedBlackTree<T, N>,
+    next_pos: Option<usize>,
+}
+
+impl<'a, T: PartialOrd, const N: usize> Iterator for Iter<'a, T, N> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let node_pos = self.next_pos?;
+        self.next_pos = get_inorder_successor(self.tree, node_pos);
+
+        Some(&self.tree.node(node_pos).data)
+    }
+}
+
+// Inserting through the shared trait can't report a full tree, so it panics instead, like
+//  a tree on `node_store::ArrayStore` does. Check `is_full` first, or call the inherent
+//  `insert` to get the error back.
+impl<T: PartialOrd, const N: usize> SearchTree<T> for StaticRedBlackTree<T, N> {
+    type Iter<'a>
+        = Iter<'a, T, N>
+    where
+        T: 'a;
+
+    fn insert(&mut self, item: T) {
+        StaticRedBlackTree::insert(self, item)
+            .unwrap_or_else(|_| panic!("Can't store more than {} nodes", N))
+    }
+
+    fn remove(&mut self, item: &T) -> Result<(), NodeNotFoundErr> {
+        StaticRedBlackTree::remove(self, item)
+    }
+
+    fn contains(&self, item: &T) -> bool {
+        StaticRedBlackTree::contains(self, item)
+    }
+
+    fn len(&self) -> usize {
+        self.get_size()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        StaticRedBlackTree::iter(self)
+    }
+
+    fn min(&self) -> Option<&T> {
+        self.get_min()
+    }
+
+    fn max(&self) -> Option<&T> {
+        self.get_max()
+    }
+
+    fn successor(&self, item: &T) -> Option<&T> {
+        self.get_successor(item)
+    }
+
+    fn predecessor(&self, item: &T) -> Option<&T> {
+        self.get_predecessor(item)
+    }
+}
@@ -0,0 +1,296 @@
+use generational_arena::Index;
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+
+use super::base_tree::{BfsIter, DfsIter, InorderIter, InternalBinarySearchTree, Node};
+use super::search_tree::SearchTree;
+use super::tree_errs::NodeNotFoundErr;
+
+use std::cmp::PartialOrd;
+use std::fmt::Display;
+use std::mem::{swap, take};
+
+// A treap is a binary search tree by value and a max-heap by a random priority handed
+//  to each node when it's inserted. Random priorities give the same expected shape as
+//  inserting values in random order, so operations take expected O(log n) time.
+pub struct Treap<T: PartialOrd + Display + Default> {
+    bst: InternalBinarySearchTree<T>,
+    priorities: HashMap<Index, u64>,
+    rng: SplitMix64,
+}
+
+// Small, seedable pseudo-random number generator, which is all we need for priorities.
+//  See: https://prng.di.unimi.it/splitmix64.c
+struct SplitMix64 {
+    state: u64,
+}
+
+impl SplitMix64 {
+    fn next(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ (z >> 31)
+    }
+}
+
+impl<T: PartialOrd + Display + Default> Default for Treap<T> {
+    fn default() -> Self {
+        Treap::new()
+    }
+}
+
+impl<T: PartialOrd + Display + Default> Treap<T> {
+    // Creates a treap seeded from the standard library's per-process random keys
+    pub fn new() -> Self {
+        Treap::with_seed(RandomState::new().build_hasher().finish())
+    }
+
+    // Creates a treap whose priorities, and therefore shape, are fully determined by
+    //  the seed and the sequence of operations. Handy for reproducible tests.
+    pub fn with_seed(seed: u64) -> Self {
+        Treap {
+            bst: InternalBinarySearchTree::new(),
+            priorities: HashMap::new(),
+            rng: SplitMix64 { state: seed },
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.bst.nodes.len()
+    }
+
+    pub fn insert(&mut self, item: T) {
+        let leaf = Node {
+            data: item,
+            left: None,
+            right: None,
+            parent: None,
+        };
+
+        let new_leaf_idx = self.bst.insert_node(leaf);
+        let priority = self.rng.next();
+        self.priorities.insert(new_leaf_idx, priority);
+
+        // Rotate the new leaf up until its parent outranks it
+        while let Some(parent_idx) = self.bst.nodes[new_leaf_idx].parent {
+            if self.priorities[&parent_idx] >= priority {
+                break;
+            }
+            self.bst.rotate_node_up(new_leaf_idx);
+        }
+    }
+
+    pub fn contains(&self, item: &T) -> bool {
+        self.bst.find_node_index(item).is_some()
+    }
+
+    pub fn remove(&mut self, item: &T) -> Result<(), NodeNotFoundErr> {
+        let node_idx_to_remove = self.bst.find_node_index(item).ok_or(NodeNotFoundErr)?;
+
+        // Rotate the node down, always lifting the higher priority child above it,
+        //  until it has at most one child and can be spliced out directly
+        while let (Some(left_idx), Some(right_idx)) = (
+            self.bst.nodes[node_idx_to_remove].left,
+            self.bst.nodes[node_idx_to_remove].right,
+        ) {
+            if self.priorities[&left_idx] > self.priorities[&right_idx] {
+                self.bst.rotate_node_up(left_idx);
+            } else {
+                self.bst.rotate_node_up(right_idx);
+            }
+        }
+
+        let (unlinked_idx, _, _) = self.bst.unlink_node(node_idx_to_remove);
+        self.priorities.remove(&unlinked_idx);
+        let _ =
+            self.bst.nodes.remove(unlinked_idx).expect(
+                "Exclusive access during mutation ensures that a node exists for every index",
+            );
+
+        Ok(())
+    }
+
+    // Moves every value greater than or equal to `key` into a new treap. Splitting a treap
+    //  by value keeps every node below one of its original ancestors, so the heap order
+    //  holds without any rotations. The split-off nodes then have to be moved over to the
+    //  new treap's arena, which is linear in the number of moved values.
+    pub fn split_off(&mut self, key: &T) -> Self {
+        let mut split_treap = Treap::with_seed(self.rng.next());
+        let (lesser_root, greater_root) = self.bst.split_subtree(self.bst.root, key);
+
+        self.bst.root = lesser_root;
+        if let Some(greater_root_idx) = greater_root {
+            split_treap.bst.root = Some(self.move_subtree_to(greater_root_idx, &mut split_treap));
+        }
+
+        split_treap
+    }
+
+    // Moves every value out of `other` and into this treap. The treaps are merged by
+    //  repeatedly splitting the lower priority treap around the other one's root, which
+    //  takes expected O(m log(n / m)) time, plus moving the smaller treap's nodes across.
+    pub fn append(&mut self, other: &mut Self) {
+        if self.get_size() < other.get_size() {
+            swap(&mut self.bst, &mut other.bst);
+            swap(&mut self.priorities, &mut other.priorities);
+        }
+
+        let other_root_idx = match other.bst.root.take() {
+            Some(other_root_idx) => other_root_idx,
+            None => return,
+        };
+
+        let moved_root_idx = other.move_subtree_to(other_root_idx, self);
+        let root_idx_opt = self.bst.root;
+
+        self.bst.root = self.union_subtrees(root_idx_opt, Some(moved_root_idx));
+        if let Some(root_idx) = self.bst.root {
+            self.bst.nodes[root_idx].parent = None;
+        }
+    }
+
+    // Recursive function that merges two detached subtrees in the same arena. The root
+    //  with the higher priority stays on top, the other subtree is split around its value,
+    //  and the halves are merged into its children.
+    fn union_subtrees(
+        &mut self,
+        first_idx_opt: Option<Index>,
+        second_idx_opt: Option<Index>,
+    ) -> Option<Index> {
+        let (first_idx, second_idx) = match (first_idx_opt, second_idx_opt) {
+            (Some(first_idx), Some(second_idx)) => (first_idx, second_idx),
+            (first_idx_opt, None) => return first_idx_opt,
+            (None, second_idx_opt) => return second_idx_opt,
+        };
+
+        let (root_idx, other_idx) = if self.priorities[&first_idx] >= self.priorities[&second_idx] {
+            (first_idx, second_idx)
+        } else {
+            (second_idx, first_idx)
+        };
+
+        let root_node = &mut self.bst.nodes[root_idx];
+        let left_idx_opt = root_node.left.take();
+        let right_idx_opt = root_node.right.take();
+
+        // Borrow the root's value as the split key, since the split mutates the arena
+        let key = take(&mut root_node.data);
+        let (other_lesser, other_greater) = self.bst.split_subtree(Some(other_idx), &key);
+        self.bst.nodes[root_idx].data = key;
+
+        let left_idx_opt = self.union_subtrees(left_idx_opt, other_lesser);
+        let right_idx_opt = self.union_subtrees(right_idx_opt, other_greater);
+
+        let root_node = &mut self.bst.nodes[root_idx];
+        root_node.left = left_idx_opt;
+        root_node.right = right_idx_opt;
+
+        for child_idx in left_idx_opt.iter().chain(right_idx_opt.iter()) {
+            self.bst.nodes[*child_idx].parent = Some(root_idx);
+        }
+
+        Some(root_idx)
+    }
+
+    // Moves a detached subtree, priorities included, into another treap's arena
+    fn move_subtree_to(&mut self, root_idx: Index, dst: &mut Self) -> Index {
+        let priorities = &mut self.priorities;
+        let dst_priorities = &mut dst.priorities;
+
+        self.bst
+            .move_subtree(root_idx, &mut dst.bst, |node_idx, new_node_idx| {
+                let priority = priorities
+                    .remove(&node_idx)
+                    .expect("Every node in the treap has a priority");
+                dst_priorities.insert(new_node_idx, priority);
+            })
+    }
+
+    // Checks that values are in order, that every child links back to its parent and has
+    //  a priority no higher than its parent's, and that the priority map holds exactly one
+    //  entry per node, so a priority left behind by a removal is caught too.
+    pub fn is_valid(&self) -> bool {
+        if self.priorities.len() != self.bst.nodes.len() {
+            return false;
+        }
+
+        if let Some(root_idx) = self.bst.root {
+            if self.bst.nodes[root_idx].parent.is_some() {
+                return false;
+            }
+        }
+
+        let values: Vec<&T> = self.bst.iter().collect();
+        let heap_ordered = self.bst.nodes.iter().all(|(node_idx, node)| {
+            node.left.iter().chain(node.right.iter()).all(|child_idx| {
+                self.bst.nodes[*child_idx].parent == Some(node_idx)
+                    && self.priorities[child_idx] <= self.priorities[&node_idx]
+            })
+        });
+
+        heap_ordered
+            && values.len() == self.bst.nodes.len()
+            && values.windows(2).all(|pair| pair[0] <= pair[1])
+    }
+
+    // Create a new iterator w/ a stack for DFS taversal
+    pub fn dfs_iter(&self) -> DfsIter<'_, T> {
+        self.bst.dfs_iter()
+    }
+
+    // Create a new iterator w/ a queue for BFS traversal
+    pub fn bfs_iter(&self) -> BfsIter<'_, T> {
+        self.bst.bfs_iter()
+    }
+
+    // Create a new iterator that visits values in sorted order
+    pub fn iter(&self) -> InorderIter<'_, T> {
+        self.bst.iter()
+    }
+}
+
+impl<T: PartialOrd + Display + Default> SearchTree<T> for Treap<T> {
+    type Iter<'a>
+        = InorderIter<'a, T>
+    where
+        T: 'a;
+
+    fn insert(&mut self, item: T) {
+        Treap::insert(self, item)
+    }
+
+    fn remove(&mut self, item: &T) -> Result<(), NodeNotFoundErr> {
+        Treap::remove(self, item)
+    }
+
+    fn contains(&self, item: &T) -> bool {
+        Treap::contains(self, item)
+    }
+
+    fn len(&self) -> usize {
+        self.get_size()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.bst.iter()
+    }
+
+    fn min(&self) -> Option<&T> {
+        self.bst.get_min()
+    }
+
+    fn max(&self) -> Option<&T> {
+        self.bst.get_max()
+    }
+
+    fn successor(&self, item: &T) -> Option<&T> {
+        self.bst.get_successor(item)
+    }
+
+    fn predecessor(&self, item: &T) -> Option<&T> {
+        self.bst.get_predecessor(item)
+    }
+}
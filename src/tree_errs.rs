@@ -1,5 +1,5 @@
-use std::error::Error;
-use std::fmt::{Display, Formatter, Result};
+use core::error::Error;
+use core::fmt::{Display, Formatter, Result};
 
 #[derive(Debug, Clone)]
 pub struct NodeNotFoundErr;
@@ -13,3 +13,101 @@ impl Display for NodeNotFoundErr {
 }
 
 impl Error for NodeNotFoundErr {}
+
+#[derive(Debug, Clone)]
+pub struct InvalidAlphaErr;
+
+impl Display for InvalidAlphaErr {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "Scapegoat alpha must be at least 0.5 and less than 1.0")
+    }
+}
+
+impl Error for InvalidAlphaErr {}
+
+#[derive(Debug, Clone)]
+pub struct InvalidProbabilityErr;
+
+impl Display for InvalidProbabilityErr {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "Access probabilities must be finite and non-negative")
+    }
+}
+
+impl Error for InvalidProbabilityErr {}
+
+#[derive(Debug, Clone)]
+pub struct InvalidPointErr;
+
+impl Display for InvalidPointErr {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "Point coordinates must be finite")
+    }
+}
+
+impl Error for InvalidPointErr {}
+
+#[derive(Debug, Clone)]
+pub struct PositionOutOfBoundsErr;
+
+impl Display for PositionOutOfBoundsErr {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "The position is past the end of the sequence")
+    }
+}
+
+impl Error for PositionOutOfBoundsErr {}
+
+// Reasons a 2-3-4 tree can't be turned into a red-black tree
+#[derive(Debug, Clone, PartialEq)]
+pub enum MalformedTreeErr {
+    WrongKeyCount,
+    WrongChildCount,
+    UnevenLeaves,
+    UnsortedKeys,
+}
+
+impl Display for MalformedTreeErr {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        match self {
+            MalformedTreeErr::WrongKeyCount => write!(f, "2-3-4 nodes must hold one to three keys"),
+            MalformedTreeErr::WrongChildCount => {
+                write!(
+                    f,
+                    "2-3-4 nodes must be leaves or have one more child than keys"
+                )
+            }
+            MalformedTreeErr::UnevenLeaves => {
+                write!(f, "Every 2-3-4 leaf must be at the same depth")
+            }
+            MalformedTreeErr::UnsortedKeys => write!(f, "2-3-4 keys must be in sorted order"),
+        }
+    }
+}
+
+impl Error for MalformedTreeErr {}
+
+#[derive(Debug, Clone)]
+pub struct CapacityExceededErr;
+
+impl Display for CapacityExceededErr {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(
+            f,
+            "The tree is already holding as many values as it has room for"
+        )
+    }
+}
+
+impl Error for CapacityExceededErr {}
+
+#[derive(Debug, Clone)]
+pub struct IncomparableKeyErr;
+
+impl Display for IncomparableKeyErr {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        write!(f, "Keys must all be comparable with one another")
+    }
+}
+
+impl Error for IncomparableKeyErr {}
@@ -0,0 +1,151 @@
+use super::tree_errs::MalformedTreeErr;
+
+use alloc::collections::VecDeque;
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::cmp::PartialOrd;
+use core::fmt::{Display, Formatter, Result};
+
+// Every red-black tree is a 2-3-4 tree in disguise: merging each black node with its red
+//  children gives a node holding one to three keys, and every leaf ends up at the same depth.
+//  Reasoning about insert and remove in these terms (split a full node, borrow from or merge
+//  with a sibling) is often much easier than following the rotations and recolorings.
+//
+// See `RedBlackTree::to_two_three_four` and `RedBlackTree::from_two_three_four`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TwoThreeFourTree<T> {
+    pub root: Option<TwoThreeFourNode<T>>,
+}
+
+// Holds one to three sorted keys. Leaves have no children, every other node has exactly
+//  one more child than it has keys.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TwoThreeFourNode<T> {
+    pub keys: Vec<T>,
+    pub children: Vec<TwoThreeFourNode<T>>,
+}
+
+impl<T> TwoThreeFourNode<T> {
+    // Shorthand for building a tree by hand
+    pub fn new(keys: Vec<T>, children: Vec<TwoThreeFourNode<T>>) -> Self {
+        TwoThreeFourNode { keys, children }
+    }
+
+    pub fn leaf(keys: Vec<T>) -> Self {
+        TwoThreeFourNode::new(keys, Vec::new())
+    }
+
+    // Recursive function that converts every key in the subtree
+    fn map<U>(self, f: &mut impl FnMut(T) -> U) -> TwoThreeFourNode<U> {
+        TwoThreeFourNode {
+            keys: self.keys.into_iter().map(&mut *f).collect(),
+            children: self
+                .children
+                .into_iter()
+                .map(|child| child.map(f))
+                .collect(),
+        }
+    }
+}
+
+impl<T> TwoThreeFourTree<T> {
+    // Converts every key in the tree, keeping its shape
+    pub fn map<U>(self, mut f: impl FnMut(T) -> U) -> TwoThreeFourTree<U> {
+        TwoThreeFourTree {
+            root: self.root.map(|root| root.map(&mut f)),
+        }
+    }
+
+    // Number of levels in the tree, which is the black height of the equivalent red-black tree
+    pub fn get_height(&self) -> usize {
+        let mut height = 0;
+        let mut cur_node_opt = self.root.as_ref();
+
+        while let Some(cur_node) = cur_node_opt {
+            height += 1;
+            cur_node_opt = cur_node.children.first();
+        }
+
+        height
+    }
+}
+
+impl<T: PartialOrd> TwoThreeFourTree<T> {
+    // Checks that every node holds one to three keys and the right number of children,
+    //  that every leaf sits at the same depth and that the keys are in sorted order
+    pub fn validate(&self) -> core::result::Result<(), MalformedTreeErr> {
+        let root = match &self.root {
+            Some(root) => root,
+            None => return Ok(()),
+        };
+
+        let mut prev_key = None;
+        Self::validate_node(root, self.get_height(), &mut prev_key)
+    }
+
+    // Recursive function that validates a subtree, with `depth` levels expected below
+    //  and including it, while walking its keys in order
+    fn validate_node<'a>(
+        node: &'a TwoThreeFourNode<T>,
+        depth: usize,
+        prev_key: &mut Option<&'a T>,
+    ) -> core::result::Result<(), MalformedTreeErr> {
+        if node.keys.is_empty() || node.keys.len() > 3 {
+            return Err(MalformedTreeErr::WrongKeyCount);
+        }
+
+        let is_leaf = node.children.is_empty();
+        if is_leaf != (depth == 1) {
+            return Err(MalformedTreeErr::UnevenLeaves);
+        }
+
+        if !is_leaf && node.children.len() != node.keys.len() + 1 {
+            return Err(MalformedTreeErr::WrongChildCount);
+        }
+
+        for (key_idx, key) in node.keys.iter().enumerate() {
+            if let Some(child) = node.children.get(key_idx) {
+                Self::validate_node(child, depth - 1, prev_key)?;
+            }
+
+            if prev_key.is_some_and(|prev_key| prev_key > key) {
+                return Err(MalformedTreeErr::UnsortedKeys);
+            }
+            *prev_key = Some(key);
+        }
+
+        match node.children.last() {
+            Some(last_child) => Self::validate_node(last_child, depth - 1, prev_key),
+            None => Ok(()),
+        }
+    }
+}
+
+// Prints one level per line, with every node's keys in brackets, e.g.
+//  [20]
+//  [5 10] [30]
+//  [1] [7] [15 17] [25] [35 40 45]
+impl<T: Display> Display for TwoThreeFourTree<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result {
+        let mut level: VecDeque<&TwoThreeFourNode<T>> = self.root.iter().collect();
+        let mut level_strs = vec![];
+
+        while !level.is_empty() {
+            let mut node_strs = vec![];
+
+            for _ in 0..level.len() {
+                let node = level.pop_front().expect("The level holds this many nodes");
+                let key_strs: Vec<String> = node.keys.iter().map(|key| key.to_string()).collect();
+
+                node_strs.push(format!("[{}]", key_strs.join(" ")));
+                level.extend(node.children.iter());
+            }
+
+            level_strs.push(node_strs.join(" "));
+        }
+
+        write!(f, "{}", level_strs.join("\n"))
+    }
+}
@@ -0,0 +1,695 @@
+use generational_arena::Index;
+use std::collections::HashMap;
+
+use super::base_tree::{BfsIter, DfsIter, InorderIter, InternalBinarySearchTree, Node};
+use super::search_tree::SearchTree;
+use super::tree_errs::NodeNotFoundErr;
+
+use std::cmp::{Ordering, PartialOrd};
+use std::fmt::Display;
+use std::mem::swap;
+
+// Neither subtree may hold more than DELTA times as many nodes as its sibling. When a
+//  rotation is needed, a single rotation is used if the inner grandchild holds fewer than
+//  GAMMA times as many nodes as the outer one, and a double rotation otherwise. These are
+//  the (3, 2) parameters proven correct by Hirai and Yamamoto, as used by Haskell's Data.Map.
+const DELTA: usize = 3;
+const GAMMA: usize = 2;
+
+// Weight-balanced trees (Adams' trees) balance on subtree sizes rather than colors or
+//  heights. Since every node knows its size anyway, `select` and `rank` come for free, and
+//  bulk set operations can be built from just splitting and joining trees.
+//
+// Like the other trees in this crate, duplicate values are kept. The set operations pair
+//  each value with at most one equal value from the other tree, the same way the lazy
+//  iterators in `set_ops` do.
+//
+// Most of the balancing works on detached subtrees: the functions below take and return
+//  subtree roots, and `link` is the only place that wires children to their parents.
+#[derive(Default)]
+pub struct WeightBalancedTree<T: PartialOrd + Display + Default> {
+    bst: InternalBinarySearchTree<T>,
+    sizes: HashMap<Index, usize>,
+}
+
+impl<T: PartialOrd + Display + Default> WeightBalancedTree<T> {
+    pub fn new() -> Self {
+        WeightBalancedTree {
+            bst: InternalBinarySearchTree::new(),
+            sizes: HashMap::new(),
+        }
+    }
+
+    pub fn get_size(&self) -> usize {
+        self.bst.nodes.len()
+    }
+
+    pub fn insert(&mut self, item: T) {
+        let new_leaf_idx = self.bst.nodes.insert(Node {
+            data: item,
+            left: None,
+            right: None,
+            parent: None,
+        });
+        self.sizes.insert(new_leaf_idx, 1);
+
+        let root_idx_opt = self.bst.root;
+        let new_root_idx = self.insert_into(root_idx_opt, new_leaf_idx);
+        self.set_root(Some(new_root_idx));
+    }
+
+    // Recursive function that inserts a detached node into a subtree, returning its new root
+    fn insert_into(&mut self, node_idx_opt: Option<Index>, new_node_idx: Index) -> Index {
+        let node_idx = match node_idx_opt {
+            Some(node_idx) => node_idx,
+            None => return self.link(None, new_node_idx, None),
+        };
+
+        let (left_idx_opt, right_idx_opt) = self.get_children(node_idx);
+        if self.bst.nodes[new_node_idx].data < self.bst.nodes[node_idx].data {
+            let left_idx = self.insert_into(left_idx_opt, new_node_idx);
+            self.balance(Some(left_idx), node_idx, right_idx_opt)
+        } else {
+            let right_idx = self.insert_into(right_idx_opt, new_node_idx);
+            self.balance(left_idx_opt, node_idx, Some(right_idx))
+        }
+    }
+
+    pub fn contains(&self, item: &T) -> bool {
+        self.bst.find_node_index(item).is_some()
+    }
+
+    pub fn remove(&mut self, item: &T) -> Result<(), NodeNotFoundErr> {
+        self.bst.find_node_index(item).ok_or(NodeNotFoundErr)?;
+
+        let root_idx_opt = self.bst.root;
+        let new_root_opt = self.remove_from(root_idx_opt, item);
+        self.set_root(new_root_opt);
+
+        Ok(())
+    }
+
+    // Recursive function that removes the value from a subtree known to hold it,
+    //  returning the subtree's new root
+    fn remove_from(&mut self, node_idx_opt: Option<Index>, item: &T) -> Option<Index> {
+        let node_idx = node_idx_opt.expect("The subtree holds the value being removed");
+        let (left_idx_opt, right_idx_opt) = self.get_children(node_idx);
+
+        if *item < self.bst.nodes[node_idx].data {
+            let left_idx_opt = self.remove_from(left_idx_opt, item);
+            Some(self.balance(left_idx_opt, node_idx, right_idx_opt))
+        } else if *item > self.bst.nodes[node_idx].data {
+            let right_idx_opt = self.remove_from(right_idx_opt, item);
+            Some(self.balance(left_idx_opt, node_idx, right_idx_opt))
+        } else {
+            self.free_node(node_idx);
+            self.merge(left_idx_opt, right_idx_opt)
+        }
+    }
+
+    // Returns the value with exactly `rank` smaller values in the tree
+    pub fn select(&self, rank: usize) -> Option<&T> {
+        let mut rank = rank;
+        let mut cur_node_opt = self.bst.root;
+
+        while let Some(cur_node_idx) = cur_node_opt {
+            let cur_node = &self.bst.nodes[cur_node_idx];
+            let left_size = self.get_node_size(cur_node.left);
+
+            if rank < left_size {
+                cur_node_opt = cur_node.left;
+            } else if rank > left_size {
+                rank -= left_size + 1;
+                cur_node_opt = cur_node.right;
+            } else {
+                return Some(&cur_node.data);
+            }
+        }
+
+        None
+    }
+
+    // Returns the number of values in the tree that are smaller than `item`
+    pub fn rank(&self, item: &T) -> usize {
+        let mut rank = 0;
+        let mut cur_node_opt = self.bst.root;
+
+        while let Some(cur_node_idx) = cur_node_opt {
+            let cur_node = &self.bst.nodes[cur_node_idx];
+
+            if cur_node.data < *item {
+                rank += self.get_node_size(cur_node.left) + 1;
+                cur_node_opt = cur_node.right;
+            } else {
+                cur_node_opt = cur_node.left;
+            }
+        }
+
+        rank
+    }
+
+    // Moves every value out of `other` and into this tree, dropping the values of `other`
+    //  that pair up with an equal value already in this tree. Each recursive step splits
+    //  both trees around one root, and the two halves are merged independently, for
+    //  O(m log(n / m + 1)) comparisons where m is the smaller size. The smaller tree's
+    //  nodes are moved across arenas beforehand, which is linear in m.
+    //
+    // The two halves never touch each other's nodes, which is what would let them be
+    //  merged on separate threads. They still run one after the other here: every node
+    //  lives in this tree's single arena, and handing the halves to different threads
+    //  would first mean moving them into arenas of their own, a linear cost.
+    pub fn union_with(&mut self, other: &mut Self) {
+        let (root_idx_opt, other_root_idx_opt) = self.absorb(other);
+        let new_root_opt = self.union_subtrees(root_idx_opt, other_root_idx_opt);
+        self.set_root(new_root_opt);
+    }
+
+    // Keeps only the values that pair up with an equal value in `other`, and empties
+    //  `other`. Runs sequentially for the same reason as `union_with`.
+    pub fn intersect_with(&mut self, other: &mut Self) {
+        let (root_idx_opt, other_root_idx_opt) = self.absorb(other);
+        let new_root_opt = self.intersect_subtrees(root_idx_opt, other_root_idx_opt);
+        self.set_root(new_root_opt);
+    }
+
+    // Removes every value that pairs up with an equal value in `other`, and empties
+    //  `other`. Runs sequentially for the same reason as `union_with`.
+    pub fn difference_with(&mut self, other: &mut Self) {
+        let (root_idx_opt, other_root_idx_opt) = self.absorb(other);
+        let new_root_opt = self.difference_subtrees(root_idx_opt, other_root_idx_opt);
+        self.set_root(new_root_opt);
+    }
+
+    // Moves the smaller tree's nodes into the larger tree's arena, and keeps that arena
+    //  in this tree. Returns the (this, other) subtree roots, both in this tree's arena.
+    fn absorb(&mut self, other: &mut Self) -> (Option<Index>, Option<Index>) {
+        let self_is_smaller = self.get_size() < other.get_size();
+        if self_is_smaller {
+            swap(&mut self.bst, &mut other.bst);
+            swap(&mut self.sizes, &mut other.sizes);
+        }
+
+        let kept_root_opt = self.bst.root.take();
+        let moved_root_opt = other.bst.root.take().map(|other_root_idx| {
+            let sizes = &mut self.sizes;
+            let other_sizes = &mut other.sizes;
+
+            other
+                .bst
+                .move_subtree(other_root_idx, &mut self.bst, |node_idx, new_node_idx| {
+                    let size = other_sizes
+                        .remove(&node_idx)
+                        .expect("Every node in the tree has a size");
+                    sizes.insert(new_node_idx, size);
+                })
+        });
+
+        if self_is_smaller {
+            (moved_root_opt, kept_root_opt)
+        } else {
+            (kept_root_opt, moved_root_opt)
+        }
+    }
+
+    // Recursive function that merges two detached subtrees. Where both hold a run of equal
+    //  values, the first subtree's run is kept, topped up from the second when it's longer.
+    fn union_subtrees(
+        &mut self,
+        first_idx_opt: Option<Index>,
+        second_idx_opt: Option<Index>,
+    ) -> Option<Index> {
+        let (first_idx, second_idx) = match (first_idx_opt, second_idx_opt) {
+            (Some(first_idx), Some(second_idx)) => (first_idx, second_idx),
+            (first_idx_opt, None) => return first_idx_opt,
+            (None, second_idx_opt) => return second_idx_opt,
+        };
+
+        let (first_lesser, first_equal, first_greater) = self.split_at_root(first_idx);
+        let (second_lesser, second_equal, second_greater) = self.split(Some(second_idx), first_idx);
+
+        let first_count = self.get_node_size(Some(first_equal));
+        let extra_equal = self.drop_smallest(second_equal, first_count);
+        let equal = self.merge(Some(first_equal), extra_equal);
+
+        let lesser = self.union_subtrees(first_lesser, second_lesser);
+        let greater = self.union_subtrees(first_greater, second_greater);
+        self.join_around(lesser, equal, greater)
+    }
+
+    // Recursive function that keeps the values of the first detached subtree that pair up
+    //  with an equal value in the second one
+    fn intersect_subtrees(
+        &mut self,
+        first_idx_opt: Option<Index>,
+        second_idx_opt: Option<Index>,
+    ) -> Option<Index> {
+        let (first_idx, second_idx) = match (first_idx_opt, second_idx_opt) {
+            (Some(first_idx), Some(second_idx)) => (first_idx, second_idx),
+            (first_idx_opt, second_idx_opt) => {
+                self.free_subtree(first_idx_opt);
+                self.free_subtree(second_idx_opt);
+                return None;
+            }
+        };
+
+        let (first_lesser, first_equal, first_greater) = self.split_at_root(first_idx);
+        let (second_lesser, second_equal, second_greater) = self.split(Some(second_idx), first_idx);
+
+        // Only as many equal values as the shorter run are paired up
+        let first_count = self.get_node_size(Some(first_equal));
+        let second_count = self.get_node_size(second_equal);
+        let equal = self.drop_smallest(Some(first_equal), first_count.saturating_sub(second_count));
+        self.free_subtree(second_equal);
+
+        let lesser = self.intersect_subtrees(first_lesser, second_lesser);
+        let greater = self.intersect_subtrees(first_greater, second_greater);
+        self.join_around(lesser, equal, greater)
+    }
+
+    // Recursive function that keeps the values of the first detached subtree that don't
+    //  pair up with an equal value in the second one
+    fn difference_subtrees(
+        &mut self,
+        first_idx_opt: Option<Index>,
+        second_idx_opt: Option<Index>,
+    ) -> Option<Index> {
+        let (first_idx, second_idx) = match (first_idx_opt, second_idx_opt) {
+            (Some(first_idx), Some(second_idx)) => (first_idx, second_idx),
+            (first_idx_opt, second_idx_opt) => {
+                self.free_subtree(second_idx_opt);
+                return first_idx_opt;
+            }
+        };
+
+        let (second_lesser, second_equal, second_greater) = self.split_at_root(second_idx);
+        let (first_lesser, first_equal, first_greater) = self.split(Some(first_idx), second_idx);
+
+        let second_count = self.get_node_size(Some(second_equal));
+        let equal = self.drop_smallest(first_equal, second_count);
+        self.free_subtree(Some(second_equal));
+
+        let lesser = self.difference_subtrees(first_lesser, second_lesser);
+        let greater = self.difference_subtrees(first_greater, second_greater);
+        self.join_around(lesser, equal, greater)
+    }
+
+    // Recursive function that splits a detached subtree around the value held by `key_idx`,
+    //  returning the lesser values, a subtree of every value equal to it and the greater
+    //  values. `key_idx` must not be part of the subtree, unless it's the subtree's root.
+    fn split(
+        &mut self,
+        node_idx_opt: Option<Index>,
+        key_idx: Index,
+    ) -> (Option<Index>, Option<Index>, Option<Index>) {
+        let node_idx = match node_idx_opt {
+            Some(node_idx) => node_idx,
+            None => return (None, None, None),
+        };
+
+        let (left_idx_opt, right_idx_opt) = self.get_children(node_idx);
+        let key = &self.bst.nodes[key_idx].data;
+        let data = &self.bst.nodes[node_idx].data;
+
+        if key < data {
+            let (lesser, equal, greater) = self.split(left_idx_opt, key_idx);
+            let greater = self.join(greater, node_idx, right_idx_opt);
+            (lesser, equal, Some(greater))
+        } else if key > data {
+            let (lesser, equal, greater) = self.split(right_idx_opt, key_idx);
+            let lesser = self.join(left_idx_opt, node_idx, lesser);
+            (Some(lesser), equal, greater)
+        } else {
+            let (lesser, equal, greater) = self.split_at_root(node_idx);
+            (lesser, Some(equal), greater)
+        }
+    }
+
+    // Splits a detached subtree around its own root's value, returning the lesser values, a
+    //  subtree of the root and every value equal to it, and the greater values. Equal values
+    //  can only sit at the largest end of the left subtree and the smallest end of the right
+    //  one, so those are only split apart when their edge value is equal to the root's.
+    fn split_at_root(&mut self, root_idx: Index) -> (Option<Index>, Index, Option<Index>) {
+        let (left_idx_opt, right_idx_opt) = self.get_children(root_idx);
+        let root_data = &self.bst.nodes[root_idx].data;
+
+        let left_has_equal = left_idx_opt.is_some_and(|left_idx| {
+            let max_data = &self.bst.nodes[self.bst.get_subtree_max(left_idx)].data;
+            max_data.partial_cmp(root_data) != Some(Ordering::Less)
+        });
+        let right_has_equal = right_idx_opt.is_some_and(|right_idx| {
+            let min_data = &self.bst.nodes[self.bst.get_subtree_min(right_idx)].data;
+            root_data.partial_cmp(min_data) != Some(Ordering::Less)
+        });
+
+        let (lesser, left_equal) = if left_has_equal {
+            let (lesser, left_equal, _) = self.split(left_idx_opt, root_idx);
+            (lesser, left_equal)
+        } else {
+            (left_idx_opt, None)
+        };
+        let (right_equal, greater) = if right_has_equal {
+            let (_, right_equal, greater) = self.split(right_idx_opt, root_idx);
+            (right_equal, greater)
+        } else {
+            (None, right_idx_opt)
+        };
+
+        (
+            lesser,
+            self.join(left_equal, root_idx, right_equal),
+            greater,
+        )
+    }
+
+    // Joins two detached subtrees around a detached middle subtree, where every value on the
+    //  left is no greater than the middle ones and every value on the right no smaller
+    fn join_around(
+        &mut self,
+        left_idx_opt: Option<Index>,
+        mid_idx_opt: Option<Index>,
+        right_idx_opt: Option<Index>,
+    ) -> Option<Index> {
+        match mid_idx_opt {
+            Some(mid_idx) => {
+                let (min_idx, rest_idx_opt) = self.detach_min(mid_idx);
+                let right_idx_opt = self.merge(rest_idx_opt, right_idx_opt);
+                Some(self.join(left_idx_opt, min_idx, right_idx_opt))
+            }
+            None => self.merge(left_idx_opt, right_idx_opt),
+        }
+    }
+
+    // Frees up to `count` of the smallest nodes of a detached subtree, returning the root of
+    //  what's left of it
+    fn drop_smallest(&mut self, node_idx_opt: Option<Index>, count: usize) -> Option<Index> {
+        let mut node_idx_opt = node_idx_opt;
+
+        for _ in 0..count {
+            let node_idx = match node_idx_opt {
+                Some(node_idx) => node_idx,
+                None => break,
+            };
+
+            let (min_idx, rest_idx_opt) = self.detach_min(node_idx);
+            self.free_node(min_idx);
+            node_idx_opt = rest_idx_opt;
+        }
+
+        node_idx_opt
+    }
+
+    // Recursive function that joins two detached subtrees of any size around a middle node,
+    //  where no value on the left is greater than the middle one and no value on the right
+    //  is smaller. Walks down the larger subtree until the sizes are close enough.
+    fn join(
+        &mut self,
+        left_idx_opt: Option<Index>,
+        mid_idx: Index,
+        right_idx_opt: Option<Index>,
+    ) -> Index {
+        // With one side empty, the middle node just becomes the other side's min or max
+        let (left_idx, right_idx) = match (left_idx_opt, right_idx_opt) {
+            (Some(left_idx), Some(right_idx)) => (left_idx, right_idx),
+            (left_idx_opt, None) => return self.insert_into(left_idx_opt, mid_idx),
+            (None, right_idx_opt) => return self.insert_into(right_idx_opt, mid_idx),
+        };
+
+        let (left_size, right_size) = (self.sizes[&left_idx], self.sizes[&right_idx]);
+        if DELTA * left_size < right_size {
+            let (inner_idx_opt, outer_idx_opt) = self.get_children(right_idx);
+            let inner_idx = self.join(left_idx_opt, mid_idx, inner_idx_opt);
+            self.balance(Some(inner_idx), right_idx, outer_idx_opt)
+        } else if DELTA * right_size < left_size {
+            let (outer_idx_opt, inner_idx_opt) = self.get_children(left_idx);
+            let inner_idx = self.join(inner_idx_opt, mid_idx, right_idx_opt);
+            self.balance(outer_idx_opt, left_idx, Some(inner_idx))
+        } else {
+            self.link(left_idx_opt, mid_idx, right_idx_opt)
+        }
+    }
+
+    // Recursive function that joins two detached subtrees, where no value on the left is
+    //  greater than any value on the right, without a middle node
+    fn merge(
+        &mut self,
+        left_idx_opt: Option<Index>,
+        right_idx_opt: Option<Index>,
+    ) -> Option<Index> {
+        let (left_idx, right_idx) = match (left_idx_opt, right_idx_opt) {
+            (Some(left_idx), Some(right_idx)) => (left_idx, right_idx),
+            (left_idx_opt, None) => return left_idx_opt,
+            (None, right_idx_opt) => return right_idx_opt,
+        };
+
+        let (left_size, right_size) = (self.sizes[&left_idx], self.sizes[&right_idx]);
+        if DELTA * left_size < right_size {
+            let (inner_idx_opt, outer_idx_opt) = self.get_children(right_idx);
+            let inner_idx_opt = self.merge(left_idx_opt, inner_idx_opt);
+            Some(self.balance(inner_idx_opt, right_idx, outer_idx_opt))
+        } else if DELTA * right_size < left_size {
+            let (outer_idx_opt, inner_idx_opt) = self.get_children(left_idx);
+            let inner_idx_opt = self.merge(inner_idx_opt, right_idx_opt);
+            Some(self.balance(outer_idx_opt, left_idx, inner_idx_opt))
+        } else if left_size > right_size {
+            // Close enough in size, so lift an edge node of the larger side to the root
+            let (max_idx, left_idx_opt) = self.detach_max(left_idx);
+            Some(self.balance(left_idx_opt, max_idx, right_idx_opt))
+        } else {
+            let (min_idx, right_idx_opt) = self.detach_min(right_idx);
+            Some(self.balance(left_idx_opt, min_idx, right_idx_opt))
+        }
+    }
+
+    // Recursive function that detaches the smallest node of a subtree, returning it along
+    //  with the subtree's new root
+    fn detach_min(&mut self, node_idx: Index) -> (Index, Option<Index>) {
+        let (left_idx_opt, right_idx_opt) = self.get_children(node_idx);
+
+        match left_idx_opt {
+            Some(left_idx) => {
+                let (min_idx, left_idx_opt) = self.detach_min(left_idx);
+                (
+                    min_idx,
+                    Some(self.balance(left_idx_opt, node_idx, right_idx_opt)),
+                )
+            }
+            None => (node_idx, right_idx_opt),
+        }
+    }
+
+    // Mirror of `detach_min`
+    fn detach_max(&mut self, node_idx: Index) -> (Index, Option<Index>) {
+        let (left_idx_opt, right_idx_opt) = self.get_children(node_idx);
+
+        match right_idx_opt {
+            Some(right_idx) => {
+                let (max_idx, right_idx_opt) = self.detach_max(right_idx);
+                (
+                    max_idx,
+                    Some(self.balance(left_idx_opt, node_idx, right_idx_opt)),
+                )
+            }
+            None => (node_idx, left_idx_opt),
+        }
+    }
+
+    // Links two subtrees under a middle node, where the subtrees were balanced before one
+    //  of them grew or shrank by a single node. At most one single or double rotation is
+    //  needed to restore the weight balance. Returns the root of the linked subtree.
+    fn balance(
+        &mut self,
+        left_idx_opt: Option<Index>,
+        mid_idx: Index,
+        right_idx_opt: Option<Index>,
+    ) -> Index {
+        let left_size = self.get_node_size(left_idx_opt);
+        let right_size = self.get_node_size(right_idx_opt);
+
+        if left_size + right_size <= 1 {
+            return self.link(left_idx_opt, mid_idx, right_idx_opt);
+        }
+
+        if right_size > DELTA * left_size {
+            let right_idx = right_idx_opt.expect("The heavier side holds nodes");
+            let (inner_idx_opt, outer_idx_opt) = self.get_children(right_idx);
+
+            if self.get_node_size(inner_idx_opt) < GAMMA * self.get_node_size(outer_idx_opt) {
+                // Single rotation: the right child becomes the root
+                let left_idx = self.link(left_idx_opt, mid_idx, inner_idx_opt);
+                return self.link(Some(left_idx), right_idx, outer_idx_opt);
+            }
+
+            // Double rotation: the right child's left child becomes the root
+            let inner_idx = inner_idx_opt.expect("The heavier inner grandchild holds nodes");
+            let (inner_left_opt, inner_right_opt) = self.get_children(inner_idx);
+            let left_idx = self.link(left_idx_opt, mid_idx, inner_left_opt);
+            let right_idx = self.link(inner_right_opt, right_idx, outer_idx_opt);
+            return self.link(Some(left_idx), inner_idx, Some(right_idx));
+        }
+
+        if left_size > DELTA * right_size {
+            let left_idx = left_idx_opt.expect("The heavier side holds nodes");
+            let (outer_idx_opt, inner_idx_opt) = self.get_children(left_idx);
+
+            if self.get_node_size(inner_idx_opt) < GAMMA * self.get_node_size(outer_idx_opt) {
+                let right_idx = self.link(inner_idx_opt, mid_idx, right_idx_opt);
+                return self.link(outer_idx_opt, left_idx, Some(right_idx));
+            }
+
+            let inner_idx = inner_idx_opt.expect("The heavier inner grandchild holds nodes");
+            let (inner_left_opt, inner_right_opt) = self.get_children(inner_idx);
+            let left_idx = self.link(outer_idx_opt, left_idx, inner_left_opt);
+            let right_idx = self.link(inner_right_opt, mid_idx, right_idx_opt);
+            return self.link(Some(left_idx), inner_idx, Some(right_idx));
+        }
+
+        self.link(left_idx_opt, mid_idx, right_idx_opt)
+    }
+
+    // Hangs two subtrees off of a node as-is and refreshes the node's size
+    fn link(
+        &mut self,
+        left_idx_opt: Option<Index>,
+        node_idx: Index,
+        right_idx_opt: Option<Index>,
+    ) -> Index {
+        for child_idx in left_idx_opt.iter().chain(right_idx_opt.iter()) {
+            self.bst.nodes[*child_idx].parent = Some(node_idx);
+        }
+
+        let node = &mut self.bst.nodes[node_idx];
+        node.left = left_idx_opt;
+        node.right = right_idx_opt;
+
+        let size = 1 + self.get_node_size(left_idx_opt) + self.get_node_size(right_idx_opt);
+        self.sizes.insert(node_idx, size);
+        node_idx
+    }
+
+    fn get_children(&self, node_idx: Index) -> (Option<Index>, Option<Index>) {
+        let node = &self.bst.nodes[node_idx];
+        (node.left, node.right)
+    }
+
+    fn get_node_size(&self, node_idx_opt: Option<Index>) -> usize {
+        // Terminating nodes hold nothing
+        node_idx_opt.map_or(0, |node_idx| self.sizes[&node_idx])
+    }
+
+    fn set_root(&mut self, root_idx_opt: Option<Index>) {
+        if let Some(root_idx) = root_idx_opt {
+            self.bst.nodes[root_idx].parent = None;
+        }
+        self.bst.root = root_idx_opt;
+    }
+
+    fn free_node(&mut self, node_idx: Index) {
+        self.sizes.remove(&node_idx);
+        let _ =
+            self.bst.nodes.remove(node_idx).expect(
+                "Exclusive access during mutation ensures that a node exists for every index",
+            );
+    }
+
+    fn free_subtree(&mut self, root_idx_opt: Option<Index>) {
+        let mut pending: Vec<Index> = root_idx_opt.into_iter().collect();
+
+        while let Some(node_idx) = pending.pop() {
+            let (left_idx_opt, right_idx_opt) = self.get_children(node_idx);
+            pending.extend(left_idx_opt.iter().chain(right_idx_opt.iter()));
+            self.free_node(node_idx);
+        }
+    }
+
+    // Checks every weight-balanced tree invariant: values in sorted order, parent links,
+    //  up-to-date sizes and the balance between siblings.
+    pub fn is_valid(&self) -> bool {
+        if self.sizes.len() != self.bst.nodes.len() {
+            return false;
+        }
+
+        if let Some(root_idx) = self.bst.root {
+            if self.bst.nodes[root_idx].parent.is_some() {
+                return false;
+            }
+        }
+
+        let values: Vec<&T> = self.bst.iter().collect();
+        let sizes_valid = self.bst.nodes.iter().all(|(node_idx, node)| {
+            let left_size = self.get_node_size(node.left);
+            let right_size = self.get_node_size(node.right);
+
+            let links_valid = node
+                .left
+                .iter()
+                .chain(node.right.iter())
+                .all(|child_idx| self.bst.nodes[*child_idx].parent == Some(node_idx));
+            let balanced = left_size + right_size <= 1
+                || (left_size <= DELTA * right_size && right_size <= DELTA * left_size);
+
+            links_valid && balanced && self.sizes[&node_idx] == 1 + left_size + right_size
+        });
+
+        sizes_valid
+            && values.len() == self.bst.nodes.len()
+            && values.windows(2).all(|pair| pair[0] <= pair[1])
+    }
+
+    // Create a new iterator w/ a stack for DFS taversal
+    pub fn dfs_iter(&self) -> DfsIter<'_, T> {
+        self.bst.dfs_iter()
+    }
+
+    // Create a new iterator w/ a queue for BFS traversal
+    pub fn bfs_iter(&self) -> BfsIter<'_, T> {
+        self.bst.bfs_iter()
+    }
+
+    // Create a new iterator that visits values in sorted order
+    pub fn iter(&self) -> InorderIter<'_, T> {
+        self.bst.iter()
+    }
+}
+
+impl<T: PartialOrd + Display + Default> SearchTree<T> for WeightBalancedTree<T> {
+    type Iter<'a>
+        = InorderIter<'a, T>
+    where
+        T: 'a;
+
+    fn insert(&mut self, item: T) {
+        WeightBalancedTree::insert(self, item)
+    }
+
+    fn remove(&mut self, item: &T) -> Result<(), NodeNotFoundErr> {
+        WeightBalancedTree::remove(self, item)
+    }
+
+    fn contains(&self, item: &T) -> bool {
+        WeightBalancedTree::contains(self, item)
+    }
+
+    fn len(&self) -> usize {
+        self.get_size()
+    }
+
+    fn iter(&self) -> Self::Iter<'_> {
+        self.bst.iter()
+    }
+
+    fn min(&self) -> Option<&T> {
+        self.bst.get_min()
+    }
+
+    fn max(&self) -> Option<&T> {
+        self.bst.get_max()
+    }
+
+    fn successor(&self, item: &T) -> Option<&T> {
+        self.bst.get_successor(item)
+    }
+
+    fn predecessor(&self, item: &T) -> Option<&T> {
+        self.bst.get_predecessor(item)
+    }
+}
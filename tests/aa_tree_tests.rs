@@ -0,0 +1,92 @@
+#![allow(clippy::bool_assert_comparison)]
+#![cfg(feature = "std")]
+
+mod common;
+
+use common::scrambled_values;
+use trees::aa_tree::AaTree;
+
+fn verify_tree_bfs(bst: &mut AaTree<i32>, expected_vals: Vec<i32>) {
+    assert_eq!(
+        bst.get_size(),
+        expected_vals.len(),
+        "Number of nodes in tree doesn't match the expected number"
+    );
+
+    let mut b_tree_iter = bst.bfs_iter();
+    for val in expected_vals {
+        let node = b_tree_iter.next();
+
+        assert_eq!(node, Some(&val), "Nodes don't match");
+    }
+    assert_eq!(b_tree_iter.next(), None);
+}
+
+#[test]
+fn test_instantiation() {
+    let _b_tree: AaTree<i32> = AaTree::new();
+}
+
+#[test]
+fn skew_and_split() {
+    let mut b_tree = AaTree::new();
+    b_tree.insert(20);
+
+    // A left child on the same level gets skewed into a right child
+    b_tree.insert(10);
+    verify_tree_bfs(&mut b_tree, vec![10, 20]);
+
+    // Two right children in a row get split, lifting the middle one a level
+    b_tree.insert(30);
+    verify_tree_bfs(&mut b_tree, vec![20, 10, 30]);
+    assert_eq!(b_tree.is_valid(), true);
+}
+
+#[test]
+fn ascending_inserts() {
+    let mut b_tree = AaTree::new();
+    for val in 1..=7 {
+        b_tree.insert(val);
+        assert_eq!(b_tree.is_valid(), true);
+    }
+
+    verify_tree_bfs(&mut b_tree, vec![4, 2, 6, 1, 3, 5, 7]);
+}
+
+#[test]
+fn remove_keeps_tree_valid() {
+    let mut b_tree = AaTree::new();
+    for val in scrambled_values(200) {
+        b_tree.insert(val);
+    }
+
+    assert_eq!(b_tree.remove(&200).is_err(), true);
+    for val in scrambled_values(200).into_iter().filter(|val| val % 3 != 0) {
+        assert_eq!(b_tree.remove(&val).is_ok(), true);
+        assert_eq!(b_tree.is_valid(), true);
+    }
+
+    assert_eq!(
+        b_tree.iter().copied().collect::<Vec<_>>(),
+        (0..200).filter(|val| val % 3 == 0).collect::<Vec<_>>()
+    );
+
+    for val in (0..200).filter(|val| val % 3 == 0) {
+        assert_eq!(b_tree.remove(&val).is_ok(), true);
+    }
+    assert_eq!(b_tree.get_size(), 0);
+    assert_eq!(b_tree.is_valid(), true);
+}
+
+#[test]
+fn duplicate_values() {
+    let mut b_tree = AaTree::new();
+    for val in &[5, 3, 5, 8, 5, 3] {
+        b_tree.insert(*val);
+    }
+
+    assert_eq!(b_tree.remove(&5).is_ok(), true);
+    assert_eq!(b_tree.remove(&3).is_ok(), true);
+    assert_eq!(b_tree.is_valid(), true);
+    assert_eq!(b_tree.iter().copied().collect::<Vec<_>>(), vec![3, 5, 5, 8]);
+}
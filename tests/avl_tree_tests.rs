@@ -0,0 +1,95 @@
+#![allow(clippy::bool_assert_comparison)]
+#![cfg(feature = "std")]
+
+mod common;
+
+use common::scrambled_values;
+use trees::avl_tree::AvlTree;
+
+fn verify_tree_bfs(bst: &mut AvlTree<i32>, expected_vals: Vec<i32>) {
+    assert_eq!(
+        bst.get_size(),
+        expected_vals.len(),
+        "Number of nodes in tree doesn't match the expected number"
+    );
+
+    let mut b_tree_iter = bst.bfs_iter();
+    for val in expected_vals {
+        let node = b_tree_iter.next();
+
+        assert_eq!(node, Some(&val), "Nodes don't match");
+    }
+    assert_eq!(b_tree_iter.next(), None);
+}
+
+#[test]
+fn test_instantiation() {
+    let _b_tree: AvlTree<i32> = AvlTree::new();
+}
+
+#[test]
+fn ascending_inserts_stay_perfectly_balanced() {
+    let mut b_tree = AvlTree::new();
+    for val in 1..=7 {
+        b_tree.insert(val);
+    }
+
+    verify_tree_bfs(&mut b_tree, vec![4, 2, 6, 1, 3, 5, 7]);
+    assert_eq!(b_tree.get_height(), 3);
+}
+
+#[test]
+fn left_right_rotation() {
+    let mut b_tree = AvlTree::new();
+    b_tree.insert(30);
+    b_tree.insert(10);
+    b_tree.insert(20);
+
+    verify_tree_bfs(&mut b_tree, vec![20, 10, 30]);
+}
+
+#[test]
+fn right_left_rotation() {
+    let mut b_tree = AvlTree::new();
+    b_tree.insert(10);
+    b_tree.insert(30);
+    b_tree.insert(20);
+
+    verify_tree_bfs(&mut b_tree, vec![20, 10, 30]);
+}
+
+#[test]
+fn remove_rebalances() {
+    let mut b_tree = AvlTree::new();
+    for val in &[20, 10, 30, 5, 15, 25, 40, 3] {
+        b_tree.insert(*val);
+    }
+
+    // Removing from the right side leaves the left side two levels taller
+    assert_eq!(b_tree.remove(&25).is_ok(), true);
+    assert_eq!(b_tree.remove(&40).is_ok(), true);
+    assert_eq!(b_tree.is_valid(), true);
+    verify_tree_bfs(&mut b_tree, vec![10, 5, 20, 3, 15, 30]);
+
+    assert_eq!(b_tree.remove(&99).is_err(), true);
+}
+
+#[test]
+fn height_stays_logarithmic() {
+    let mut b_tree = AvlTree::new();
+    for val in 0..1023 {
+        b_tree.insert(val);
+    }
+
+    assert_eq!(b_tree.is_valid(), true);
+    assert_eq!(b_tree.get_height(), 10);
+
+    for val in scrambled_values(1023).into_iter().take(800) {
+        assert_eq!(b_tree.remove(&val).is_ok(), true);
+        assert_eq!(b_tree.is_valid(), true);
+    }
+
+    // AVL trees never exceed ~1.44 log2(n + 2)
+    assert_eq!(b_tree.get_size(), 223);
+    assert_eq!(b_tree.get_height() <= 11, true);
+}
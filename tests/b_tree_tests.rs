@@ -0,0 +1,120 @@
+#![allow(clippy::bool_assert_comparison)]
+#![cfg(feature = "alloc")]
+
+mod common;
+
+use common::scrambled_values;
+use trees::b_tree::BTree;
+#[cfg(feature = "std")]
+use trees::red_black_tree::RedBlackTree;
+
+use std::ops::Bound;
+
+fn values<const B: usize>(b_tree: &BTree<i32, B>) -> Vec<i32> {
+    b_tree.iter().copied().collect()
+}
+
+#[test]
+fn test_instantiation() {
+    let _b_tree: BTree<i32, 3> = BTree::new();
+    let _b_tree: BTree<i32, 64> = BTree::new();
+}
+
+#[test]
+fn inserts_split_nodes() {
+    let mut b_tree: BTree<i32, 3> = BTree::new();
+    for val in 0..100 {
+        b_tree.insert(val);
+        assert_eq!(b_tree.is_valid(), true);
+    }
+
+    assert_eq!(b_tree.get_size(), 100);
+    assert_eq!(values(&b_tree), (0..100).collect::<Vec<_>>());
+}
+
+#[test]
+fn remove_keeps_tree_valid() {
+    let mut b_tree: BTree<i32, 4> = BTree::new();
+    for val in scrambled_values(300) {
+        b_tree.insert(val);
+    }
+
+    assert_eq!(b_tree.remove(&300).is_err(), true);
+    for val in scrambled_values(300).into_iter().filter(|val| val % 3 != 0) {
+        assert_eq!(b_tree.remove(&val).is_ok(), true);
+        assert_eq!(b_tree.is_valid(), true);
+    }
+    assert_eq!(
+        values(&b_tree),
+        (0..300).filter(|val| val % 3 == 0).collect::<Vec<_>>()
+    );
+
+    for val in (0..300).filter(|val| val % 3 == 0) {
+        assert_eq!(b_tree.remove(&val).is_ok(), true);
+    }
+    assert_eq!(b_tree.get_size(), 0);
+    assert_eq!(b_tree.is_valid(), true);
+}
+
+#[test]
+fn duplicate_values() {
+    let mut b_tree: BTree<i32, 3> = BTree::new();
+    for val in &[5, 3, 5, 8, 5, 3, 5] {
+        b_tree.insert(*val);
+    }
+
+    assert_eq!(b_tree.range(5..=5).count(), 4);
+    assert_eq!(b_tree.remove(&5).is_ok(), true);
+    assert_eq!(b_tree.remove(&3).is_ok(), true);
+    assert_eq!(b_tree.is_valid(), true);
+    assert_eq!(values(&b_tree), vec![3, 5, 5, 5, 8]);
+}
+
+#[test]
+fn range() {
+    let mut b_tree: BTree<i32, 5> = BTree::new();
+    for val in scrambled_values(200) {
+        b_tree.insert(val);
+    }
+
+    assert_eq!(
+        b_tree.range(40..60).copied().collect::<Vec<_>>(),
+        (40..60).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        b_tree.range(190..).copied().collect::<Vec<_>>(),
+        (190..200).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        b_tree.range(..=3).copied().collect::<Vec<_>>(),
+        vec![0, 1, 2, 3]
+    );
+    assert_eq!(
+        b_tree
+            .range((Bound::Included(&60), Bound::Excluded(&40)))
+            .next(),
+        None
+    );
+    assert_eq!(b_tree.range(500..).next(), None);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn matches_red_black_tree() {
+    let mut b_tree: BTree<i32, 6> = BTree::new();
+    let mut rb_tree = RedBlackTree::new();
+
+    for (step, val) in scrambled_values(500).into_iter().enumerate() {
+        let val = val % 150;
+        if step % 4 == 3 {
+            assert_eq!(b_tree.remove(&val).is_ok(), rb_tree.remove(&val).is_ok());
+        } else {
+            b_tree.insert(val);
+            rb_tree.insert(val);
+        }
+
+        assert_eq!(b_tree.is_valid(), true);
+        assert_eq!(b_tree.get_size(), rb_tree.get_size());
+        assert_eq!(b_tree.iter().eq(rb_tree.iter()), true);
+    }
+}
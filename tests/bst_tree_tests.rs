@@ -1,3 +1,6 @@
+#![allow(clippy::bool_assert_comparison)]
+#![cfg(feature = "alloc")]
+
 use trees::binary_search_tree::BinarySearchTree;
 
 fn verify_tree_bfs(bst: &mut BinarySearchTree<i32>, expected_vals: Vec<i32>) {
@@ -171,3 +174,233 @@ fn remove_single_root() {
     assert_eq!(b_tree.get_size(), 0);
     assert_eq!(b_tree.bfs_iter().next(), None);
 }
+
+// Removing a root with a single child used to empty the whole tree, rather than
+//  promoting the child
+#[test]
+fn remove_root_with_single_child() {
+    let mut b_tree = BinarySearchTree::new();
+    b_tree.insert(55);
+    b_tree.insert(42);
+    b_tree.insert(44);
+
+    assert_eq!(b_tree.remove(&55).is_ok(), true);
+    assert_eq!(b_tree.get_size(), 2);
+    verify_tree_bfs(&mut b_tree, vec![42, 44]);
+
+    assert_eq!(b_tree.remove(&42).is_ok(), true);
+    assert_eq!(b_tree.get_size(), 1);
+    verify_tree_bfs(&mut b_tree, vec![44]);
+    assert_eq!(b_tree.contains(&44), true);
+}
+
+#[test]
+fn split_off() {
+    let mut b_tree = BinarySearchTree::new();
+    for val in &[55, 42, 44, 88, 66, 99, 43, 65, 97, 100] {
+        b_tree.insert(*val);
+    }
+
+    let mut split_tree = b_tree.split_off(&66);
+    assert_eq!(
+        b_tree.iter().copied().collect::<Vec<_>>(),
+        vec![42, 43, 44, 55, 65]
+    );
+    assert_eq!(
+        split_tree.iter().copied().collect::<Vec<_>>(),
+        vec![66, 88, 97, 99, 100]
+    );
+
+    // Splitting keeps the remaining shape intact
+    verify_tree_bfs(&mut b_tree, vec![55, 42, 65, 44, 43]);
+    verify_tree_bfs(&mut split_tree, vec![88, 66, 99, 97, 100]);
+}
+
+#[test]
+fn split_off_either_end() {
+    for (split_key, lesser_len) in [(43, 1), (97, 7), (0, 0), (101, 10)] {
+        let mut b_tree = BinarySearchTree::new();
+        for val in &[55, 42, 44, 88, 66, 99, 43, 65, 97, 100] {
+            b_tree.insert(*val);
+        }
+
+        let split_tree = b_tree.split_off(&split_key);
+        assert_eq!(b_tree.get_size(), lesser_len);
+        assert_eq!(split_tree.get_size(), 10 - lesser_len);
+        assert_eq!(b_tree.iter().all(|val| *val < split_key), true);
+        assert_eq!(split_tree.iter().all(|val| *val >= split_key), true);
+    }
+}
+
+#[test]
+fn append() {
+    let mut b_tree = BinarySearchTree::new();
+    let mut other = BinarySearchTree::new();
+    for val in &[5, 2, 8] {
+        b_tree.insert(*val);
+    }
+    for val in &[10, 9, 12] {
+        other.insert(*val);
+    }
+
+    // Non-overlapping trees are hung off the largest node
+    b_tree.append(&mut other);
+    assert_eq!(other.get_size(), 0);
+    verify_tree_bfs(&mut b_tree, vec![5, 2, 8, 10, 9, 12]);
+
+    // Overlapping trees get merged and rebuilt balanced
+    let mut overlapping = BinarySearchTree::new();
+    overlapping.insert(7);
+    overlapping.insert(3);
+    b_tree.append(&mut overlapping);
+    verify_tree_bfs(&mut b_tree, vec![7, 3, 9, 2, 5, 8, 10, 12]);
+}
+
+#[test]
+fn retain() {
+    let mut b_tree = BinarySearchTree::new();
+    for val in &[55, 42, 44, 88, 66, 99, 43, 65, 97, 100] {
+        b_tree.insert(*val);
+    }
+
+    b_tree.retain(|val| val % 2 == 0);
+    assert_eq!(
+        b_tree.iter().copied().collect::<Vec<_>>(),
+        vec![42, 44, 66, 88, 100]
+    );
+}
+
+#[test]
+fn drain_and_remove_range() {
+    let mut b_tree = BinarySearchTree::new();
+    for val in &[55, 42, 44, 88, 66, 99, 43, 65, 97, 100] {
+        b_tree.insert(*val);
+    }
+
+    assert_eq!(b_tree.drain_range(44..=88), vec![44, 55, 65, 66, 88]);
+    assert_eq!(b_tree.remove_range(..43), 1);
+    verify_tree_bfs(&mut b_tree, vec![97, 43, 99, 100]);
+
+    assert_eq!(b_tree.remove_range(..), 4);
+    assert_eq!(b_tree.get_size(), 0);
+}
+
+#[test]
+fn scapegoat_invalid_alpha() {
+    assert_eq!(
+        BinarySearchTree::<i32>::with_scapegoat_alpha(0.4).is_err(),
+        true
+    );
+    assert_eq!(
+        BinarySearchTree::<i32>::with_scapegoat_alpha(1.0).is_err(),
+        true
+    );
+    assert_eq!(
+        BinarySearchTree::<i32>::with_scapegoat_alpha(0.5).is_ok(),
+        true
+    );
+}
+
+#[test]
+fn scapegoat_sorted_inserts() {
+    let mut b_tree = BinarySearchTree::with_scapegoat_alpha(0.6).unwrap();
+    for val in 0..1000 {
+        b_tree.insert(val);
+    }
+
+    // log(1000) / log(1 / 0.6) is ~13.5, plus one level for the root
+    assert_eq!(b_tree.get_height() <= 14, true);
+    assert_eq!(
+        b_tree.iter().copied().collect::<Vec<_>>(),
+        (0..1000).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn scapegoat_remove_rebuilds() {
+    let mut b_tree = BinarySearchTree::with_scapegoat_alpha(0.5).unwrap();
+    for val in 0..15 {
+        b_tree.insert(val);
+    }
+
+    // Dropping below half of the largest size rebuilds the whole tree
+    for val in 0..8 {
+        b_tree.remove(&val).unwrap();
+    }
+    verify_tree_bfs(&mut b_tree, vec![11, 9, 13, 8, 10, 12, 14]);
+}
+
+#[test]
+fn scapegoat_append() {
+    let mut b_tree = BinarySearchTree::with_scapegoat_alpha(0.5).unwrap();
+    let mut other = BinarySearchTree::new();
+    for val in 0..7 {
+        b_tree.insert(val);
+        other.insert(val + 7);
+    }
+
+    // Hanging a plain tree off a scapegoat tree still honours alpha
+    b_tree.append(&mut other);
+    assert_eq!(b_tree.get_height(), 4);
+    assert_eq!(
+        b_tree.iter().copied().collect::<Vec<_>>(),
+        (0..14).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn rebalance() {
+    let mut b_tree = BinarySearchTree::new();
+    b_tree.rebalance();
+    assert_eq!(b_tree.get_size(), 0);
+
+    for val in 1..=10 {
+        b_tree.insert(val);
+    }
+    assert_eq!(b_tree.get_height(), 10);
+
+    b_tree.rebalance();
+    assert_eq!(b_tree.get_height(), 4);
+    verify_tree_bfs(&mut b_tree, vec![7, 4, 9, 2, 6, 8, 10, 1, 3, 5]);
+
+    b_tree.insert(11);
+    assert_eq!(b_tree.contains(&11), true);
+    assert_eq!(b_tree.remove(&4).is_ok(), true);
+    assert_eq!(
+        b_tree.iter().copied().collect::<Vec<_>>(),
+        vec![1, 2, 3, 5, 6, 7, 8, 9, 10, 11]
+    );
+}
+
+#[test]
+fn optimal_from_access_probabilities() {
+    let (mut b_tree, cost) =
+        BinarySearchTree::from_access_probabilities(vec![(2, 0.2), (3, 0.7), (1, 0.1)]).unwrap();
+
+    // The most likely value goes at the root, even though it's the largest
+    verify_tree_bfs(&mut b_tree, vec![3, 2, 1]);
+    assert_eq!((cost - 1.4).abs() < 1e-9, true);
+
+    // Uniform probabilities give a perfectly balanced tree
+    let uniform = (1..=7).map(|val| (val, 1.0 / 7.0)).collect();
+    let (mut b_tree, cost) = BinarySearchTree::from_access_probabilities(uniform).unwrap();
+    verify_tree_bfs(&mut b_tree, vec![4, 2, 6, 1, 3, 5, 7]);
+    assert_eq!((cost - 17.0 / 7.0).abs() < 1e-9, true);
+
+    // Built trees are regular trees from then on
+    b_tree.insert(8);
+    assert_eq!(b_tree.remove(&4).is_ok(), true);
+    assert_eq!(
+        b_tree.iter().copied().collect::<Vec<_>>(),
+        vec![1, 2, 3, 5, 6, 7, 8]
+    );
+
+    let (b_tree, cost) = BinarySearchTree::<i32>::from_access_probabilities(vec![]).unwrap();
+    assert_eq!(b_tree.get_size(), 0);
+    assert_eq!(cost, 0.0);
+
+    assert_eq!(
+        BinarySearchTree::from_access_probabilities(vec![(1, 0.5), (2, -0.5)]).is_err(),
+        true
+    );
+}
@@ -0,0 +1,93 @@
+#![allow(clippy::bool_assert_comparison)]
+#![cfg(feature = "std")]
+
+mod common;
+
+use common::scrambled_values;
+use std::ops::Bound;
+use trees::cartesian_tree::CartesianTree;
+
+fn verify_tree_bfs(tree: &CartesianTree<i32>, expected_vals: Vec<i32>) {
+    assert_eq!(
+        tree.get_size(),
+        expected_vals.len(),
+        "Number of nodes in tree doesn't match the expected number"
+    );
+
+    let mut tree_iter = tree.bfs_iter();
+    for val in expected_vals {
+        let node = tree_iter.next();
+
+        assert_eq!(node, Some(&val), "Nodes don't match");
+    }
+    assert_eq!(tree_iter.next(), None);
+}
+
+#[test]
+fn from_slice() {
+    let values = vec![9, 3, 7, 1, 8, 12, 10, 20, 15, 18, 5];
+    let tree = CartesianTree::from_slice(&values);
+
+    verify_tree_bfs(&tree, vec![1, 3, 5, 9, 7, 8, 10, 12, 15, 20, 18]);
+    assert_eq!(tree.iter().copied().collect::<Vec<_>>(), values);
+    assert_eq!(tree.min(), Some(&1));
+    assert_eq!(tree.get(5), Some(&12));
+    assert_eq!(tree.get(11), None);
+}
+
+#[test]
+fn range_min() {
+    let tree = CartesianTree::from_slice(&[9, 3, 7, 1, 8, 12, 10, 20, 15, 18, 5]);
+
+    assert_eq!(tree.range_min(..), Some(&1));
+    assert_eq!(tree.range_min(4..8), Some(&8));
+    assert_eq!(tree.range_min(5..=9), Some(&10));
+    assert_eq!(tree.range_min(7..=7), Some(&20));
+    assert_eq!(tree.range_min_position(1..3), Some(1));
+
+    // Empty or out of bounds ranges
+    assert_eq!(tree.range_min(4..4), None);
+    assert_eq!(tree.range_min(8..12), None);
+    assert_eq!(CartesianTree::<i32>::from_slice(&[]).range_min(..), None);
+}
+
+#[test]
+fn range_min_with_overflowing_bounds() {
+    let tree = CartesianTree::from_slice(&[9, 3, 7]);
+
+    assert_eq!(tree.range_min_position(0..=usize::MAX), None);
+    assert_eq!(
+        tree.range_min_position((Bound::Excluded(usize::MAX), Bound::Unbounded)),
+        None
+    );
+    assert_eq!(tree.range_min(0..=2), Some(&3));
+}
+
+#[test]
+fn range_min_matches_scan() {
+    let values: Vec<i32> = scrambled_values(120)
+        .into_iter()
+        .map(|val| val % 40)
+        .collect();
+    let tree = CartesianTree::from_slice(&values);
+
+    for start in 0..values.len() {
+        for end in start + 1..=values.len() {
+            let expected_pos = (start..end).min_by_key(|pos| (values[*pos], *pos)).unwrap();
+            assert_eq!(tree.range_min_position(start..end), Some(expected_pos));
+        }
+    }
+}
+
+#[test]
+fn sorted_slices() {
+    // Ascending values form a right spine, descending ones a left spine
+    let ascending: Vec<i32> = (0..64).collect();
+    let tree = CartesianTree::from_slice(&ascending);
+    assert_eq!(tree.range_min(10..50), Some(&10));
+
+    let descending: Vec<i32> = (0..64).rev().collect();
+    let tree = CartesianTree::from_slice(&descending);
+    assert_eq!(tree.range_min(10..50), Some(&14));
+    assert_eq!(tree.dfs_iter().copied().collect::<Vec<_>>(), ascending);
+}
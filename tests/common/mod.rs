@@ -0,0 +1,7 @@
+// Helpers shared by the integration tests. Not every test file uses all of them.
+#![allow(dead_code)]
+
+// Deterministic pseudo-random values, so that larger trees can be checked without a rand dep
+pub fn scrambled_values(count: i32) -> Vec<i32> {
+    (0..count).map(|i| (i * 7919) % count).collect()
+}
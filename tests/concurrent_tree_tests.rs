@@ -0,0 +1,230 @@
+#![allow(clippy::bool_assert_comparison)]
+#![cfg(feature = "std")]
+
+mod common;
+
+use common::scrambled_values;
+use std::thread;
+use trees::concurrent_tree::ConcurrentRedBlackTree;
+use trees::red_black_tree::RedBlackTree;
+
+const THREAD_COUNT: i32 = 8;
+
+#[test]
+fn single_thread() {
+    let tree = ConcurrentRedBlackTree::new();
+    for val in scrambled_values(100) {
+        tree.insert(val);
+    }
+
+    assert_eq!(tree.get_size(), 100);
+    assert_eq!(tree.contains(&42), true);
+    assert_eq!(tree.remove(&42).is_ok(), true);
+    assert_eq!(tree.remove(&42).is_err(), true);
+    assert_eq!(tree.contains(&42), false);
+    assert_eq!(tree.snapshot().iter().count(), 99);
+
+    let inner = tree.into_inner();
+    assert_eq!(inner.is_valid(), true);
+    assert_eq!(inner.get_size(), 99);
+}
+
+#[test]
+fn chunked_iter() {
+    let mut inner = RedBlackTree::new();
+    for val in [5, 1, 3, 3, 3, 2, 4, 3, 6].iter() {
+        inner.insert(*val);
+    }
+    let tree = ConcurrentRedBlackTree::from_tree(inner);
+
+    // Chunk boundaries land in the middle of the run of duplicates
+    for chunk_size in 1..=10 {
+        let values: Vec<i32> = tree.iter_chunked(chunk_size).collect();
+        assert_eq!(values, vec![1, 2, 3, 3, 3, 3, 4, 5, 6]);
+    }
+    assert_eq!(
+        tree.iter().collect::<Vec<_>>(),
+        tree.snapshot().iter().copied().collect::<Vec<_>>()
+    );
+    assert_eq!(ConcurrentRedBlackTree::<i32>::new().iter().next(), None);
+}
+
+#[test]
+fn chunked_iter_sees_later_writes() {
+    let tree = ConcurrentRedBlackTree::new();
+    for val in (0..10).map(|val| val * 10) {
+        tree.insert(val);
+    }
+
+    let mut iter = tree.iter_chunked(3);
+    assert_eq!(iter.next(), Some(0));
+
+    // Behind the iterator, so it's missed; ahead of it, so it's seen
+    tree.insert(5);
+    tree.insert(55);
+    tree.remove(&90).unwrap();
+
+    let rest: Vec<i32> = iter.collect();
+    assert_eq!(rest, vec![10, 20, 30, 40, 50, 55, 60, 70, 80]);
+}
+
+#[test]
+fn concurrent_inserts() {
+    let tree = ConcurrentRedBlackTree::new();
+
+    thread::scope(|scope| {
+        for thread_num in 0..THREAD_COUNT {
+            let tree = &tree;
+            scope.spawn(move || {
+                for val in scrambled_values(1000) {
+                    tree.insert(val * THREAD_COUNT + thread_num);
+                }
+            });
+        }
+    });
+
+    assert_eq!(tree.get_size(), 1000 * THREAD_COUNT as usize);
+    assert_eq!(tree.is_valid(), true);
+    assert_eq!(
+        tree.snapshot().iter().copied().collect::<Vec<_>>(),
+        (0..1000 * THREAD_COUNT).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn concurrent_readers_and_writers() {
+    // Even values are there from the start and never touched, while the writers insert
+    //  and remove odd values underneath the readers
+    let tree = ConcurrentRedBlackTree::new();
+    for val in scrambled_values(2000)
+        .into_iter()
+        .filter(|val| val % 2 == 0)
+    {
+        tree.insert(val);
+    }
+
+    thread::scope(|scope| {
+        for thread_num in 0..THREAD_COUNT / 2 {
+            let tree = &tree;
+            scope.spawn(move || {
+                let odd_vals: Vec<i32> = (0..2000)
+                    .filter(|val| val % 2 == 1 && (val / 2) % (THREAD_COUNT / 2) == thread_num)
+                    .collect();
+
+                for val in odd_vals.iter() {
+                    tree.insert(*val);
+                }
+                for val in odd_vals.iter().step_by(2) {
+                    tree.remove(val).unwrap();
+                }
+            });
+        }
+
+        for _ in 0..THREAD_COUNT / 2 {
+            let tree = &tree;
+            scope.spawn(move || {
+                for _ in 0..20 {
+                    let values: Vec<i32> = tree.iter_chunked(50).collect();
+                    assert!(values.windows(2).all(|pair| pair[0] < pair[1]));
+
+                    let even_vals: Vec<i32> =
+                        values.into_iter().filter(|val| val % 2 == 0).collect();
+                    assert_eq!(even_vals, (0..2000).step_by(2).collect::<Vec<_>>());
+
+                    assert_eq!(tree.contains(&1000), true);
+                }
+            });
+        }
+    });
+
+    assert_eq!(tree.is_valid(), true);
+    assert_eq!(tree.get_size(), 1500);
+
+    let odd_vals: Vec<i32> = tree
+        .snapshot()
+        .iter()
+        .copied()
+        .filter(|val| val % 2 == 1)
+        .collect();
+    assert_eq!(odd_vals.len(), 500);
+}
+
+#[test]
+fn snapshot_is_point_in_time() {
+    let tree = ConcurrentRedBlackTree::new();
+    for val in 0..10 {
+        tree.insert(val);
+    }
+
+    // Snapshots share the live tree rather than copying it
+    let snapshot = tree.snapshot();
+    assert_eq!(snapshot.ptr_eq(&tree.snapshot()), true);
+    tree.insert(10);
+    assert_eq!(snapshot.ptr_eq(&tree.snapshot()), false);
+    tree.remove(&0).unwrap();
+    let later_snapshot = tree.snapshot();
+    tree.remove(&5).unwrap();
+
+    assert_eq!(
+        snapshot.iter().copied().collect::<Vec<_>>(),
+        (0..10).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        later_snapshot.iter().copied().collect::<Vec<_>>(),
+        (1..=10).collect::<Vec<_>>()
+    );
+    assert_eq!(tree.contains(&5), false);
+
+    // Snapshots still alive don't stop us from taking the tree back out
+    let inner = tree.into_inner();
+    assert_eq!(inner.get_size(), 9);
+    assert_eq!(snapshot.get_size(), 10);
+}
+
+#[test]
+fn snapshots_while_writing() {
+    // Writers insert a value and then remove the one before it, so a consistent view
+    //  holds one value from each writer's range, or two neighbouring ones in between
+    let tree = ConcurrentRedBlackTree::new();
+
+    thread::scope(|scope| {
+        for thread_num in 0..THREAD_COUNT / 2 {
+            let tree = &tree;
+            scope.spawn(move || {
+                let base = thread_num * 10_000;
+                tree.insert(base);
+                for val in base + 1..base + 2000 {
+                    tree.insert(val);
+                    tree.remove(&(val - 1)).unwrap();
+                }
+            });
+        }
+
+        for _ in 0..THREAD_COUNT / 2 {
+            let tree = &tree;
+            scope.spawn(move || {
+                for _ in 0..200 {
+                    let snapshot = tree.snapshot();
+                    assert_eq!(snapshot.is_valid(), true);
+
+                    let values: Vec<i32> = snapshot.iter().copied().collect();
+                    for pair in values.windows(2) {
+                        let same_writer = pair[0] / 10_000 == pair[1] / 10_000;
+                        assert!(!same_writer || pair[0] + 1 == pair[1]);
+                    }
+                    for triple in values.windows(3) {
+                        assert!(triple[0] / 10_000 != triple[2] / 10_000);
+                    }
+                }
+            });
+        }
+    });
+
+    assert_eq!(tree.is_valid(), true);
+    assert_eq!(
+        tree.snapshot().iter().copied().collect::<Vec<_>>(),
+        (0..THREAD_COUNT / 2)
+            .map(|thread_num| thread_num * 10_000 + 1999)
+            .collect::<Vec<_>>()
+    );
+}
@@ -0,0 +1,213 @@
+#![allow(clippy::bool_assert_comparison)]
+#![cfg(feature = "alloc")]
+
+use trees::kd_tree::{KdTree, Point};
+
+fn verify_tree_bfs(tree: &KdTree<2>, expected_vals: Vec<[f64; 2]>) {
+    assert_eq!(
+        tree.get_size(),
+        expected_vals.len(),
+        "Number of nodes in tree doesn't match the expected number"
+    );
+
+    let mut tree_iter = tree.bfs_iter();
+    for val in expected_vals {
+        let node = tree_iter.next();
+
+        assert_eq!(node, Some(&Point(val)), "Nodes don't match");
+    }
+    assert_eq!(tree_iter.next(), None);
+}
+
+// Spreads points over a grid of `count` by `count` without any obvious order
+fn scrambled_points(count: i32) -> Vec<Point<2>> {
+    (0..count)
+        .flat_map(|i| (0..count).map(move |j| (i, j)))
+        .map(|(i, j)| {
+            Point([
+                ((i * 7919 + j * 104729) % count) as f64,
+                ((i * 6007 + j * 7919) % count) as f64 / 2.0,
+            ])
+        })
+        .collect()
+}
+
+#[test]
+fn insert() {
+    let mut tree = KdTree::new();
+    for point in [
+        [5.0, 4.0],
+        [2.0, 6.0],
+        [8.0, 1.0],
+        [3.0, 2.0],
+        [9.0, 7.0],
+        [5.0, 0.0],
+    ]
+    .iter()
+    {
+        tree.insert(Point(*point)).unwrap();
+    }
+
+    // Points equal to a split on its axis go to the right
+    verify_tree_bfs(
+        &tree,
+        vec![
+            [5.0, 4.0],
+            [2.0, 6.0],
+            [8.0, 1.0],
+            [3.0, 2.0],
+            [5.0, 0.0],
+            [9.0, 7.0],
+        ],
+    );
+    assert_eq!(tree.is_valid(), true);
+    assert_eq!(tree.contains(&Point([3.0, 2.0])), true);
+    assert_eq!(tree.contains(&Point([3.0, 4.0])), false);
+}
+
+#[test]
+fn invalid_points() {
+    let mut tree = KdTree::new();
+    assert!(tree.insert(Point([1.0, f64::NAN])).is_err());
+    assert!(tree.insert(Point([f64::INFINITY, 1.0])).is_err());
+    assert_eq!(tree.get_size(), 0);
+
+    assert!(KdTree::from_points(vec![Point([0.0, 0.0]), Point([f64::NAN, 0.0])]).is_err());
+}
+
+#[test]
+fn from_points() {
+    let points = scrambled_points(32);
+    let tree = KdTree::from_points(points.clone()).unwrap();
+
+    assert_eq!(tree.get_size(), points.len());
+    assert_eq!(tree.is_valid(), true);
+    assert!(points.iter().all(|point| tree.contains(point)));
+
+    // With no repeated coordinates, every median split is even
+    let points: Vec<Point<2>> = (0..1023)
+        .map(|i| Point([i as f64, ((i * 7919) % 1023) as f64]))
+        .collect();
+    let tree = KdTree::from_points(points).unwrap();
+    assert_eq!(tree.get_height(), 10);
+}
+
+#[test]
+fn from_points_with_duplicates() {
+    let points = vec![Point([1.0, 1.0]); 20];
+    let tree = KdTree::from_points(points).unwrap();
+
+    assert_eq!(tree.get_size(), 20);
+    assert_eq!(tree.is_valid(), true);
+    assert_eq!(tree.k_nearest(&Point([0.0, 0.0]), 5).len(), 5);
+}
+
+#[test]
+fn nearest() {
+    let tree = KdTree::from_points(vec![
+        Point([2.0, 3.0]),
+        Point([5.0, 4.0]),
+        Point([9.0, 6.0]),
+        Point([4.0, 7.0]),
+        Point([8.0, 1.0]),
+        Point([7.0, 2.0]),
+    ])
+    .unwrap();
+
+    assert_eq!(tree.nearest(&Point([9.0, 2.0])), Some(&Point([8.0, 1.0])));
+    assert_eq!(tree.nearest(&Point([3.0, 6.0])), Some(&Point([4.0, 7.0])));
+    assert_eq!(tree.nearest(&Point([5.0, 4.0])), Some(&Point([5.0, 4.0])));
+    assert_eq!(KdTree::<2>::new().nearest(&Point([0.0, 0.0])), None);
+}
+
+#[test]
+fn k_nearest_matches_scan() {
+    let points = scrambled_points(24);
+    let mut tree = KdTree::new();
+    for point in points.iter() {
+        tree.insert(*point).unwrap();
+    }
+
+    for target in [[0.0, 0.0], [11.3, 5.7], [23.5, 12.0], [-4.0, 30.0]].iter() {
+        let target = Point(*target);
+        let mut expected: Vec<f64> = points
+            .iter()
+            .map(|point| point.distance_squared(&target))
+            .collect();
+        expected.sort_by(|dist, other| dist.total_cmp(other));
+
+        let found: Vec<f64> = tree
+            .k_nearest(&target, 10)
+            .iter()
+            .map(|point| point.distance_squared(&target))
+            .collect();
+        assert_eq!(found, expected[..10].to_vec());
+    }
+
+    assert_eq!(tree.k_nearest(&Point([0.0, 0.0]), 0).len(), 0);
+    assert_eq!(tree.k_nearest(&Point([0.0, 0.0]), 1000).len(), points.len());
+}
+
+#[test]
+fn k_nearest_beyond_the_tree_size() {
+    let points = scrambled_points(24);
+    let tree = KdTree::from_points(points.clone()).unwrap();
+    let target = Point([3.0, 3.0]);
+
+    for count in [points.len() + 1, usize::MAX / 2, usize::MAX].iter() {
+        assert_eq!(tree.k_nearest(&target, *count).len(), points.len());
+    }
+    assert_eq!(KdTree::<2>::new().k_nearest(&target, usize::MAX).len(), 0);
+}
+
+#[test]
+fn within_box() {
+    let points = scrambled_points(20);
+    let tree = KdTree::from_points(points.clone()).unwrap();
+
+    let (min, max) = (Point([3.0, 2.5]), Point([9.0, 6.0]));
+    let mut found: Vec<Point<2>> = tree.within_box(&min, &max).into_iter().copied().collect();
+    let mut expected: Vec<Point<2>> = points
+        .into_iter()
+        .filter(|point| {
+            (0..2).all(|axis| min.0[axis] <= point.0[axis] && point.0[axis] <= max.0[axis])
+        })
+        .collect();
+
+    found.sort_by(|point, other| point.partial_cmp(other).unwrap());
+    expected.sort_by(|point, other| point.partial_cmp(other).unwrap());
+    assert_eq!(found.is_empty(), false);
+    assert_eq!(found, expected);
+
+    // A box with min above max is empty
+    assert_eq!(tree.within_box(&max, &min).len(), 0);
+}
+
+#[test]
+fn three_dimensions() {
+    let points: Vec<Point<3>> = (0..200)
+        .map(|i| {
+            Point([
+                (i * 37 % 200) as f64,
+                (i * 91 % 200) as f64,
+                (i * 13 % 200) as f64,
+            ])
+        })
+        .collect();
+    let tree = KdTree::from_points(points.clone()).unwrap();
+    assert_eq!(tree.is_valid(), true);
+
+    let target = Point([50.0, 120.0, 75.0]);
+    let expected = points
+        .iter()
+        .min_by(|point, other| {
+            point
+                .distance_squared(&target)
+                .total_cmp(&other.distance_squared(&target))
+        })
+        .unwrap();
+    assert_eq!(
+        tree.nearest(&target).unwrap().distance_squared(&target),
+        expected.distance_squared(&target)
+    );
+}
@@ -0,0 +1,110 @@
+#![allow(clippy::bool_assert_comparison)]
+#![cfg(feature = "std")]
+
+mod common;
+
+use common::scrambled_values;
+use trees::llrb_tree::LlrbTree;
+use trees::red_black_tree::RedBlackTree;
+
+fn verify_tree_bfs(bst: &mut LlrbTree<i32>, expected_vals: Vec<i32>) {
+    assert_eq!(
+        bst.get_size(),
+        expected_vals.len(),
+        "Number of nodes in tree doesn't match the expected number"
+    );
+
+    let mut b_tree_iter = bst.bfs_iter();
+    for val in expected_vals {
+        let node = b_tree_iter.next();
+
+        assert_eq!(node, Some(&val), "Nodes don't match");
+    }
+    assert_eq!(b_tree_iter.next(), None);
+}
+
+#[test]
+fn test_instantiation() {
+    let _b_tree: LlrbTree<i32> = LlrbTree::new();
+}
+
+#[test]
+fn ascending_inserts() {
+    let mut b_tree = LlrbTree::new();
+    for val in 1..=7 {
+        b_tree.insert(val);
+        assert_eq!(b_tree.is_valid(), true);
+    }
+
+    verify_tree_bfs(&mut b_tree, vec![4, 2, 6, 1, 3, 5, 7]);
+}
+
+#[test]
+fn red_links_lean_left() {
+    let mut b_tree = LlrbTree::new();
+    b_tree.insert(10);
+    b_tree.insert(20);
+
+    // 20 is black at the root, with 10 hanging off it as a red left child
+    verify_tree_bfs(&mut b_tree, vec![20, 10]);
+    assert_eq!(b_tree.is_valid(), true);
+}
+
+#[test]
+fn remove_keeps_tree_valid() {
+    let mut b_tree = LlrbTree::new();
+    for val in scrambled_values(200) {
+        b_tree.insert(val);
+    }
+
+    assert_eq!(b_tree.remove(&200).is_err(), true);
+    for val in scrambled_values(200).into_iter().filter(|val| val % 3 != 0) {
+        assert_eq!(b_tree.remove(&val).is_ok(), true);
+        assert_eq!(b_tree.is_valid(), true);
+    }
+
+    assert_eq!(
+        b_tree.iter().copied().collect::<Vec<_>>(),
+        (0..200).filter(|val| val % 3 == 0).collect::<Vec<_>>()
+    );
+
+    for val in (0..200).filter(|val| val % 3 == 0) {
+        assert_eq!(b_tree.remove(&val).is_ok(), true);
+    }
+    assert_eq!(b_tree.get_size(), 0);
+    assert_eq!(b_tree.is_valid(), true);
+}
+
+#[test]
+fn duplicate_values() {
+    let mut b_tree = LlrbTree::new();
+    for val in &[5, 3, 5, 8, 5, 3] {
+        b_tree.insert(*val);
+    }
+
+    assert_eq!(b_tree.remove(&5).is_ok(), true);
+    assert_eq!(b_tree.remove(&3).is_ok(), true);
+    assert_eq!(b_tree.is_valid(), true);
+    assert_eq!(b_tree.iter().copied().collect::<Vec<_>>(), vec![3, 5, 5, 8]);
+}
+
+#[test]
+fn matches_red_black_tree() {
+    let mut llrb_tree = LlrbTree::new();
+    let mut rb_tree = RedBlackTree::new();
+
+    // Interleave inserts and removals, including repeated values, and compare after each one
+    for (step, val) in scrambled_values(500).into_iter().enumerate() {
+        let val = val % 150;
+        if step % 4 == 3 {
+            assert_eq!(llrb_tree.remove(&val).is_ok(), rb_tree.remove(&val).is_ok());
+        } else {
+            llrb_tree.insert(val);
+            rb_tree.insert(val);
+        }
+
+        assert_eq!(llrb_tree.is_valid(), true);
+        assert_eq!(llrb_tree.get_size(), rb_tree.get_size());
+        assert_eq!(llrb_tree.iter().eq(rb_tree.iter()), true);
+    }
+}
@@ -0,0 +1,190 @@
+#![allow(clippy::bool_assert_comparison)]
+#![cfg(feature = "std")]
+
+mod common;
+
+use common::scrambled_values;
+use trees::node_store::{ArenaStore, ArrayStore, Node, NodeStore, SlabStore};
+use trees::red_black_tree::RedBlackTree;
+
+fn leaf(data: i32) -> Node<i32> {
+    Node {
+        data,
+        left: None,
+        right: None,
+        parent: None,
+    }
+}
+
+// Runs the same inserts and removes against a tree of each backend, which should all end
+//  up with the exact same shape
+fn build_tree<S: NodeStore<i32>>() -> RedBlackTree<i32, S> {
+    let mut tree = RedBlackTree::<i32, S>::default();
+    for val in scrambled_values(200) {
+        tree.insert(val);
+    }
+    for val in (0..200).filter(|val| val % 3 == 0) {
+        tree.remove(&val).unwrap();
+    }
+
+    tree
+}
+
+fn store_basics<S: NodeStore<i32>>() {
+    let mut store = S::default();
+    assert_eq!(store.is_empty(), true);
+
+    let first_idx = store.insert(leaf(1));
+    let second_idx = store.insert(leaf(2));
+    assert_eq!(store.len(), 2);
+    assert_eq!(store[first_idx].data, 1);
+
+    store[second_idx].parent = Some(first_idx);
+    assert_eq!(store[second_idx].parent, Some(first_idx));
+
+    if let (Some(first), Some(second)) = store.get2_mut(second_idx, first_idx) {
+        std::mem::swap(&mut first.data, &mut second.data);
+    }
+    assert_eq!(store[first_idx].data, 2);
+    assert_eq!(store[second_idx].data, 1);
+
+    let mut stored: Vec<i32> = store.iter().map(|(_, node)| node.data).collect();
+    stored.sort();
+    assert_eq!(stored, vec![1, 2]);
+
+    assert_eq!(store.remove(first_idx).map(|node| node.data), Some(2));
+    assert_eq!(store.remove(first_idx).is_none(), true);
+    assert_eq!(store.len(), 1);
+    assert_eq!(
+        store
+            .iter()
+            .map(|(node_idx, _)| node_idx)
+            .collect::<Vec<_>>(),
+        vec![second_idx]
+    );
+}
+
+#[test]
+fn arena_store() {
+    store_basics::<ArenaStore<i32>>();
+}
+
+#[test]
+fn slab_store() {
+    store_basics::<SlabStore<i32>>();
+}
+
+#[test]
+fn array_store() {
+    store_basics::<ArrayStore<i32, 4>>();
+}
+
+#[test]
+fn slab_store_reuses_slots() {
+    let mut store = SlabStore::new();
+    let node_idxs: Vec<_> = (0..4).map(|val| store.insert(leaf(val))).collect();
+
+    store.remove(node_idxs[1]).unwrap();
+    store.remove(node_idxs[2]).unwrap();
+
+    // Freed slots are reused last-freed first, and the stale handles now reach the new nodes
+    assert_eq!(store.insert(leaf(10)), node_idxs[2]);
+    assert_eq!(store.insert(leaf(11)), node_idxs[1]);
+    assert_eq!(store[node_idxs[1]].data, 11);
+    assert_eq!(store.len(), 4);
+}
+
+#[test]
+fn array_store_fills_up() {
+    let mut store: ArrayStore<i32, 3> = ArrayStore::new();
+    assert_eq!(store.capacity(), 3);
+
+    let node_idxs: Vec<_> = (0..3).map(|val| store.insert(leaf(val))).collect();
+    assert_eq!(store.is_full(), true);
+
+    store.remove(node_idxs[0]).unwrap();
+    assert_eq!(store.is_full(), false);
+    store.insert(leaf(3));
+    assert_eq!(store.is_full(), true);
+}
+
+#[test]
+fn array_backed_tree_fills_up() {
+    let mut tree = RedBlackTree::<i32, ArrayStore<i32, 8>>::default();
+    assert_eq!(tree.capacity(), 8);
+
+    for val in scrambled_values(8) {
+        assert_eq!(tree.is_full(), false);
+        tree.try_insert(val).unwrap();
+    }
+    assert_eq!(tree.is_full(), true);
+    assert!(tree.try_insert(8).is_err());
+    assert_eq!(tree.get_size(), 8);
+
+    tree.remove(&3).unwrap();
+    assert_eq!(tree.try_insert(8).is_ok(), true);
+    assert_eq!(
+        tree.iter().copied().collect::<Vec<_>>(),
+        vec![0, 1, 2, 4, 5, 6, 7, 8]
+    );
+    assert_eq!(tree.is_valid(), true);
+}
+
+#[test]
+#[should_panic(expected = "Can't store more than 2 nodes")]
+fn array_store_panics_when_full() {
+    let mut store: ArrayStore<i32, 2> = ArrayStore::new();
+    for val in 0..3 {
+        store.insert(leaf(val));
+    }
+}
+
+#[test]
+#[should_panic(expected = "No node is stored for this index")]
+fn slab_store_panics_on_removed_node() {
+    let mut store = SlabStore::new();
+    let node_idx = store.insert(leaf(0));
+    store.remove(node_idx).unwrap();
+
+    let _ = &store[node_idx];
+}
+
+#[test]
+fn red_black_tree_backends_match() {
+    let arena_tree = build_tree::<ArenaStore<i32>>();
+    let slab_tree = build_tree::<SlabStore<i32>>();
+    let array_tree = build_tree::<ArrayStore<i32, 200>>();
+
+    for tree_vals in [
+        slab_tree.bfs_iter().copied().collect::<Vec<_>>(),
+        array_tree.bfs_iter().copied().collect::<Vec<_>>(),
+    ] {
+        assert_eq!(
+            tree_vals,
+            arena_tree.bfs_iter().copied().collect::<Vec<_>>()
+        );
+    }
+
+    assert_eq!(slab_tree.is_valid(), true);
+    assert_eq!(array_tree.is_valid(), true);
+    assert_eq!(array_tree.get_size(), 133);
+}
+
+#[test]
+fn red_black_tree_split_and_append() {
+    let mut tree = build_tree::<SlabStore<i32>>();
+    let mut upper = tree.split_off(&100);
+
+    assert_eq!(tree.is_valid(), true);
+    assert_eq!(upper.is_valid(), true);
+    assert_eq!(tree.iter().all(|val| *val < 100), true);
+    assert_eq!(upper.iter().all(|val| *val >= 100), true);
+
+    tree.append(&mut upper);
+    assert_eq!(tree.is_valid(), true);
+    assert_eq!(upper.get_size(), 0);
+    assert_eq!(
+        tree.iter().copied().collect::<Vec<_>>(),
+        (0..200).filter(|val| val % 3 != 0).collect::<Vec<_>>()
+    );
+}
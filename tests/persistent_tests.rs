@@ -0,0 +1,144 @@
+#![allow(clippy::bool_assert_comparison)]
+#![cfg(feature = "std")]
+
+mod common;
+
+use common::scrambled_values;
+use trees::persistent::PersistentRedBlackTree;
+
+fn verify_tree(tree: &PersistentRedBlackTree<i32>, expected_vals: &[i32]) {
+    assert_eq!(
+        tree.get_size(),
+        expected_vals.len(),
+        "Number of nodes in tree doesn't match the expected number"
+    );
+    assert_eq!(tree.iter().copied().collect::<Vec<_>>(), expected_vals);
+    assert_eq!(tree.is_valid(), true);
+}
+
+#[test]
+fn insert_keeps_old_versions() {
+    let empty = PersistentRedBlackTree::new();
+    let one = empty.insert(5);
+    let two = one.insert(3);
+    let three = two.insert(8);
+
+    verify_tree(&empty, &[]);
+    verify_tree(&one, &[5]);
+    verify_tree(&two, &[3, 5]);
+    verify_tree(&three, &[3, 5, 8]);
+}
+
+#[test]
+fn remove_keeps_old_versions() {
+    let mut tree = PersistentRedBlackTree::new();
+    for val in 1..=10 {
+        tree = tree.insert(val);
+    }
+
+    let removed = tree.remove(&4).unwrap();
+    let removed_twice = removed.remove(&9).unwrap();
+
+    verify_tree(&tree, &(1..=10).collect::<Vec<_>>());
+    verify_tree(&removed, &[1, 2, 3, 5, 6, 7, 8, 9, 10]);
+    verify_tree(&removed_twice, &[1, 2, 3, 5, 6, 7, 8, 10]);
+    assert!(removed_twice.remove(&4).is_err());
+}
+
+#[test]
+fn clone_shares_the_tree() {
+    let mut tree = PersistentRedBlackTree::new();
+    for val in scrambled_values(100) {
+        tree = tree.insert(val);
+    }
+
+    let snapshot = tree.clone();
+    assert_eq!(snapshot.ptr_eq(&tree), true);
+
+    let changed = tree.insert(100);
+    assert_eq!(changed.ptr_eq(&tree), false);
+    assert_eq!(snapshot.ptr_eq(&tree), true);
+}
+
+#[test]
+fn duplicates() {
+    let tree = PersistentRedBlackTree::new()
+        .insert(2)
+        .insert(1)
+        .insert(2)
+        .insert(2);
+    verify_tree(&tree, &[1, 2, 2, 2]);
+
+    let tree = tree.remove(&2).unwrap();
+    verify_tree(&tree, &[1, 2, 2]);
+}
+
+#[test]
+fn incomparable_values_are_not_found() {
+    let tree = PersistentRedBlackTree::new().insert(1.0).insert(2.0);
+
+    assert_eq!(tree.contains(&f64::NAN), false);
+    assert_eq!(tree.remove(&f64::NAN).is_err(), true);
+    assert_eq!(tree.iter().copied().collect::<Vec<_>>(), vec![1.0, 2.0]);
+}
+
+#[test]
+fn history_of_versions() {
+    // Every version along the way has to stay intact, whatever happened after it
+    let values = scrambled_values(300);
+    let mut versions = vec![PersistentRedBlackTree::new()];
+    let mut expected = vec![vec![]];
+
+    for val in values.iter() {
+        let tree = versions.last().unwrap().insert(*val);
+        let mut vals = expected.last().unwrap().clone();
+        vals.push(*val);
+        vals.sort();
+
+        versions.push(tree);
+        expected.push(vals);
+    }
+
+    for val in values.iter().rev().step_by(2) {
+        let tree = versions.last().unwrap().remove(val).unwrap();
+        let mut vals: Vec<i32> = expected.last().unwrap().clone();
+        vals.retain(|kept| kept != val);
+
+        versions.push(tree);
+        expected.push(vals);
+    }
+
+    for (tree, vals) in versions.iter().zip(expected.iter()) {
+        verify_tree(tree, vals);
+    }
+}
+
+#[test]
+fn versions_across_threads() {
+    let mut tree = PersistentRedBlackTree::new();
+    for val in 0..1000 {
+        tree = tree.insert(val);
+    }
+
+    let handles: Vec<_> = (0..4)
+        .map(|thread_num| {
+            let tree = tree.clone();
+            std::thread::spawn(move || {
+                let mut tree = tree;
+                for val in (thread_num..1000).step_by(4) {
+                    tree = tree.remove(&val).unwrap();
+                }
+                tree
+            })
+        })
+        .collect();
+
+    for (thread_num, handle) in handles.into_iter().enumerate() {
+        let thread_tree = handle.join().unwrap();
+        assert_eq!(thread_tree.get_size(), 750);
+        assert_eq!(thread_tree.contains(&(thread_num as i32)), false);
+        assert_eq!(thread_tree.is_valid(), true);
+    }
+
+    verify_tree(&tree, &(0..1000).collect::<Vec<_>>());
+}
@@ -0,0 +1,643 @@
+#![allow(clippy::bool_assert_comparison)]
+#![cfg(feature = "alloc")]
+
+mod common;
+
+use common::scrambled_values;
+use trees::binary_search_tree::BinarySearchTree;
+use trees::raw::BinarySearchTree as RawBinarySearchTree;
+use trees::raw::RedBlackTree as RawRedBlackTree;
+#[cfg(feature = "std")]
+use trees::red_black_tree::RedBlackTree;
+use trees::two_three_four::{TwoThreeFourNode, TwoThreeFourTree};
+
+// Miri is far slower than native code, so the differential tests run fewer operations there
+const OP_COUNT: i32 = if cfg!(miri) { 300 } else { 20_000 };
+
+fn verify_tree(tree: &RawRedBlackTree<i32>, expected_vals: &[i32]) {
+    assert_eq!(
+        tree.get_size(),
+        expected_vals.len(),
+        "Number of nodes in tree doesn't match the expected number"
+    );
+    assert_eq!(tree.iter().copied().collect::<Vec<_>>(), expected_vals);
+    assert_eq!(tree.is_valid(), true);
+}
+
+// Compares the raw tree against the arena-based one, which is what it's meant to replace.
+//  Both run the same balancing, so they should hold the same values in the same shape.
+#[cfg(feature = "std")]
+fn verify_matches(tree: &RawRedBlackTree<i32>, reference: &RedBlackTree<i32>) {
+    assert_eq!(tree.get_size(), reference.get_size());
+    assert_eq!(tree.is_valid(), true);
+    assert_eq!(
+        tree.iter().collect::<Vec<_>>(),
+        reference.iter().collect::<Vec<_>>()
+    );
+    assert_eq!(tree.to_two_three_four(), reference.to_two_three_four());
+}
+
+// Same for the binary search trees, whose shape shows in the order of a BFS traversal
+fn verify_bst_matches(tree: &RawBinarySearchTree<i32>, reference: &BinarySearchTree<i32>) {
+    assert_eq!(tree.get_size(), reference.get_size());
+    assert_eq!(tree.is_valid(), true);
+    assert_eq!(
+        tree.bfs_iter().collect::<Vec<_>>(),
+        reference.bfs_iter().collect::<Vec<_>>()
+    );
+}
+
+fn tree_from(vals: &[i32]) -> RawRedBlackTree<i32> {
+    let mut tree = RawRedBlackTree::new();
+    for val in vals {
+        tree.insert(*val);
+    }
+    tree
+}
+
+// Small linear congruential generator, so the operation sequences are repeatable
+fn pseudo_random_values(count: i32, seed: u64) -> Vec<i32> {
+    let mut state = seed;
+    (0..count)
+        .map(|_| {
+            state = state
+                .wrapping_mul(6364136223846793005)
+                .wrapping_add(1442695040888963407);
+            (state >> 33) as i32
+        })
+        .collect()
+}
+
+#[test]
+fn insert_and_remove() {
+    let mut tree = RawRedBlackTree::new();
+    verify_tree(&tree, &[]);
+
+    for val in [5, 3, 8, 1, 4, 7, 9, 2, 6].iter() {
+        tree.insert(*val);
+    }
+    verify_tree(&tree, &[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+    assert_eq!(tree.remove(&5).is_ok(), true);
+    assert_eq!(tree.remove(&5).is_err(), true);
+    assert_eq!(tree.remove(&1).is_ok(), true);
+    assert_eq!(tree.remove(&9).is_ok(), true);
+    verify_tree(&tree, &[2, 3, 4, 6, 7, 8]);
+
+    for val in [2, 3, 4, 6, 7, 8].iter() {
+        tree.remove(val).unwrap();
+    }
+    verify_tree(&tree, &[]);
+    assert_eq!(tree.remove(&2).is_err(), true);
+}
+
+#[test]
+fn duplicates() {
+    let mut tree = RawRedBlackTree::new();
+    for val in [3, 1, 3, 2, 3, 1].iter() {
+        tree.insert(*val);
+    }
+    verify_tree(&tree, &[1, 1, 2, 3, 3, 3]);
+
+    tree.remove(&3).unwrap();
+    tree.remove(&1).unwrap();
+    verify_tree(&tree, &[1, 2, 3, 3]);
+}
+
+#[test]
+fn sequential_values_stay_balanced() {
+    let count = if cfg!(miri) { 256 } else { 4096 };
+    let mut tree = RawRedBlackTree::new();
+    for val in 0..count {
+        tree.insert(val);
+    }
+    verify_tree(&tree, &(0..count).collect::<Vec<_>>());
+
+    for val in (0..count).step_by(2) {
+        tree.remove(&val).unwrap();
+    }
+    verify_tree(&tree, &(1..count).step_by(2).collect::<Vec<_>>());
+}
+
+#[test]
+fn traversals() {
+    let mut tree = RawRedBlackTree::new();
+    for val in 1..=7 {
+        tree.insert(val);
+    }
+
+    // Inserting in order keeps 2 at the root and leans the tree right: 4 under 2, and 6
+    //  under 4
+    assert_eq!(
+        tree.bfs_iter().copied().collect::<Vec<_>>(),
+        vec![2, 1, 4, 3, 6, 5, 7]
+    );
+    assert_eq!(
+        tree.dfs_iter().copied().collect::<Vec<_>>(),
+        vec![2, 1, 4, 3, 6, 5, 7]
+    );
+    assert_eq!(RawRedBlackTree::<i32>::new().bfs_iter().next(), None);
+    assert_eq!(RawRedBlackTree::<i32>::new().dfs_iter().next(), None);
+}
+
+#[test]
+fn navigation() {
+    let mut tree = RawRedBlackTree::new();
+    for val in scrambled_values(50).into_iter().map(|val| val * 2) {
+        tree.insert(val);
+    }
+
+    assert_eq!(tree.get_min(), Some(&0));
+    assert_eq!(tree.get_max(), Some(&98));
+    assert_eq!(tree.get_successor(&10), Some(&12));
+    assert_eq!(tree.get_successor(&11), Some(&12));
+    assert_eq!(tree.get_successor(&98), None);
+    assert_eq!(tree.get_predecessor(&10), Some(&8));
+    assert_eq!(tree.get_predecessor(&0), None);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn matches_arena_tree() {
+    let mut tree = RawRedBlackTree::new();
+    let mut reference = RedBlackTree::new();
+
+    // Values are kept in a small range, so that plenty of removes hit duplicates
+    for (step, val) in pseudo_random_values(OP_COUNT, 42).into_iter().enumerate() {
+        let val = val % (OP_COUNT / 4);
+
+        if val % 3 == 0 {
+            assert_eq!(tree.remove(&val).is_ok(), reference.remove(&val).is_ok());
+        } else {
+            tree.insert(val);
+            reference.insert(val);
+        }
+
+        assert_eq!(tree.contains(&val), reference.contains(&val));
+        if step % 97 == 0 {
+            verify_matches(&tree, &reference);
+        }
+    }
+
+    verify_matches(&tree, &reference);
+    assert_eq!(tree.get_min(), reference.iter().next());
+    assert_eq!(tree.get_max(), reference.iter().last());
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn matches_arena_tree_while_draining() {
+    let values = pseudo_random_values(OP_COUNT / 2, 7);
+    let mut tree = RawRedBlackTree::new();
+    let mut reference = RedBlackTree::new();
+    for val in values.iter() {
+        tree.insert(*val);
+        reference.insert(*val);
+    }
+    verify_matches(&tree, &reference);
+
+    for (step, val) in values.iter().rev().enumerate() {
+        tree.remove(val).unwrap();
+        reference.remove(val).unwrap();
+
+        if step % 61 == 0 {
+            verify_matches(&tree, &reference);
+        }
+    }
+
+    verify_tree(&tree, &[]);
+}
+
+#[test]
+fn drops_owned_values() {
+    // Leaks and double frees show up under Miri, and as a wrong count here
+    let mut tree = RawRedBlackTree::new();
+    for val in scrambled_values(100) {
+        tree.insert(val.to_string());
+    }
+    for val in (0..100).step_by(3) {
+        tree.remove(&val.to_string()).unwrap();
+    }
+
+    assert_eq!(tree.get_size(), 66);
+    assert_eq!(tree.is_valid(), true);
+    drop(tree);
+}
+
+#[test]
+fn send_between_threads() {
+    let mut tree = RawRedBlackTree::new();
+    for val in scrambled_values(100) {
+        tree.insert(val);
+    }
+
+    let tree = std::thread::spawn(move || {
+        tree.remove(&50).unwrap();
+        tree
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(tree.get_size(), 99);
+    assert_eq!(tree.contains(&50), false);
+}
+
+#[test]
+fn retain_and_ranges() {
+    let mut tree = tree_from(&scrambled_values(100));
+
+    // Few removals are rebalanced one at a time, many rebuild the tree instead
+    tree.retain(|val| val % 25 != 0);
+    assert_eq!(tree.is_valid(), true);
+    tree.retain(|val| val % 2 == 0);
+    verify_tree(
+        &tree,
+        &(0..100)
+            .filter(|val| val % 2 == 0 && val % 25 != 0)
+            .collect::<Vec<_>>(),
+    );
+
+    assert_eq!(tree.drain_range(10..=14), vec![10, 12, 14]);
+    assert_eq!(tree.remove_range(60..), 20);
+    assert_eq!(tree.remove_range(200..300), 0);
+    verify_tree(
+        &tree,
+        &[
+            2, 4, 6, 8, 16, 18, 20, 22, 24, 26, 28, 30, 32, 34, 36, 38, 40, 42, 44, 46, 48, 52, 54,
+            56, 58,
+        ],
+    );
+
+    assert_eq!(tree.drain_range(..).len(), 25);
+    verify_tree(&tree, &[]);
+}
+
+#[test]
+fn split_off_and_append() {
+    let mut tree = tree_from(&scrambled_values(200));
+    let mut greater = tree.split_off(&150);
+    verify_tree(&tree, &(0..150).collect::<Vec<_>>());
+    verify_tree(&greater, &(150..200).collect::<Vec<_>>());
+
+    let mut lesser = tree.split_off(&0);
+    verify_tree(&tree, &[]);
+    verify_tree(&lesser, &(0..150).collect::<Vec<_>>());
+
+    // Trees that don't overlap are joined, and overlapping ones are merged
+    lesser.append(&mut greater);
+    verify_tree(&greater, &[]);
+    verify_tree(&lesser, &(0..200).collect::<Vec<_>>());
+
+    let mut evens = tree_from(&(0..50).map(|val| val * 2).collect::<Vec<_>>());
+    let mut odds = tree_from(&(0..50).map(|val| val * 2 + 1).collect::<Vec<_>>());
+    evens.append(&mut odds);
+    verify_tree(&odds, &[]);
+    verify_tree(&evens, &(0..100).collect::<Vec<_>>());
+
+    tree.append(&mut evens);
+    verify_tree(&evens, &[]);
+    verify_tree(&tree, &(0..100).collect::<Vec<_>>());
+}
+
+#[test]
+fn set_operations() {
+    let first = tree_from(&[9, 1, 5, 3, 7]);
+    let second = tree_from(&[4, 5, 6, 7, 8, 9, 10]);
+    let empty = RawRedBlackTree::new();
+
+    assert_eq!(
+        first.union(&second).copied().collect::<Vec<_>>(),
+        vec![1, 3, 4, 5, 6, 7, 8, 9, 10]
+    );
+    assert_eq!(
+        first.intersection(&second).copied().collect::<Vec<_>>(),
+        vec![5, 7, 9]
+    );
+    assert_eq!(
+        first.difference(&second).copied().collect::<Vec<_>>(),
+        vec![1, 3]
+    );
+    assert_eq!(
+        first
+            .symmetric_difference(&second)
+            .copied()
+            .collect::<Vec<_>>(),
+        vec![1, 3, 4, 6, 8, 10]
+    );
+
+    assert_eq!(tree_from(&[5, 7]).is_subset(&first), true);
+    assert_eq!(first.is_superset(&tree_from(&[5, 7])), true);
+    assert_eq!(first.is_subset(&second), false);
+    assert_eq!(empty.is_subset(&first), true);
+    assert_eq!(first.is_disjoint(&tree_from(&[2, 4])), true);
+    assert_eq!(first.is_disjoint(&second), false);
+}
+
+#[test]
+fn two_three_four_round_trip() {
+    let tree = tree_from(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+    assert_eq!(
+        tree.to_two_three_four().to_string(),
+        "[4]\n[2] [6 8]\n[1] [3] [5] [7] [9 10]"
+    );
+
+    let view = tree_from(&scrambled_values(200)).into_two_three_four();
+    let mut tree = RawRedBlackTree::from_two_three_four(view.clone()).unwrap();
+    verify_tree(&tree, &(0..200).collect::<Vec<_>>());
+    assert_eq!(tree.to_two_three_four().map(|val| *val), view);
+
+    // The rebuilt tree is still a working red-black tree
+    tree.insert(200);
+    tree.remove(&50).unwrap();
+    assert_eq!(tree.is_valid(), true);
+
+    let uneven_leaves = TwoThreeFourTree {
+        root: Some(TwoThreeFourNode::new(
+            vec![10],
+            vec![
+                TwoThreeFourNode::leaf(vec![5]),
+                TwoThreeFourNode::new(
+                    vec![15],
+                    vec![
+                        TwoThreeFourNode::leaf(vec![12]),
+                        TwoThreeFourNode::leaf(vec![17]),
+                    ],
+                ),
+            ],
+        )),
+    };
+    assert_eq!(
+        RawRedBlackTree::from_two_three_four(uneven_leaves).is_err(),
+        true
+    );
+}
+
+#[test]
+fn clone_is_independent() {
+    // Leaks and double frees show up under Miri
+    let mut tree = RawRedBlackTree::new();
+    for val in scrambled_values(100) {
+        tree.insert(val.to_string());
+    }
+
+    let mut cloned = tree.clone();
+    assert_eq!(cloned.is_valid(), true);
+    assert_eq!(cloned.to_two_three_four(), tree.to_two_three_four());
+
+    cloned.retain(|val| val.len() == 1);
+    tree.remove(&"5".to_string()).unwrap();
+    assert_eq!(cloned.get_size(), 10);
+    assert_eq!(tree.get_size(), 99);
+    assert_eq!(cloned.contains(&"5".to_string()), true);
+}
+
+#[test]
+fn display_lists_every_node() {
+    let tree = tree_from(&scrambled_values(20));
+    let printed = tree.to_string();
+
+    assert_eq!(printed.lines().count(), 20);
+    assert_eq!(printed.lines().next().unwrap().contains("C:Black"), true);
+    assert_eq!(RawRedBlackTree::<i32>::new().to_string(), "");
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn bulk_operations_match_arena_tree() {
+    let values = pseudo_random_values(OP_COUNT / 2, 11);
+    let mut tree = RawRedBlackTree::new();
+    let mut reference = RedBlackTree::new();
+    for val in values.iter() {
+        tree.insert(*val % OP_COUNT);
+        reference.insert(*val % OP_COUNT);
+    }
+    verify_matches(&tree, &reference);
+
+    tree.retain(|val| val % 50 != 0);
+    reference.retain(|val| val % 50 != 0);
+    verify_matches(&tree, &reference);
+
+    tree.retain(|val| val % 3 != 0);
+    reference.retain(|val| val % 3 != 0);
+    verify_matches(&tree, &reference);
+
+    let bounds = (OP_COUNT / 10, OP_COUNT / 5);
+    assert_eq!(
+        tree.drain_range(bounds.0..bounds.1),
+        reference.drain_range(bounds.0..bounds.1)
+    );
+    assert_eq!(
+        tree.remove_range(OP_COUNT / 2..),
+        reference.remove_range(OP_COUNT / 2..)
+    );
+    verify_matches(&tree, &reference);
+
+    for key in [OP_COUNT / 3, OP_COUNT / 8, 0] {
+        let mut greater = tree.split_off(&key);
+        let mut reference_greater = reference.split_off(&key);
+        verify_matches(&tree, &reference);
+        verify_matches(&greater, &reference_greater);
+
+        greater.append(&mut tree);
+        reference_greater.append(&mut reference);
+        verify_matches(&greater, &reference_greater);
+        verify_matches(&tree, &reference);
+        tree = greater;
+        reference = reference_greater;
+    }
+
+    let mut other = RawRedBlackTree::new();
+    let mut reference_other = RedBlackTree::new();
+    for val in pseudo_random_values(OP_COUNT / 4, 12) {
+        other.insert(val % OP_COUNT);
+        reference_other.insert(val % OP_COUNT);
+    }
+    tree.append(&mut other);
+    reference.append(&mut reference_other);
+    verify_matches(&tree, &reference);
+    verify_matches(&other, &reference_other);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn set_operations_match_arena_tree() {
+    let first_values = pseudo_random_values(OP_COUNT / 4, 21);
+    let second_values = pseudo_random_values(OP_COUNT / 4, 22);
+    let build = |values: &[i32]| {
+        let mut tree = RawRedBlackTree::new();
+        let mut reference = RedBlackTree::new();
+        for val in values {
+            tree.insert(val % 500);
+            reference.insert(val % 500);
+        }
+        (tree, reference)
+    };
+    let (first, reference_first) = build(&first_values);
+    let (second, reference_second) = build(&second_values);
+
+    assert_eq!(
+        first.union(&second).collect::<Vec<_>>(),
+        reference_first.union(&reference_second).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        first.intersection(&second).collect::<Vec<_>>(),
+        reference_first
+            .intersection(&reference_second)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        first.difference(&second).collect::<Vec<_>>(),
+        reference_first
+            .difference(&reference_second)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        first.symmetric_difference(&second).collect::<Vec<_>>(),
+        reference_first
+            .symmetric_difference(&reference_second)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        first.is_subset(&second),
+        reference_first.is_subset(&reference_second)
+    );
+    assert_eq!(
+        first.is_disjoint(&second),
+        reference_first.is_disjoint(&reference_second)
+    );
+}
+
+#[test]
+fn bst_matches_arena_tree() {
+    for scapegoat_alpha in [None, Some(0.6)] {
+        let (mut tree, mut reference) = match scapegoat_alpha {
+            Some(alpha) => (
+                RawBinarySearchTree::with_scapegoat_alpha(alpha).unwrap(),
+                BinarySearchTree::with_scapegoat_alpha(alpha).unwrap(),
+            ),
+            None => (RawBinarySearchTree::new(), BinarySearchTree::new()),
+        };
+
+        for (step, val) in pseudo_random_values(OP_COUNT, 31).into_iter().enumerate() {
+            let val = val % (OP_COUNT / 4);
+
+            if val % 3 == 0 {
+                assert_eq!(tree.remove(&val).is_ok(), reference.remove(&val).is_ok());
+            } else {
+                tree.insert(val);
+                reference.insert(val);
+            }
+
+            assert_eq!(tree.contains(&val), reference.contains(&val));
+            if step % 97 == 0 {
+                verify_bst_matches(&tree, &reference);
+            }
+        }
+
+        verify_bst_matches(&tree, &reference);
+        assert_eq!(tree.get_height(), reference.get_height());
+
+        tree.rebalance();
+        reference.rebalance();
+        verify_bst_matches(&tree, &reference);
+    }
+}
+
+#[test]
+fn bst_bulk_operations_match_arena_tree() {
+    for scapegoat_alpha in [None, Some(0.7)] {
+        let (mut tree, mut reference) = match scapegoat_alpha {
+            Some(alpha) => (
+                RawBinarySearchTree::with_scapegoat_alpha(alpha).unwrap(),
+                BinarySearchTree::with_scapegoat_alpha(alpha).unwrap(),
+            ),
+            None => (RawBinarySearchTree::new(), BinarySearchTree::new()),
+        };
+        for val in pseudo_random_values(OP_COUNT / 2, 41) {
+            tree.insert(val % OP_COUNT);
+            reference.insert(val % OP_COUNT);
+        }
+
+        tree.retain(|val| val % 3 != 0);
+        reference.retain(|val| val % 3 != 0);
+        verify_bst_matches(&tree, &reference);
+
+        let bounds = (OP_COUNT / 10, OP_COUNT / 5);
+        assert_eq!(
+            tree.drain_range(bounds.0..bounds.1),
+            reference.drain_range(bounds.0..bounds.1)
+        );
+        assert_eq!(
+            tree.remove_range(OP_COUNT / 2..),
+            reference.remove_range(OP_COUNT / 2..)
+        );
+        verify_bst_matches(&tree, &reference);
+
+        for key in [OP_COUNT / 3, OP_COUNT / 8, 0] {
+            let mut greater = tree.split_off(&key);
+            let mut reference_greater = reference.split_off(&key);
+            verify_bst_matches(&tree, &reference);
+            verify_bst_matches(&greater, &reference_greater);
+
+            greater.append(&mut tree);
+            reference_greater.append(&mut reference);
+            verify_bst_matches(&greater, &reference_greater);
+            verify_bst_matches(&tree, &reference);
+            tree = greater;
+            reference = reference_greater;
+        }
+
+        let mut other = RawBinarySearchTree::new();
+        let mut reference_other = BinarySearchTree::new();
+        for val in pseudo_random_values(OP_COUNT / 4, 42) {
+            other.insert(val % OP_COUNT);
+            reference_other.insert(val % OP_COUNT);
+        }
+        tree.append(&mut other);
+        reference.append(&mut reference_other);
+        verify_bst_matches(&tree, &reference);
+        verify_bst_matches(&other, &reference_other);
+    }
+}
+
+#[test]
+fn optimal_bst_matches_arena_tree() {
+    let items: Vec<(i32, f64)> = pseudo_random_values(64, 51)
+        .into_iter()
+        .enumerate()
+        .map(|(val, weight)| (val as i32, (weight % 100) as f64))
+        .collect();
+
+    let (tree, cost) = RawBinarySearchTree::from_access_probabilities(items.clone()).unwrap();
+    let (reference, reference_cost) = BinarySearchTree::from_access_probabilities(items).unwrap();
+    verify_bst_matches(&tree, &reference);
+    assert_eq!(cost, reference_cost);
+
+    assert_eq!(
+        RawBinarySearchTree::from_access_probabilities(vec![(1, -0.5)]).is_err(),
+        true
+    );
+    assert_eq!(
+        RawBinarySearchTree::<i32>::with_scapegoat_alpha(1.0).is_err(),
+        true
+    );
+}
+
+#[test]
+fn bst_drops_owned_values() {
+    // Leaks and double frees show up under Miri, and as a wrong count here
+    let mut tree = RawBinarySearchTree::with_scapegoat_alpha(0.6).unwrap();
+    for val in scrambled_values(100) {
+        tree.insert(val.to_string());
+    }
+    for val in (0..100).step_by(3) {
+        tree.remove(&val.to_string()).unwrap();
+    }
+    assert_eq!(tree.drain_range("5".to_string().."6".to_string()).len(), 8);
+
+    let greater = tree.split_off(&"5".to_string());
+    assert_eq!(tree.get_size() + greater.get_size(), 58);
+    assert_eq!(tree.is_valid(), true);
+    assert_eq!(greater.is_valid(), true);
+    drop(tree);
+}
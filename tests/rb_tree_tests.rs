@@ -1,4 +1,13 @@
+#![allow(clippy::bool_assert_comparison)]
+#![cfg(feature = "std")]
+
+mod common;
+
+use common::scrambled_values;
+use std::rc::Rc;
 use trees::red_black_tree::RedBlackTree;
+use trees::tree_errs::MalformedTreeErr;
+use trees::two_three_four::{TwoThreeFourNode, TwoThreeFourTree};
 
 fn verify_tree_bfs(bst: &mut RedBlackTree<i32>, expected_vals: Vec<i32>) {
     assert_eq!(
@@ -245,3 +254,455 @@ fn find_items() {
 //     assert_eq!(b_tree.get_size(), 0);
 //     assert_eq!(b_tree.bfs_iter().next(), None);
 // }
+
+#[test]
+fn remove_keeps_tree_valid() {
+    let mut b_tree = RedBlackTree::new();
+    for val in scrambled_values(200) {
+        b_tree.insert(val);
+    }
+
+    assert_eq!(b_tree.remove(&500).is_err(), true);
+
+    for val in (0..200).step_by(3) {
+        assert_eq!(b_tree.remove(&val).is_ok(), true);
+        assert_eq!(b_tree.is_valid(), true);
+        assert_eq!(b_tree.contains(&val), false);
+    }
+
+    let expected: Vec<i32> = (0..200).filter(|val| val % 3 != 0).collect();
+    assert_eq!(b_tree.iter().copied().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn remove_single_root_node() {
+    let mut b_tree = RedBlackTree::new();
+    b_tree.insert(55);
+
+    assert_eq!(b_tree.remove(&55).is_ok(), true);
+    assert_eq!(b_tree.get_size(), 0);
+    assert_eq!(b_tree.bfs_iter().next(), None);
+    assert_eq!(b_tree.is_valid(), true);
+}
+
+// Removes every value in turn from trees of every size up to 40, built both in sorted and
+//  scrambled order, which runs through every double black case (red sibling, black sibling
+//  with no red nephew, near red nephew, far red nephew) on both sides of the parent
+#[test]
+fn remove_each_value_from_small_trees() {
+    for size in 1..=40 {
+        for insert_order in [(0..size).collect::<Vec<_>>(), scrambled_values(size)] {
+            for removed_val in 0..size {
+                let mut b_tree = tree_from(&insert_order);
+
+                assert_eq!(b_tree.remove(&removed_val).is_ok(), true);
+                assert_eq!(b_tree.is_valid(), true);
+                assert_eq!(
+                    b_tree.iter().copied().collect::<Vec<_>>(),
+                    (0..size)
+                        .filter(|val| *val != removed_val)
+                        .collect::<Vec<_>>()
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn remove_in_sorted_order() {
+    let mut ascending_tree = tree_from(&scrambled_values(100));
+    let mut descending_tree = tree_from(&scrambled_values(100));
+
+    for val in 0..100 {
+        assert_eq!(ascending_tree.remove(&val).is_ok(), true);
+        assert_eq!(descending_tree.remove(&(99 - val)).is_ok(), true);
+        assert_eq!(ascending_tree.is_valid(), true);
+        assert_eq!(descending_tree.is_valid(), true);
+        assert_eq!(
+            ascending_tree.iter().next().copied(),
+            Some(val + 1).filter(|min| *min < 100)
+        );
+    }
+
+    assert_eq!(ascending_tree.get_size(), 0);
+    assert_eq!(descending_tree.get_size(), 0);
+}
+
+#[test]
+fn remove_duplicate_values() {
+    let mut b_tree = tree_from(&[5, 3, 5, 8, 5, 1]);
+
+    assert_eq!(b_tree.remove(&5).is_ok(), true);
+    assert_eq!(b_tree.is_valid(), true);
+    assert_eq!(
+        b_tree.iter().copied().collect::<Vec<_>>(),
+        vec![1, 3, 5, 5, 8]
+    );
+
+    assert_eq!(b_tree.remove(&5).is_ok(), true);
+    assert_eq!(b_tree.remove(&5).is_ok(), true);
+    assert_eq!(b_tree.remove(&5).is_err(), true);
+    assert_eq!(b_tree.iter().copied().collect::<Vec<_>>(), vec![1, 3, 8]);
+}
+
+#[test]
+fn iter_visits_values_in_order() {
+    let mut b_tree = RedBlackTree::new();
+    for val in scrambled_values(50) {
+        b_tree.insert(val);
+    }
+    b_tree.insert(25);
+
+    let mut expected: Vec<i32> = (0..50).collect();
+    expected.insert(25, 25);
+    assert_eq!(b_tree.iter().copied().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn split_off() {
+    for split_key in &[-1, 0, 1, 37, 64, 99, 100, 150] {
+        let mut b_tree = RedBlackTree::new();
+        for val in scrambled_values(100) {
+            b_tree.insert(val);
+        }
+
+        let split_tree = b_tree.split_off(split_key);
+
+        assert_eq!(b_tree.is_valid(), true);
+        assert_eq!(split_tree.is_valid(), true);
+        assert_eq!(
+            b_tree.iter().copied().collect::<Vec<_>>(),
+            (0..100).filter(|val| val < split_key).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            split_tree.iter().copied().collect::<Vec<_>>(),
+            (0..100).filter(|val| val >= split_key).collect::<Vec<_>>()
+        );
+    }
+}
+
+#[test]
+fn split_off_keeps_duplicates_together() {
+    let mut b_tree = RedBlackTree::new();
+    for val in &[5, 3, 5, 8, 5, 1, 5] {
+        b_tree.insert(*val);
+    }
+
+    let split_tree = b_tree.split_off(&5);
+    assert_eq!(b_tree.iter().copied().collect::<Vec<_>>(), vec![1, 3]);
+    assert_eq!(
+        split_tree.iter().copied().collect::<Vec<_>>(),
+        vec![5, 5, 5, 5, 8]
+    );
+    assert_eq!(split_tree.is_valid(), true);
+}
+
+#[test]
+fn append_non_overlapping_trees() {
+    let mut lesser = RedBlackTree::new();
+    let mut greater = RedBlackTree::new();
+    for val in 0..10 {
+        lesser.insert(val);
+    }
+    for val in 10..100 {
+        greater.insert(val);
+    }
+
+    lesser.append(&mut greater);
+    assert_eq!(lesser.is_valid(), true);
+    assert_eq!(greater.get_size(), 0);
+    assert_eq!(
+        lesser.iter().copied().collect::<Vec<_>>(),
+        (0..100).collect::<Vec<_>>()
+    );
+
+    let mut smaller = RedBlackTree::new();
+    smaller.insert(-2);
+    smaller.insert(-1);
+
+    lesser.append(&mut smaller);
+    assert_eq!(lesser.is_valid(), true);
+    assert_eq!(
+        lesser.iter().copied().collect::<Vec<_>>(),
+        (-2..100).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn append_overlapping_trees() {
+    let mut evens = RedBlackTree::new();
+    let mut odds = RedBlackTree::new();
+    for val in 0..50 {
+        evens.insert(val * 2);
+        odds.insert(val * 2 + 1);
+    }
+
+    evens.append(&mut odds);
+    assert_eq!(evens.is_valid(), true);
+    assert_eq!(odds.get_size(), 0);
+    assert_eq!(
+        evens.iter().copied().collect::<Vec<_>>(),
+        (0..100).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn split_off_then_append_round_trip() {
+    let mut b_tree = RedBlackTree::new();
+    for val in scrambled_values(300) {
+        b_tree.insert(val);
+    }
+
+    let mut split_tree = b_tree.split_off(&120);
+    b_tree.append(&mut split_tree);
+    assert_eq!(b_tree.is_valid(), true);
+    assert_eq!(
+        b_tree.iter().copied().collect::<Vec<_>>(),
+        (0..300).collect::<Vec<_>>()
+    );
+}
+
+fn tree_from(vals: &[i32]) -> RedBlackTree<i32> {
+    let mut b_tree = RedBlackTree::new();
+    for val in vals {
+        b_tree.insert(*val);
+    }
+    b_tree
+}
+
+#[test]
+fn set_operations() {
+    let first = tree_from(&[9, 1, 5, 3, 7]);
+    let second = tree_from(&[4, 5, 6, 7, 8, 9, 10]);
+
+    assert_eq!(
+        first.union(&second).copied().collect::<Vec<_>>(),
+        vec![1, 3, 4, 5, 6, 7, 8, 9, 10]
+    );
+    assert_eq!(
+        first.intersection(&second).copied().collect::<Vec<_>>(),
+        vec![5, 7, 9]
+    );
+    assert_eq!(
+        first.difference(&second).copied().collect::<Vec<_>>(),
+        vec![1, 3]
+    );
+    assert_eq!(
+        second.difference(&first).copied().collect::<Vec<_>>(),
+        vec![4, 6, 8, 10]
+    );
+    assert_eq!(
+        first
+            .symmetric_difference(&second)
+            .copied()
+            .collect::<Vec<_>>(),
+        vec![1, 3, 4, 6, 8, 10]
+    );
+}
+
+#[test]
+fn set_operations_with_empty_and_duplicate_values() {
+    let empty = RedBlackTree::new();
+    let dupes = tree_from(&[2, 2, 2, 3]);
+    let pair = tree_from(&[2, 2]);
+
+    assert_eq!(dupes.union(&empty).count(), 4);
+    assert_eq!(empty.intersection(&dupes).next(), None);
+    assert_eq!(
+        dupes.difference(&pair).copied().collect::<Vec<_>>(),
+        vec![2, 3]
+    );
+    assert_eq!(
+        dupes.intersection(&pair).copied().collect::<Vec<_>>(),
+        vec![2, 2]
+    );
+}
+
+#[test]
+fn subset_superset_and_disjoint() {
+    let small = tree_from(&[3, 5]);
+    let large = tree_from(&[1, 3, 5, 7]);
+    let other = tree_from(&[2, 4, 6]);
+    let empty = RedBlackTree::new();
+
+    assert_eq!(small.is_subset(&large), true);
+    assert_eq!(large.is_subset(&small), false);
+    assert_eq!(large.is_superset(&small), true);
+    assert_eq!(empty.is_subset(&small), true);
+    assert_eq!(large.is_disjoint(&other), true);
+    assert_eq!(large.is_disjoint(&small), false);
+    assert_eq!(empty.is_disjoint(&empty), true);
+}
+
+#[test]
+fn retain() {
+    let mut b_tree = tree_from(&scrambled_values(100));
+
+    // Few removals are rebalanced one at a time
+    b_tree.retain(|val| val % 25 != 0);
+    assert_eq!(b_tree.is_valid(), true);
+    assert_eq!(b_tree.get_size(), 96);
+
+    // Many removals rebuild the tree instead
+    b_tree.retain(|val| val % 2 == 0);
+    assert_eq!(b_tree.is_valid(), true);
+    assert_eq!(
+        b_tree.iter().copied().collect::<Vec<_>>(),
+        (0..100)
+            .filter(|val| val % 2 == 0 && val % 25 != 0)
+            .collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn drain_and_remove_range() {
+    let mut b_tree = tree_from(&scrambled_values(100));
+
+    assert_eq!(b_tree.drain_range(10..14), vec![10, 11, 12, 13]);
+    assert_eq!(b_tree.is_valid(), true);
+
+    assert_eq!(b_tree.drain_range(..=5), vec![0, 1, 2, 3, 4, 5]);
+    assert_eq!(b_tree.remove_range(50..), 50);
+    assert_eq!(b_tree.remove_range(200..300), 0);
+    assert_eq!(b_tree.is_valid(), true);
+    assert_eq!(
+        b_tree.iter().copied().collect::<Vec<_>>(),
+        (6..10).chain(14..50).collect::<Vec<_>>()
+    );
+
+    assert_eq!(b_tree.remove_range(..), 40);
+    assert_eq!(b_tree.get_size(), 0);
+    assert_eq!(b_tree.is_valid(), true);
+}
+
+#[test]
+fn remove_range_drops_values() {
+    let vals: Vec<Rc<i32>> = (0..100).map(Rc::new).collect();
+    let mut b_tree = RedBlackTree::new();
+    for val in &vals {
+        b_tree.insert(Rc::clone(val));
+    }
+
+    // A handful of removals rebalances as it goes, most of the tree gets rebuilt instead
+    assert_eq!(b_tree.remove_range(Rc::new(10)..Rc::new(13)), 3);
+    assert_eq!(b_tree.remove_range(Rc::new(20)..), 80);
+    assert_eq!(b_tree.is_valid(), true);
+
+    for (val, rc) in vals.iter().enumerate() {
+        let expected_count = if (10..13).contains(&val) || val >= 20 {
+            1
+        } else {
+            2
+        };
+        assert_eq!(Rc::strong_count(rc), expected_count);
+    }
+}
+
+#[test]
+fn two_three_four_view() {
+    let b_tree = tree_from(&[1, 2, 3, 4, 5, 6, 7, 8, 9, 10]);
+    let view = b_tree.to_two_three_four();
+
+    // 8 is red under 6, and 10 is red under 9
+    assert_eq!(view.get_height(), 3);
+    assert_eq!(view.to_string(), "[4]\n[2] [6 8]\n[1] [3] [5] [7] [9 10]");
+    assert_eq!(
+        RedBlackTree::<i32>::new().to_two_three_four().to_string(),
+        ""
+    );
+}
+
+#[test]
+fn two_three_four_round_trip() {
+    let b_tree = tree_from(&scrambled_values(200));
+    let view = b_tree.into_two_three_four();
+    assert_eq!(view.validate().is_ok(), true);
+
+    let mut b_tree = RedBlackTree::from_two_three_four(view).unwrap();
+    assert_eq!(b_tree.is_valid(), true);
+    assert_eq!(
+        b_tree.iter().copied().collect::<Vec<_>>(),
+        (0..200).collect::<Vec<_>>()
+    );
+
+    // The rebuilt tree is still a working red-black tree
+    b_tree.insert(200);
+    assert_eq!(b_tree.remove(&50).is_ok(), true);
+    assert_eq!(b_tree.is_valid(), true);
+}
+
+#[test]
+fn from_two_three_four() {
+    let view = TwoThreeFourTree {
+        root: Some(TwoThreeFourNode::new(
+            vec![10, 20],
+            vec![
+                TwoThreeFourNode::leaf(vec![5]),
+                TwoThreeFourNode::leaf(vec![12, 15, 17]),
+                TwoThreeFourNode::leaf(vec![25, 30]),
+            ],
+        )),
+    };
+
+    let mut b_tree = RedBlackTree::from_two_three_four(view.clone()).unwrap();
+    assert_eq!(b_tree.is_valid(), true);
+    verify_tree_bfs(&mut b_tree, vec![20, 10, 30, 5, 15, 25, 12, 17]);
+    assert_eq!(b_tree.to_two_three_four().map(|val| *val), view);
+}
+
+#[test]
+fn from_malformed_two_three_four() {
+    let too_many_keys = TwoThreeFourTree {
+        root: Some(TwoThreeFourNode::leaf(vec![1, 2, 3, 4])),
+    };
+    let uneven_leaves = TwoThreeFourTree {
+        root: Some(TwoThreeFourNode::new(
+            vec![10],
+            vec![
+                TwoThreeFourNode::leaf(vec![5]),
+                TwoThreeFourNode::new(
+                    vec![15],
+                    vec![
+                        TwoThreeFourNode::leaf(vec![12]),
+                        TwoThreeFourNode::leaf(vec![17]),
+                    ],
+                ),
+            ],
+        )),
+    };
+    let missing_child = TwoThreeFourTree {
+        root: Some(TwoThreeFourNode::new(
+            vec![10, 20],
+            vec![
+                TwoThreeFourNode::leaf(vec![5]),
+                TwoThreeFourNode::leaf(vec![15]),
+            ],
+        )),
+    };
+    let unsorted = TwoThreeFourTree {
+        root: Some(TwoThreeFourNode::new(
+            vec![10],
+            vec![
+                TwoThreeFourNode::leaf(vec![15]),
+                TwoThreeFourNode::leaf(vec![5]),
+            ],
+        )),
+    };
+
+    assert_eq!(
+        RedBlackTree::from_two_three_four(too_many_keys).err(),
+        Some(MalformedTreeErr::WrongKeyCount)
+    );
+    assert_eq!(
+        RedBlackTree::from_two_three_four(uneven_leaves).err(),
+        Some(MalformedTreeErr::UnevenLeaves)
+    );
+    assert_eq!(
+        RedBlackTree::from_two_three_four(missing_child).err(),
+        Some(MalformedTreeErr::WrongChildCount)
+    );
+    assert_eq!(
+        RedBlackTree::from_two_three_four(unsorted).err(),
+        Some(MalformedTreeErr::UnsortedKeys)
+    );
+}
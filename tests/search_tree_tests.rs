@@ -0,0 +1,157 @@
+#![allow(clippy::bool_assert_comparison)]
+#![cfg(feature = "alloc")]
+
+mod common;
+
+use common::scrambled_values;
+#[cfg(feature = "std")]
+use trees::aa_tree::AaTree;
+#[cfg(feature = "std")]
+use trees::avl_tree::AvlTree;
+use trees::b_tree::BTree;
+use trees::binary_search_tree::BinarySearchTree;
+#[cfg(feature = "std")]
+use trees::llrb_tree::LlrbTree;
+#[cfg(feature = "std")]
+use trees::node_store::{ArrayStore, SlabStore};
+#[cfg(feature = "std")]
+use trees::persistent::PersistentRedBlackTree;
+use trees::raw::BinarySearchTree as RawBinarySearchTree;
+use trees::raw::RedBlackTree as RawRedBlackTree;
+#[cfg(feature = "std")]
+use trees::red_black_tree::RedBlackTree;
+use trees::search_tree::SearchTree;
+use trees::splay_tree::SplayTree;
+use trees::static_tree::StaticRedBlackTree;
+#[cfg(feature = "std")]
+use trees::treap::Treap;
+#[cfg(feature = "std")]
+use trees::weight_balanced_tree::WeightBalancedTree;
+
+// Every test in this file is written against the SearchTree trait, and the macro at the
+//  bottom runs the whole suite once per tree type.
+
+fn insert_and_contains<S: SearchTree<i32> + Default>() {
+    let mut tree = S::default();
+    assert_eq!(tree.is_empty(), true);
+
+    for val in scrambled_values(100) {
+        tree.insert(val);
+    }
+
+    assert_eq!(tree.len(), 100);
+    assert_eq!(tree.is_empty(), false);
+    assert_eq!(tree.contains(&0), true);
+    assert_eq!(tree.contains(&99), true);
+    assert_eq!(tree.contains(&100), false);
+}
+
+fn remove<S: SearchTree<i32> + Default>() {
+    let mut tree = S::default();
+    for val in scrambled_values(100) {
+        tree.insert(val);
+    }
+
+    assert_eq!(tree.remove(&100).is_err(), true);
+    for val in (0..100).filter(|val| val % 3 == 0) {
+        assert_eq!(tree.remove(&val).is_ok(), true);
+        assert_eq!(tree.contains(&val), false);
+    }
+
+    assert_eq!(tree.len(), 66);
+    assert_eq!(
+        tree.iter().copied().collect::<Vec<_>>(),
+        (0..100).filter(|val| val % 3 != 0).collect::<Vec<_>>()
+    );
+}
+
+fn iter_in_order<S: SearchTree<i32> + Default>() {
+    let mut tree = S::default();
+    assert_eq!(tree.iter().next(), None);
+
+    for val in scrambled_values(64) {
+        tree.insert(val);
+    }
+
+    assert_eq!(
+        tree.iter().copied().collect::<Vec<_>>(),
+        (0..64).collect::<Vec<_>>()
+    );
+}
+
+fn navigation<S: SearchTree<i32> + Default>() {
+    let mut tree = S::default();
+    assert_eq!(tree.min(), None);
+    assert_eq!(tree.max(), None);
+    assert_eq!(tree.successor(&5), None);
+
+    for val in &[40, 10, 30, 20, 50] {
+        tree.insert(*val);
+    }
+
+    assert_eq!(tree.min(), Some(&10));
+    assert_eq!(tree.max(), Some(&50));
+    assert_eq!(tree.successor(&30), Some(&40));
+    assert_eq!(tree.successor(&35), Some(&40));
+    assert_eq!(tree.successor(&50), None);
+    assert_eq!(tree.predecessor(&30), Some(&20));
+    assert_eq!(tree.predecessor(&5), None);
+    assert_eq!(tree.predecessor(&100), Some(&50));
+}
+
+macro_rules! search_tree_tests {
+    ($mod_name:ident, $tree:ty) => {
+        mod $mod_name {
+            #[test]
+            fn insert_and_contains() {
+                super::insert_and_contains::<$tree>();
+            }
+
+            #[test]
+            fn remove() {
+                super::remove::<$tree>();
+            }
+
+            #[test]
+            fn iter_in_order() {
+                super::iter_in_order::<$tree>();
+            }
+
+            #[test]
+            fn navigation() {
+                super::navigation::<$tree>();
+            }
+        }
+    };
+}
+
+search_tree_tests!(binary_search_tree, super::BinarySearchTree<i32>);
+#[cfg(feature = "std")]
+search_tree_tests!(red_black_tree, super::RedBlackTree<i32>);
+#[cfg(feature = "std")]
+search_tree_tests!(
+    red_black_tree_slab,
+    super::RedBlackTree<i32, super::SlabStore<i32>>
+);
+#[cfg(feature = "std")]
+search_tree_tests!(
+    red_black_tree_array,
+    super::RedBlackTree<i32, super::ArrayStore<i32, 128>>
+);
+#[cfg(feature = "std")]
+search_tree_tests!(avl_tree, super::AvlTree<i32>);
+#[cfg(feature = "std")]
+search_tree_tests!(treap, super::Treap<i32>);
+search_tree_tests!(splay_tree, super::SplayTree<i32>);
+#[cfg(feature = "std")]
+search_tree_tests!(llrb_tree, super::LlrbTree<i32>);
+#[cfg(feature = "std")]
+search_tree_tests!(aa_tree, super::AaTree<i32>);
+#[cfg(feature = "std")]
+search_tree_tests!(weight_balanced_tree, super::WeightBalancedTree<i32>);
+search_tree_tests!(b_tree, super::BTree<i32, 4>);
+#[cfg(feature = "std")]
+search_tree_tests!(persistent, super::PersistentRedBlackTree<i32>);
+search_tree_tests!(raw, super::RawRedBlackTree<i32>);
+search_tree_tests!(raw_bst, super::RawBinarySearchTree<i32>);
+search_tree_tests!(static_tree, super::StaticRedBlackTree<i32, 128>);
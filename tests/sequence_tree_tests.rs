@@ -0,0 +1,169 @@
+#![allow(clippy::bool_assert_comparison)]
+#![cfg(feature = "std")]
+
+mod common;
+
+use common::scrambled_values;
+use trees::sequence_tree::SequenceTree;
+
+fn verify_sequence(seq: &SequenceTree<i32>, expected_vals: &[i32]) {
+    assert_eq!(
+        seq.get_size(),
+        expected_vals.len(),
+        "Number of nodes in tree doesn't match the expected number"
+    );
+    assert_eq!(seq.iter().copied().collect::<Vec<_>>(), expected_vals);
+    assert_eq!(seq.is_valid(), true);
+}
+
+fn sequence_from(values: &[i32]) -> SequenceTree<i32> {
+    let mut seq = SequenceTree::new();
+    for val in values {
+        seq.push(*val);
+    }
+    seq
+}
+
+#[test]
+fn insert_at() {
+    let mut seq = SequenceTree::new();
+    seq.insert_at(0, 3).unwrap();
+    seq.insert_at(0, 1).unwrap();
+    seq.insert_at(1, 2).unwrap();
+    seq.insert_at(3, 5).unwrap();
+    seq.insert_at(3, 4).unwrap();
+
+    verify_sequence(&seq, &[1, 2, 3, 4, 5]);
+    assert!(seq.insert_at(6, 7).is_err());
+    assert_eq!(seq.get_size(), 5);
+}
+
+#[test]
+fn get() {
+    let mut seq = sequence_from(&[10, 20, 30]);
+
+    assert_eq!(seq.get(0), Some(&10));
+    assert_eq!(seq.get(2), Some(&30));
+    assert_eq!(seq.get(3), None);
+
+    *seq.get_mut(1).unwrap() = 25;
+    verify_sequence(&seq, &[10, 25, 30]);
+}
+
+#[test]
+fn remove_at() {
+    let mut seq = sequence_from(&[1, 2, 3, 4, 5, 6]);
+
+    assert_eq!(seq.remove_at(0).unwrap(), 1);
+    assert_eq!(seq.remove_at(2).unwrap(), 4);
+    assert_eq!(seq.remove_at(3).unwrap(), 6);
+    assert!(seq.remove_at(3).is_err());
+
+    verify_sequence(&seq, &[2, 3, 5]);
+}
+
+#[test]
+fn matches_vec() {
+    let mut seq = SequenceTree::new();
+    let mut expected = Vec::new();
+
+    // Values aren't sorted, so the tree can only find its way by position
+    for (step, val) in scrambled_values(500).into_iter().enumerate() {
+        let pos = val as usize % (expected.len() + 1);
+        seq.insert_at(pos, val).unwrap();
+        expected.insert(pos, val);
+
+        if step % 3 == 2 {
+            let pos = (val as usize * 31) % expected.len();
+            assert_eq!(seq.remove_at(pos).unwrap(), expected.remove(pos));
+        }
+    }
+
+    verify_sequence(&seq, &expected);
+    for (pos, val) in expected.iter().enumerate() {
+        assert_eq!(seq.get(pos), Some(val));
+    }
+}
+
+#[test]
+fn split_at() {
+    let values = scrambled_values(200);
+
+    for pos in [0, 1, 57, 100, 199, 200].iter() {
+        let mut seq = sequence_from(&values);
+        let back = seq.split_at(*pos).unwrap();
+
+        verify_sequence(&seq, &values[..*pos]);
+        verify_sequence(&back, &values[*pos..]);
+    }
+
+    let mut seq = sequence_from(&values);
+    assert!(seq.split_at(201).is_err());
+    verify_sequence(&seq, &values);
+}
+
+#[test]
+fn concat() {
+    let values = scrambled_values(300);
+
+    for pos in [0, 1, 20, 150, 280, 300].iter() {
+        let mut front = sequence_from(&values[..*pos]);
+        let mut back = sequence_from(&values[*pos..]);
+        front.concat(&mut back);
+
+        verify_sequence(&front, &values);
+        verify_sequence(&back, &[]);
+    }
+}
+
+#[test]
+fn split_and_concat() {
+    // Cuts the sequence into pieces and glues them back together in a different order
+    let values = scrambled_values(150);
+    let mut seq = sequence_from(&values);
+
+    let mut back = seq.split_at(100).unwrap();
+    let mut middle = seq.split_at(40).unwrap();
+    back.concat(&mut middle);
+    back.concat(&mut seq);
+
+    let expected: Vec<i32> = values[100..]
+        .iter()
+        .chain(values[40..100].iter())
+        .chain(values[..40].iter())
+        .copied()
+        .collect();
+    verify_sequence(&back, &expected);
+
+    back.insert_at(75, -1).unwrap();
+    assert_eq!(back.get(75), Some(&-1));
+    assert_eq!(back.is_valid(), true);
+}
+
+#[test]
+fn default_sequence_tracks_positions() {
+    let mut seq = SequenceTree::default();
+    for val in 0..5 {
+        seq.push(val);
+    }
+
+    assert_eq!(seq.get(2), Some(&2));
+    verify_sequence(&seq, &[0, 1, 2, 3, 4]);
+}
+
+#[test]
+fn values_need_no_ordering() {
+    // Positions alone decide where values go, so they don't have to be comparable
+    struct Unordered(i32);
+
+    let mut seq = SequenceTree::new();
+    for val in 0..50 {
+        seq.insert_at(0, Unordered(val)).unwrap();
+    }
+
+    assert_eq!(seq.get(0).map(|item| item.0), Some(49));
+    assert_eq!(seq.remove_at(49).unwrap().0, 0);
+    assert_eq!(seq.dfs_iter().count(), 49);
+    assert_eq!(seq.bfs_iter().count(), 49);
+    assert_eq!(seq.is_valid(), true);
+}
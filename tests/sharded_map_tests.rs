@@ -0,0 +1,174 @@
+#![allow(clippy::bool_assert_comparison)]
+#![cfg(feature = "std")]
+
+mod common;
+
+use common::scrambled_values;
+use std::thread;
+use trees::sharded_map::ShardedMap;
+
+const THREAD_COUNT: i32 = 8;
+
+#[test]
+fn insert_get_remove() {
+    let map = ShardedMap::new();
+    assert_eq!(map.is_empty(), true);
+
+    assert_eq!(map.insert(3, 30).unwrap(), None);
+    assert_eq!(map.insert(1, 10).unwrap(), None);
+    assert_eq!(map.insert(3, 33).unwrap(), Some(30));
+
+    assert_eq!(map.len(), 2);
+    assert_eq!(map.get(&3), Some(33));
+    assert_eq!(map.get(&2), None);
+    assert_eq!(map.contains_key(&1), true);
+
+    assert_eq!(map.remove(&1).unwrap(), 10);
+    assert!(map.remove(&1).is_err());
+    assert_eq!(map.len(), 1);
+    assert_eq!(map.is_valid(), true);
+}
+
+#[test]
+fn shards_split_as_they_grow() {
+    let map = ShardedMap::with_max_shard_size(16);
+    for val in scrambled_values(500) {
+        map.insert(val, val * 2).unwrap();
+    }
+
+    // Every split leaves two shards of at least eight entries
+    assert!(map.shard_count() > 500 / 17);
+    assert!(map.shard_count() <= 500 / 8);
+    assert_eq!(map.is_valid(), true);
+    assert_eq!(map.len(), 500);
+
+    let entries: Vec<(i32, i32)> = map.iter().collect();
+    assert_eq!(
+        entries,
+        (0..500).map(|val| (val, val * 2)).collect::<Vec<_>>()
+    );
+    assert!((0..500).all(|val| map.get(&val) == Some(val * 2)));
+}
+
+#[test]
+fn split_points() {
+    let map = ShardedMap::with_split_points(vec![200, 100, 300, 100], 1000).unwrap();
+    assert_eq!(map.shard_count(), 4);
+
+    for val in scrambled_values(400) {
+        map.insert(val, -val).unwrap();
+    }
+
+    assert_eq!(map.shard_count(), 4);
+    assert_eq!(map.is_valid(), true);
+
+    // Iteration crosses shard boundaries, including empty shards
+    for val in 100..300 {
+        map.remove(&val).unwrap();
+    }
+    let keys: Vec<i32> = map.iter().map(|(key, _)| key).collect();
+    assert_eq!(keys, (0..100).chain(300..400).collect::<Vec<_>>());
+}
+
+#[test]
+fn incomparable_keys() {
+    assert!(ShardedMap::<f64, i32>::with_split_points(vec![1.0, f64::NAN, 2.0], 1000).is_err());
+    assert!(ShardedMap::<f64, i32>::with_split_points(vec![f64::NAN], 1000).is_err());
+
+    let map = ShardedMap::with_split_points(vec![2.5, -1.0], 1000).unwrap();
+    map.insert(0.0, 0).unwrap();
+    map.insert(3.0, 3).unwrap();
+    assert_eq!(map.shard_count(), 3);
+    assert_eq!(map.is_valid(), true);
+
+    // NaN keys would land in the same shard as duplicates and could become split points
+    let map = ShardedMap::with_max_shard_size(2);
+    for val in 0..40 {
+        assert!(map.insert(f64::NAN, val).is_err());
+        map.insert(val as f64, val).unwrap();
+    }
+    assert_eq!(map.len(), 40);
+    assert_eq!(map.contains_key(&f64::NAN), false);
+    assert!(map.remove(&f64::NAN).is_err());
+    assert_eq!(map.is_valid(), true);
+}
+
+#[test]
+fn concurrent_writers() {
+    let map = ShardedMap::with_max_shard_size(64);
+
+    thread::scope(|scope| {
+        for thread_num in 0..THREAD_COUNT {
+            let map = &map;
+            scope.spawn(move || {
+                let keys: Vec<i32> = scrambled_values(1000)
+                    .into_iter()
+                    .map(|val| val * THREAD_COUNT + thread_num)
+                    .collect();
+
+                for key in keys.iter() {
+                    map.insert(*key, thread_num).unwrap();
+                }
+                for key in keys.iter().filter(|key| *key % 3 == 0) {
+                    map.remove(key).unwrap();
+                }
+            });
+        }
+    });
+
+    let expected: Vec<i32> = (0..1000 * THREAD_COUNT)
+        .filter(|key| key % 3 != 0)
+        .collect();
+    assert_eq!(map.is_valid(), true);
+    assert_eq!(map.len(), expected.len());
+    assert!(map.shard_count() > 1);
+
+    let entries: Vec<(i32, i32)> = map.iter().collect();
+    assert_eq!(
+        entries.iter().map(|(key, _)| *key).collect::<Vec<_>>(),
+        expected
+    );
+    assert!(entries.iter().all(|(key, val)| key % THREAD_COUNT == *val));
+}
+
+#[test]
+fn iterate_while_writing() {
+    // Even keys are there from the start and never touched, while the writers add odd
+    //  keys and split shards underneath the readers
+    let map = ShardedMap::with_max_shard_size(32);
+    for val in scrambled_values(2000)
+        .into_iter()
+        .filter(|val| val % 2 == 0)
+    {
+        map.insert(val, true).unwrap();
+    }
+
+    thread::scope(|scope| {
+        for thread_num in 0..THREAD_COUNT / 2 {
+            let map = &map;
+            scope.spawn(move || {
+                for val in (0..2000).filter(|val| val % 2 == 1) {
+                    if (val / 2) % (THREAD_COUNT / 2) == thread_num {
+                        map.insert(val, true).unwrap();
+                    }
+                }
+            });
+        }
+
+        for _ in 0..THREAD_COUNT / 2 {
+            let map = &map;
+            scope.spawn(move || {
+                for _ in 0..10 {
+                    let keys: Vec<i32> = map.iter().map(|(key, _)| key).collect();
+                    assert!(keys.windows(2).all(|pair| pair[0] < pair[1]));
+
+                    let even_keys: Vec<i32> = keys.into_iter().filter(|key| key % 2 == 0).collect();
+                    assert_eq!(even_keys, (0..2000).step_by(2).collect::<Vec<_>>());
+                }
+            });
+        }
+    });
+
+    assert_eq!(map.is_valid(), true);
+    assert_eq!(map.len(), 2000);
+}
@@ -0,0 +1,102 @@
+#![allow(clippy::bool_assert_comparison)]
+#![cfg(feature = "alloc")]
+
+use trees::splay_tree::SplayTree;
+
+fn verify_tree_bfs(bst: &mut SplayTree<i32>, expected_vals: Vec<i32>) {
+    assert_eq!(
+        bst.get_size(),
+        expected_vals.len(),
+        "Number of nodes in tree doesn't match the expected number"
+    );
+
+    let mut b_tree_iter = bst.bfs_iter();
+    for val in expected_vals {
+        let node = b_tree_iter.next();
+
+        assert_eq!(node, Some(&val), "Nodes don't match");
+    }
+    assert_eq!(b_tree_iter.next(), None);
+}
+
+fn tree_from(vals: &[i32]) -> SplayTree<i32> {
+    let mut b_tree = SplayTree::new();
+    for val in vals {
+        b_tree.insert(*val);
+    }
+    b_tree
+}
+
+#[test]
+fn test_instantiation() {
+    let _b_tree: SplayTree<i32> = SplayTree::new();
+}
+
+#[test]
+fn insert_splays_new_value_to_root() {
+    let mut b_tree = tree_from(&[1, 2, 3, 4, 5]);
+
+    // Ascending inserts always splay the new maximum up over a left-leaning spine
+    verify_tree_bfs(&mut b_tree, vec![5, 4, 3, 2, 1]);
+}
+
+#[test]
+fn find_splays_with_zig_zig() {
+    let mut b_tree = tree_from(&[1, 2, 3, 4, 5]);
+
+    assert_eq!(b_tree.find(&1), Some(&1));
+    verify_tree_bfs(&mut b_tree, vec![1, 4, 2, 5, 3]);
+
+    // Found values stay at the root for quick repeat access
+    assert_eq!(b_tree.find(&1), Some(&1));
+    assert_eq!(b_tree.bfs_iter().next(), Some(&1));
+}
+
+#[test]
+fn find_splays_with_zig_zag() {
+    let mut b_tree = tree_from(&[50, 10, 30]);
+    verify_tree_bfs(&mut b_tree, vec![30, 10, 50]);
+
+    b_tree.insert(20);
+    assert_eq!(b_tree.find(&30), Some(&30));
+    verify_tree_bfs(&mut b_tree, vec![30, 20, 50, 10]);
+}
+
+#[test]
+fn find_missing_value_splays_last_visited_node() {
+    let mut b_tree = tree_from(&[10, 20, 30, 40]);
+
+    assert_eq!(b_tree.find(&25), None);
+    assert_eq!(b_tree.bfs_iter().next(), Some(&20));
+
+    let mut empty_tree: SplayTree<i32> = SplayTree::new();
+    assert_eq!(empty_tree.find(&25), None);
+}
+
+#[test]
+fn peek_leaves_tree_untouched() {
+    let mut b_tree = tree_from(&[1, 2, 3, 4, 5]);
+
+    assert_eq!(b_tree.peek(&1), Some(&1));
+    assert_eq!(b_tree.peek(&6), None);
+    assert_eq!(b_tree.contains(&3), true);
+    verify_tree_bfs(&mut b_tree, vec![5, 4, 3, 2, 1]);
+}
+
+#[test]
+fn remove() {
+    let mut b_tree = tree_from(&[1, 2, 3, 4, 5]);
+
+    assert_eq!(b_tree.remove(&9).is_err(), true);
+
+    // 3 is splayed to the root, then 2 (the largest on its left) takes its place
+    assert_eq!(b_tree.remove(&3).is_ok(), true);
+    verify_tree_bfs(&mut b_tree, vec![2, 1, 4, 5]);
+
+    assert_eq!(b_tree.remove(&1).is_ok(), true);
+    assert_eq!(b_tree.remove(&2).is_ok(), true);
+    assert_eq!(b_tree.remove(&4).is_ok(), true);
+    assert_eq!(b_tree.remove(&5).is_ok(), true);
+    assert_eq!(b_tree.get_size(), 0);
+    assert_eq!(b_tree.bfs_iter().next(), None);
+}
@@ -0,0 +1,144 @@
+#![allow(clippy::bool_assert_comparison)]
+
+mod common;
+
+use common::scrambled_values;
+#[cfg(feature = "std")]
+use trees::red_black_tree::RedBlackTree;
+use trees::static_tree::StaticRedBlackTree;
+
+fn verify_tree<const N: usize>(tree: &StaticRedBlackTree<i32, N>, expected_vals: &[i32]) {
+    assert_eq!(
+        tree.get_size(),
+        expected_vals.len(),
+        "Number of nodes in tree doesn't match the expected number"
+    );
+    assert_eq!(tree.iter().copied().collect::<Vec<_>>(), expected_vals);
+    assert_eq!(tree.is_valid(), true);
+}
+
+#[test]
+fn insert_and_remove() {
+    let mut tree: StaticRedBlackTree<i32, 16> = StaticRedBlackTree::new();
+    verify_tree(&tree, &[]);
+
+    for val in [5, 3, 8, 1, 4, 7, 9, 2, 6].iter() {
+        tree.insert(*val).unwrap();
+    }
+    verify_tree(&tree, &[1, 2, 3, 4, 5, 6, 7, 8, 9]);
+
+    assert_eq!(tree.remove(&5).is_ok(), true);
+    assert_eq!(tree.remove(&5).is_err(), true);
+    assert_eq!(tree.remove(&1).is_ok(), true);
+    assert_eq!(tree.remove(&9).is_ok(), true);
+    verify_tree(&tree, &[2, 3, 4, 6, 7, 8]);
+    assert_eq!(tree.contains(&4), true);
+    assert_eq!(tree.contains(&5), false);
+}
+
+#[test]
+fn duplicates() {
+    let mut tree: StaticRedBlackTree<i32, 8> = StaticRedBlackTree::new();
+    for val in [3, 1, 3, 2, 3, 1].iter() {
+        tree.insert(*val).unwrap();
+    }
+    verify_tree(&tree, &[1, 1, 2, 3, 3, 3]);
+
+    tree.remove(&3).unwrap();
+    tree.remove(&1).unwrap();
+    verify_tree(&tree, &[1, 2, 3, 3]);
+}
+
+#[test]
+fn capacity_exceeded() {
+    let mut tree: StaticRedBlackTree<i32, 4> = StaticRedBlackTree::new();
+    assert_eq!(tree.capacity(), 4);
+
+    for val in 0..4 {
+        tree.insert(val).unwrap();
+    }
+    assert_eq!(tree.is_full(), true);
+    assert_eq!(tree.insert(4).is_err(), true);
+    verify_tree(&tree, &[0, 1, 2, 3]);
+
+    // Removing a value frees up its slot again
+    tree.remove(&1).unwrap();
+    assert_eq!(tree.is_full(), false);
+    tree.insert(4).unwrap();
+    verify_tree(&tree, &[0, 2, 3, 4]);
+
+    let mut empty: StaticRedBlackTree<i32, 0> = StaticRedBlackTree::new();
+    assert_eq!(empty.insert(0).is_err(), true);
+    verify_tree(&empty, &[]);
+}
+
+#[test]
+fn fill_and_drain() {
+    let mut tree: StaticRedBlackTree<i32, 256> = StaticRedBlackTree::new();
+    for val in scrambled_values(256) {
+        tree.insert(val).unwrap();
+    }
+    verify_tree(&tree, &(0..256).collect::<Vec<_>>());
+
+    for val in (0..256).filter(|val| val % 2 == 0) {
+        tree.remove(&val).unwrap();
+    }
+    verify_tree(
+        &tree,
+        &(0..256).filter(|val| val % 2 == 1).collect::<Vec<_>>(),
+    );
+
+    for val in (0..256).filter(|val| val % 2 == 1).rev() {
+        tree.remove(&val).unwrap();
+    }
+    verify_tree(&tree, &[]);
+}
+
+#[test]
+fn navigation() {
+    let mut tree: StaticRedBlackTree<i32, 64> = StaticRedBlackTree::new();
+    assert_eq!(tree.get_min(), None);
+    assert_eq!(tree.get_max(), None);
+
+    for val in scrambled_values(50).into_iter().map(|val| val * 2) {
+        tree.insert(val).unwrap();
+    }
+
+    assert_eq!(tree.get_min(), Some(&0));
+    assert_eq!(tree.get_max(), Some(&98));
+    assert_eq!(tree.get_successor(&10), Some(&12));
+    assert_eq!(tree.get_successor(&11), Some(&12));
+    assert_eq!(tree.get_successor(&98), None);
+    assert_eq!(tree.get_predecessor(&10), Some(&8));
+    assert_eq!(tree.get_predecessor(&0), None);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn matches_red_black_tree() {
+    let mut tree: StaticRedBlackTree<i32, 512> = StaticRedBlackTree::new();
+    let mut reference = RedBlackTree::new();
+
+    // Values wrap around a small range, so that plenty of removes hit duplicates
+    for (step, val) in scrambled_values(2000).into_iter().enumerate() {
+        let val = val % 300;
+
+        if step % 3 == 0 {
+            assert_eq!(tree.remove(&val).is_ok(), reference.remove(&val).is_ok());
+        } else if !tree.is_full() {
+            tree.insert(val).unwrap();
+            reference.insert(val);
+        }
+
+        if step % 50 == 0 {
+            assert_eq!(tree.is_valid(), true);
+            assert_eq!(
+                tree.iter().collect::<Vec<_>>(),
+                reference.iter().collect::<Vec<_>>()
+            );
+        }
+    }
+
+    assert_eq!(tree.get_size(), reference.get_size());
+    assert_eq!(tree.is_valid(), true);
+}
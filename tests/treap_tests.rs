@@ -0,0 +1,104 @@
+#![allow(clippy::bool_assert_comparison)]
+#![cfg(feature = "std")]
+
+mod common;
+
+use common::scrambled_values;
+use trees::treap::Treap;
+
+fn treap_from(seed: u64, vals: &[i32]) -> Treap<i32> {
+    let mut treap = Treap::with_seed(seed);
+    for val in vals {
+        treap.insert(*val);
+    }
+    treap
+}
+
+#[test]
+fn test_instantiation() {
+    let _treap: Treap<i32> = Treap::new();
+}
+
+#[test]
+fn same_seed_gives_same_shape() {
+    let vals = scrambled_values(200);
+    let first = treap_from(42, &vals);
+    let second = treap_from(42, &vals);
+
+    assert_eq!(first.is_valid(), true);
+    assert_eq!(
+        first.bfs_iter().collect::<Vec<_>>(),
+        second.bfs_iter().collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn sorted_inserts() {
+    let mut treap = Treap::with_seed(7);
+    for val in 0..1000 {
+        treap.insert(val);
+    }
+
+    assert_eq!(treap.is_valid(), true);
+    assert_eq!(
+        treap.iter().copied().collect::<Vec<_>>(),
+        (0..1000).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn remove() {
+    let mut treap = treap_from(3, &scrambled_values(100));
+
+    assert_eq!(treap.remove(&100).is_err(), true);
+    for val in (0..100).step_by(2) {
+        assert_eq!(treap.remove(&val).is_ok(), true);
+        assert_eq!(treap.is_valid(), true);
+    }
+
+    assert_eq!(
+        treap.iter().copied().collect::<Vec<_>>(),
+        (1..100).step_by(2).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn split_off_and_append() {
+    let mut treap = treap_from(11, &scrambled_values(100));
+
+    let mut split_treap = treap.split_off(&40);
+    assert_eq!(treap.is_valid(), true);
+    assert_eq!(split_treap.is_valid(), true);
+    assert_eq!(
+        treap.iter().copied().collect::<Vec<_>>(),
+        (0..40).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        split_treap.iter().copied().collect::<Vec<_>>(),
+        (40..100).collect::<Vec<_>>()
+    );
+
+    treap.append(&mut split_treap);
+    assert_eq!(treap.is_valid(), true);
+    assert_eq!(split_treap.get_size(), 0);
+    assert_eq!(
+        treap.iter().copied().collect::<Vec<_>>(),
+        (0..100).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn append_overlapping_treaps() {
+    let evens: Vec<i32> = (0..100).map(|val| val * 2).collect();
+    let odds: Vec<i32> = (0..50).map(|val| val * 2 + 1).collect();
+    let mut treap = treap_from(5, &odds);
+    let mut other = treap_from(6, &evens);
+
+    treap.append(&mut other);
+    assert_eq!(treap.is_valid(), true);
+    assert_eq!(other.get_size(), 0);
+    assert_eq!(
+        treap.iter().copied().collect::<Vec<_>>(),
+        (0..100).chain((100..200).step_by(2)).collect::<Vec<_>>()
+    );
+}
@@ -0,0 +1,211 @@
+#![allow(clippy::bool_assert_comparison)]
+#![cfg(feature = "std")]
+
+mod common;
+
+use common::scrambled_values;
+use trees::weight_balanced_tree::WeightBalancedTree;
+
+fn tree_from(vals: impl IntoIterator<Item = i32>) -> WeightBalancedTree<i32> {
+    let mut b_tree = WeightBalancedTree::new();
+    for val in vals {
+        b_tree.insert(val);
+    }
+    b_tree
+}
+
+fn values(b_tree: &WeightBalancedTree<i32>) -> Vec<i32> {
+    b_tree.iter().copied().collect()
+}
+
+#[test]
+fn test_instantiation() {
+    let _b_tree: WeightBalancedTree<i32> = WeightBalancedTree::new();
+}
+
+#[test]
+fn ascending_inserts() {
+    let mut b_tree = WeightBalancedTree::new();
+    for val in 0..500 {
+        b_tree.insert(val);
+        assert_eq!(b_tree.is_valid(), true);
+    }
+
+    assert_eq!(values(&b_tree), (0..500).collect::<Vec<_>>());
+}
+
+#[test]
+fn duplicates_are_kept() {
+    let mut b_tree = tree_from(vec![5, 3, 5, 8, 5, 3]);
+
+    assert_eq!(b_tree.get_size(), 6);
+    assert_eq!(b_tree.is_valid(), true);
+    assert_eq!(values(&b_tree), vec![3, 3, 5, 5, 5, 8]);
+    assert_eq!(b_tree.rank(&5), 2);
+    assert_eq!(b_tree.select(4), Some(&5));
+
+    assert_eq!(b_tree.remove(&5).is_ok(), true);
+    assert_eq!(b_tree.is_valid(), true);
+    assert_eq!(values(&b_tree), vec![3, 3, 5, 5, 8]);
+}
+
+#[test]
+fn remove_keeps_tree_valid() {
+    let mut b_tree = tree_from(scrambled_values(200));
+
+    assert_eq!(b_tree.remove(&200).is_err(), true);
+    for val in scrambled_values(200).into_iter().filter(|val| val % 3 != 0) {
+        assert_eq!(b_tree.remove(&val).is_ok(), true);
+        assert_eq!(b_tree.is_valid(), true);
+    }
+
+    assert_eq!(
+        values(&b_tree),
+        (0..200).filter(|val| val % 3 == 0).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn select_and_rank() {
+    let b_tree = tree_from(scrambled_values(100).into_iter().map(|val| val * 2));
+
+    assert_eq!(b_tree.select(0), Some(&0));
+    assert_eq!(b_tree.select(42), Some(&84));
+    assert_eq!(b_tree.select(99), Some(&198));
+    assert_eq!(b_tree.select(100), None);
+
+    assert_eq!(b_tree.rank(&0), 0);
+    assert_eq!(b_tree.rank(&84), 42);
+    assert_eq!(b_tree.rank(&85), 43);
+    assert_eq!(b_tree.rank(&1000), 100);
+}
+
+#[test]
+fn union_with() {
+    let mut b_tree = tree_from((0..300).filter(|val| val % 2 == 0));
+    let mut other = tree_from((0..100).filter(|val| val % 3 == 0));
+
+    b_tree.union_with(&mut other);
+    assert_eq!(b_tree.is_valid(), true);
+    assert_eq!(other.get_size(), 0);
+    assert_eq!(
+        values(&b_tree),
+        (0..300)
+            .filter(|val| val % 2 == 0 || (*val < 100 && val % 3 == 0))
+            .collect::<Vec<_>>()
+    );
+
+    // The smaller tree on the left works too
+    let mut small = tree_from(vec![-1, 1, 500]);
+    small.union_with(&mut b_tree);
+    assert_eq!(small.is_valid(), true);
+    assert_eq!(small.get_size(), 170);
+    assert_eq!(small.select(0), Some(&-1));
+    assert_eq!(small.select(169), Some(&500));
+}
+
+#[test]
+fn intersect_with() {
+    let mut b_tree = tree_from((0..300).filter(|val| val % 2 == 0));
+    let mut other = tree_from((0..100).filter(|val| val % 3 == 0));
+
+    b_tree.intersect_with(&mut other);
+    assert_eq!(b_tree.is_valid(), true);
+    assert_eq!(other.get_size(), 0);
+    assert_eq!(values(&b_tree), (0..100).step_by(6).collect::<Vec<_>>());
+
+    b_tree.intersect_with(&mut WeightBalancedTree::new());
+    assert_eq!(b_tree.get_size(), 0);
+}
+
+#[test]
+fn difference_with() {
+    let mut b_tree = tree_from(0..50);
+    let mut other = tree_from((0..300).filter(|val| val % 2 == 0));
+
+    // Removing a larger tree's values from a smaller tree
+    b_tree.difference_with(&mut other);
+    assert_eq!(b_tree.is_valid(), true);
+    assert_eq!(other.get_size(), 0);
+    assert_eq!(values(&b_tree), (1..50).step_by(2).collect::<Vec<_>>());
+
+    let mut other = tree_from(vec![1, 3, 5, 100]);
+    b_tree.difference_with(&mut other);
+    assert_eq!(b_tree.is_valid(), true);
+    assert_eq!(values(&b_tree), (7..50).step_by(2).collect::<Vec<_>>());
+}
+
+// Equal values pair up one to one, just like the lazy set operations on `RedBlackTree`
+#[test]
+fn set_operations_with_duplicates() {
+    let first_vals = vec![1, 2, 2, 2, 5, 7, 7, 9];
+    let second_vals = vec![2, 2, 5, 5, 5, 7, 8];
+
+    let mut union = tree_from(first_vals.clone());
+    union.union_with(&mut tree_from(second_vals.clone()));
+    assert_eq!(union.is_valid(), true);
+    assert_eq!(values(&union), vec![1, 2, 2, 2, 5, 5, 5, 7, 7, 8, 9]);
+
+    let mut intersection = tree_from(first_vals.clone());
+    intersection.intersect_with(&mut tree_from(second_vals.clone()));
+    assert_eq!(intersection.is_valid(), true);
+    assert_eq!(values(&intersection), vec![2, 2, 5, 7]);
+
+    let mut difference = tree_from(first_vals);
+    difference.difference_with(&mut tree_from(second_vals));
+    assert_eq!(difference.is_valid(), true);
+    assert_eq!(values(&difference), vec![1, 2, 7, 9]);
+}
+
+// Compares every operation against a sorted merge over larger trees with long runs of
+//  equal values, which puts equal values on both sides of plenty of roots
+#[test]
+fn set_operations_match_sorted_merge() {
+    let first_vals: Vec<i32> = scrambled_values(400)
+        .into_iter()
+        .map(|val| val % 50)
+        .collect();
+    let second_vals: Vec<i32> = scrambled_values(150)
+        .into_iter()
+        .map(|val| val % 70)
+        .collect();
+
+    let count = |vals: &[i32], val: i32| vals.iter().filter(|other| **other == val).count();
+    let expected = |pick: fn(usize, usize) -> usize| {
+        (0..70)
+            .flat_map(|val| {
+                let repeats = pick(count(&first_vals, val), count(&second_vals, val));
+                std::iter::repeat_n(val, repeats)
+            })
+            .collect::<Vec<_>>()
+    };
+
+    let mut union = tree_from(first_vals.clone());
+    union.union_with(&mut tree_from(second_vals.clone()));
+    assert_eq!(union.is_valid(), true);
+    assert_eq!(values(&union), expected(usize::max));
+
+    let mut intersection = tree_from(first_vals.clone());
+    intersection.intersect_with(&mut tree_from(second_vals.clone()));
+    assert_eq!(intersection.is_valid(), true);
+    assert_eq!(values(&intersection), expected(usize::min));
+
+    let mut difference = tree_from(first_vals.clone());
+    difference.difference_with(&mut tree_from(second_vals.clone()));
+    assert_eq!(difference.is_valid(), true);
+    assert_eq!(values(&difference), expected(usize::saturating_sub));
+
+    // The smaller tree's nodes get moved over, whichever side it's on
+    let mut reverse_difference = tree_from(second_vals.clone());
+    reverse_difference.difference_with(&mut tree_from(first_vals.clone()));
+    assert_eq!(reverse_difference.is_valid(), true);
+    assert_eq!(
+        values(&reverse_difference),
+        (0..70)
+            .flat_map(|val| {
+                let repeats = count(&second_vals, val).saturating_sub(count(&first_vals, val));
+                std::iter::repeat_n(val, repeats)
+            })
+            .collect::<Vec<_>>()
+    );
+}